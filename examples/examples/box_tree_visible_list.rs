@@ -43,7 +43,7 @@ fn main() {
     // Simulate a few scroll positions by changing the viewport rectangle
     for scroll in [0.0, 30.0, 200.0, 600.0] {
         let viewport = Rect::new(0.0, scroll, WIDTH, scroll + 100.0);
-        let visible: Vec<_> = tree.intersect_rect(viewport, filter).collect();
+        let visible: Vec<_> = tree.visible_in_paint_order(viewport, filter).collect();
         let indices: Vec<_> = visible
             .into_iter()
             .filter_map(|id| ids.iter().position(|x| *x == id))
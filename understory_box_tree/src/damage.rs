@@ -6,6 +6,57 @@
 use alloc::vec::Vec;
 use kurbo::Rect;
 
+use crate::types::NodeId;
+
+bitflags::bitflags! {
+    /// Kinds of change recorded for a node in a [`NodeChange`].
+    ///
+    /// A node may report several kinds at once, for example a node that was
+    /// both moved and re-flagged in the same commit.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct ChangeKind: u8 {
+        /// Node was inserted since the previous commit.
+        const ADDED    = 0b0000_0001;
+        /// Node was removed since the previous commit.
+        const REMOVED  = 0b0000_0010;
+        /// Node's world-space bounds changed (moved, resized, or re-transformed).
+        const MOVED    = 0b0000_0100;
+        /// Node's own or inherited clip changed.
+        const CLIPPED  = 0b0000_1000;
+        /// Node's [`NodeFlags`](crate::NodeFlags) changed.
+        const FLAGS    = 0b0001_0000;
+        /// Node's `z_index` or position in the tree changed.
+        const Z_ORDER  = 0b0010_0000;
+    }
+}
+
+/// A structured change to a single node, reported by [`crate::Tree::commit`] when
+/// change tracking is enabled with [`crate::Tree::set_change_tracking`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NodeChange {
+    /// The changed node. For [`ChangeKind::REMOVED`], this identifier is already stale.
+    pub id: NodeId,
+    /// What changed.
+    pub kind: ChangeKind,
+    /// World-space bounds as of the previous commit; `None` for added nodes.
+    pub old_bounds: Option<Rect>,
+    /// World-space bounds as of this commit; `None` for removed nodes.
+    pub new_bounds: Option<Rect>,
+}
+
+/// Spatial-index damage for one indexed layer, reported by [`crate::Tree::commit`].
+///
+/// See [`crate::Tree::set_layer`] for how layers partition the index.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayerDamage {
+    /// Root of the layer, or `None` for the base layer holding nodes outside any layer.
+    ///
+    /// For a layer whose root was removed, this identifier is already stale.
+    pub layer: Option<NodeId>,
+    /// Union of the world-space AABBs added, removed, or moved in this layer's index.
+    pub rect: Rect,
+}
+
 /// A batched set of changes derived from [`crate::Tree::commit`].
 ///
 /// `Damage` is intentionally coarse: it summarizes regions that may have
@@ -21,6 +72,18 @@ pub struct Damage {
     /// rectangles changed, plus any spatial-index damage. Callers can use
     /// this to bound paint or query traversals.
     pub dirty_rects: Vec<Rect>,
+    /// Per-node changes since the previous commit.
+    ///
+    /// Only populated when change tracking is enabled with
+    /// [`crate::Tree::set_change_tracking`]; empty otherwise. Removals come first,
+    /// followed by the remaining changes in tree order.
+    pub changes: Vec<NodeChange>,
+    /// Spatial-index damage per indexed layer.
+    ///
+    /// Only layers whose index changed are listed: the base layer first, then live
+    /// layers in the order they were created, then layers removed since the previous
+    /// commit. Each rect is also included in `dirty_rects`.
+    pub layers: Vec<LayerDamage>,
 }
 
 impl Damage {
@@ -30,4 +93,108 @@ impl Damage {
         let first = it.next()?;
         Some(it.fold(first, |acc, r| acc.union(r)))
     }
+
+    /// Merge the damage rects into at most `max_regions` non-overlapping rectangles.
+    ///
+    /// Rects with zero area are dropped. Overlapping rects are merged into their union
+    /// until none overlap; then, while more than `max_regions` remain, the pair whose
+    /// union adds the least extra area is merged. The result covers every input rect.
+    /// Rects that only share an edge are not considered overlapping. `max_regions` is
+    /// treated as at least 1.
+    ///
+    /// This is quadratic or worse in the number of rects, and intended for the modest
+    /// counts produced by a single commit.
+    pub fn coalesced(&self, max_regions: usize) -> Vec<Rect> {
+        let max_regions = max_regions.max(1);
+        let mut regions: Vec<Rect> = self
+            .dirty_rects
+            .iter()
+            .copied()
+            .filter(|r| r.area() > 0.0)
+            .collect();
+        loop {
+            merge_overlapping(&mut regions);
+            if regions.len() <= max_regions {
+                return regions;
+            }
+            let mut best = (0, 1, f64::INFINITY);
+            for i in 0..regions.len() {
+                for j in i + 1..regions.len() {
+                    let (a, b) = (regions[i], regions[j]);
+                    let cost = a.union(b).area() - a.area() - b.area();
+                    if cost < best.2 {
+                        best = (i, j, cost);
+                    }
+                }
+            }
+            let (i, j, _) = best;
+            let other = regions.swap_remove(j);
+            regions[i] = regions[i].union(other);
+        }
+    }
+}
+
+/// Repeatedly replace overlapping pairs with their union until no two rects overlap.
+fn merge_overlapping(regions: &mut Vec<Rect>) {
+    let mut i = 0;
+    while i < regions.len() {
+        let mut merged = false;
+        let mut j = i + 1;
+        while j < regions.len() {
+            if regions[i].intersect(regions[j]).area() > 0.0 {
+                let other = regions.swap_remove(j);
+                regions[i] = regions[i].union(other);
+                merged = true;
+            } else {
+                j += 1;
+            }
+        }
+        // A grown rect may now overlap earlier ones, so rescan from the start.
+        if merged {
+            i = 0;
+        } else {
+            i += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coalesced_regions_are_bounded_and_disjoint() {
+        let damage = Damage {
+            dirty_rects: alloc::vec![
+                Rect::new(0.0, 0.0, 10.0, 10.0),
+                Rect::new(5.0, 5.0, 15.0, 15.0),
+                Rect::new(100.0, 0.0, 110.0, 10.0),
+                Rect::new(120.0, 0.0, 130.0, 10.0),
+                Rect::new(0.0, 100.0, 10.0, 110.0),
+                Rect::new(50.0, 50.0, 50.0, 60.0),
+            ],
+            ..Damage::default()
+        };
+
+        // The overlapping pair merges; the zero-area rect is dropped.
+        let regions = damage.coalesced(8);
+        assert_eq!(regions.len(), 4);
+        assert!(regions.contains(&Rect::new(0.0, 0.0, 15.0, 15.0)));
+
+        // Limiting the count merges the cheapest pair: the two nearby rects on the right.
+        let regions = damage.coalesced(3);
+        assert_eq!(regions.len(), 3);
+        assert!(regions.contains(&Rect::new(100.0, 0.0, 130.0, 10.0)));
+
+        let regions = damage.coalesced(0);
+        assert_eq!(regions, alloc::vec![damage.union_rect().unwrap()]);
+        for r in &damage.dirty_rects {
+            if r.area() > 0.0 {
+                assert!(
+                    regions.iter().any(|c| c.union(*r) == *c),
+                    "must cover inputs"
+                );
+            }
+        }
+    }
 }
@@ -30,9 +30,12 @@
 //! Upstream code is expected to compute positions and sizes using whatever layout system you choose and then update this tree with the resulting world-space boxes, transforms, optional clips, and z-order.
 //! Think of this as a scene and spatial index, not a layout system.
 //!
-//! This crate also does not model stacking contexts, opacity, or blend modes. It provides a single
-//! global z ordering (`z_index`) over boxes plus hit-testing and visibility queries. Higher-level
-//! code is expected to introduce groups, stacking semantics, and paint order if needed.
+//! This crate also does not model opacity or blend modes. By default, `z_index` is a single global
+//! ordering over boxes, used by hit testing, visibility queries, and a paint order (`z_index`, then
+//! tree order) for feeding a display list. A node flagged with [`NodeFlags::STACKING_CONTEXT`] scopes
+//! its descendants' `z_index` values: the subtree is ordered as a unit by the node's own `z_index`,
+//! so a child of a dialog cannot escape above a sibling popover. Higher-level code is expected to
+//! introduce groups and compositing if needed.
 //!
 //! ## Integration with Understory Index
 //!
@@ -49,25 +52,57 @@
 //! [`understory_index::backends::BvhF32`]/[`understory_index::backends::BvhF64`]/[`understory_index::backends::BvhI64`]
 //! for details.
 //!
+//! The index can be partitioned: [`Tree::set_layer`] makes a node the root of an indexed layer with its
+//! own backend instance, so a large map, a few overlays, and a drag layer do not churn each other's index.
+//! Queries combine all layers and still rank results by `z_index`, and [`Tree::commit`] reports index
+//! damage per layer in [`Damage::layers`].
+//!
+//! ## Precise hit testing
+//!
+//! Nodes may optionally carry a hit shape: any [`PreciseHitTest`](understory_precise_hit::PreciseHitTest)
+//! in the node's local coordinates, such as a circle, rounded rect, or path.
+//! [`Tree::hit_test_point_precise`] uses the spatial index as the broad phase, localizes the point with
+//! the inverse world transform, and tests it against the shape (or `local_bounds` when none is set),
+//! returning the winning [`Hit`] together with its score.
+//!
 //! ## API overview
 //!
 //! - [`Tree`]: container managing nodes and the spatial index synchronization.
-//! - [`LocalNode`]: per-node local data (bounds, transform, optional clip, z, flags).
+//! - [`LocalNode`]: per-node local data (bounds, transform, optional clip, z, flags, scroll offset).
 //!   See [`LocalNode::flags`] for visibility/picking/focusable controls.
-//! - [`NodeFlags`]: visibility, picking, and focusable controls.
+//! - [`NodeFlags`]: visibility, picking, focusable, and stacking context controls, plus host-defined bits
+//!   from [`NodeFlags::custom`].
 //! - [`NodeId`]: generational handle of a node.
-//! - [`QueryFilter`]: restricts hit/intersect results (visible/pickable/focusable).
+//! - [`TieBreakPolicy`]: hit-testing tie-break after equal z and depth, mirroring the responder's policy.
+//! - [`QueryFilter`]: restricts hit/intersect results by all-of, any-of, and none-of flag sets, a subtree
+//!   scope, and an optional predicate closure.
 //!   See [`NodeFlags::VISIBLE`], [`NodeFlags::PICKABLE`], and [`NodeFlags::FOCUSABLE`].
 //!
 //! Key operations:
 //! - [`Tree::insert`](Tree::insert) → [`NodeId`]
 //! - [`Tree::set_local_transform`](Tree::set_local_transform) / [`Tree::set_local_clip`](Tree::set_local_clip) /
 //!   [`Tree::set_local_bounds`](Tree::set_local_bounds) / [`Tree::set_flags`](Tree::set_flags)
-//! - [`Tree::commit`](Tree::commit) → damage summary; updates world data and the spatial index.
+//! - [`Tree::commit`](Tree::commit) → damage summary; updates world data and the spatial index,
+//!   skipping subtrees that did not change.
+//! - [`Tree::set_scroll_offset`](Tree::set_scroll_offset) scrolls a container's descendants without dirtying them.
+//! - [`Tree::set_layer`](Tree::set_layer) / [`Tree::clear_layer`](Tree::clear_layer) partition the spatial index into layers;
+//!   [`Tree::layer_of`](Tree::layer_of) reports which layer indexes a node.
 //! - [`Tree::hit_test_point`](Tree::hit_test_point) and [`Tree::intersect_rect`](Tree::intersect_rect).
+//! - [`Tree::visible_in_paint_order`](Tree::visible_in_paint_order) / [`Tree::paint_order`](Tree::paint_order)
+//!   iterate nodes back to front using sort keys cached by [`Tree::commit`]; [`Tree::paint_rank`](Tree::paint_rank)
+//!   returns a node's position in that order.
+//! - [`Tree::hit_test_point_all`](Tree::hit_test_point_all) returns every node under a point in paint order (back to front);
+//!   [`Tree::set_tie_break`](Tree::set_tie_break) selects the [`TieBreakPolicy`] for equal z and depth.
+//! - [`Tree::set_hit_shape`](Tree::set_hit_shape) / [`Tree::hit_test_point_precise`](Tree::hit_test_point_precise)
+//!   for shape-aware hit testing with a [`HitScore`](understory_precise_hit::HitScore).
+//! - [`Tree::set_attachment`](Tree::set_attachment) / [`Tree::attachment`](Tree::attachment) store typed host data with a node,
+//!   dropped on removal; [`Tree::hit_test_point_with`](Tree::hit_test_point_with) and
+//!   [`Tree::intersect_rect_with`](Tree::intersect_rect_with) filter by it and borrow it in results.
 //! - [`Tree::z_index`](Tree::z_index) exposes the stacking order of a live [`NodeId`].
 //! - [`Tree::parent_of`](Tree::parent_of) returns the parent of a live [`NodeId`].
-//! - [`Tree::flags`](Tree::flags) returns the [`NodeFlags`] of a live [`NodeId`].
+//! - [`Tree::flags`](Tree::flags) returns the [`NodeFlags`] of a live [`NodeId`];
+//!   [`Tree::matches_filter`](Tree::matches_filter) applies a [`QueryFilter`] to it outside of queries.
+//! - [`Tree::is_visible`](Tree::is_visible) reports whether a node is flagged visible and not entirely clipped away.
 //! - [`Tree::world_transform`](Tree::world_transform) / [`Tree::world_bounds`](Tree::world_bounds)
 //!   expose the local→world transform and world-space AABB for a live [`NodeId`].
 //! - [`Tree::children_of`](Tree::children_of) returns the children of a live [`NodeId`].
@@ -77,14 +112,21 @@
 //!
 //! - [`Tree::commit`] batches adds/updates/removals and produces coarse damage (added/removed AABBs and
 //!   old/new pairs for moved nodes). The reported rectangles may overlap and are not a minimal cover,
-//!   but are sufficient to bound a paint traversal in most UIs.
+//!   but are sufficient to bound a paint traversal in most UIs. [`Damage::coalesced`] merges them into a
+//!   bounded number of non-overlapping regions.
+//! - With [`Tree::set_change_tracking`] enabled, [`Damage::changes`] also lists each changed [`NodeId`] as a
+//!   [`NodeChange`] with its [`ChangeKind`] (added, removed, moved, clipped, flags, z-order) and old/new
+//!   world bounds, for renderers and accessibility bridges that need per-node updates.
 //! - World AABBs are loose under rotation/shear and rounded-rect clips are approximated by
-//!   their axis-aligned bounds for acceleration; precise hit-filtering is applied where cheap.
+//!   their axis-aligned bounds for acceleration. At commit, each clipping node stores its clip as a
+//!   world-space quad and every node links to its nearest clipping ancestor, forming a clip chain.
+//!   [`Tree::intersect_rect`] and [`Tree::is_visible`] clip candidates against this chain to filter
+//!   them exactly under rotated clips; hit testing checks the point against each clip in local space.
 //!
 //! ## Examples
 //!
 //! - `examples/basic_box_tree.rs`: builds a trivial tree, commits, and runs a couple of queries.
-//! - `examples/visible_list.rs`: demonstrates using `intersect_rect` to compute a visible set,
+//! - `examples/visible_list.rs`: demonstrates using `visible_in_paint_order` to compute a visible set,
 //!   a building block for virtualization.
 //!
 //! This crate is `no_std` and uses `alloc`.
@@ -98,6 +140,6 @@ mod tree;
 mod types;
 mod util;
 
-pub use damage::Damage;
+pub use damage::{ChangeKind, Damage, LayerDamage, NodeChange};
 pub use tree::{Hit, QueryFilter, Tree};
-pub use types::{LocalNode, NodeFlags, NodeId};
+pub use types::{LocalNode, NodeFlags, NodeId, TieBreakPolicy};
@@ -3,13 +3,18 @@
 
 //! Core tree implementation: structure, updates, queries.
 
-use alloc::{vec, vec::Vec};
-use kurbo::{Affine, Point, Rect, RoundedRect, Shape};
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::any::Any;
+use core::cmp::Ordering;
+use kurbo::{Affine, Point, Rect, RoundedRect, Shape, Vec2};
 use understory_index::{Backend, IndexGeneric, Key as AabbKey, backends::FlatVec};
+use understory_precise_hit::{HitParams, HitScore, PreciseHitTest};
 
-use crate::damage::Damage;
-use crate::types::{LocalNode, NodeFlags, NodeId};
-use crate::util::{rect_to_aabb, transform_rect_bbox};
+use crate::damage::{ChangeKind, Damage, LayerDamage, NodeChange};
+use crate::types::{LocalNode, NodeFlags, NodeId, TieBreakPolicy};
+use crate::util::{
+    clip_polygon, is_axis_aligned, rect_to_aabb, transform_rect_bbox, transform_rect_quad,
+};
 
 /// Top-level region tree.
 ///
@@ -53,7 +58,24 @@ pub struct Tree<B: Backend<f64> = FlatVec<f64>> {
     generations: Vec<u32>,
     pub(crate) free_list: Vec<usize>,
     pub(crate) epoch: u64,
+    /// Index of the base layer: nodes outside any layer created with [`Tree::set_layer`].
     pub(crate) index: IndexGeneric<f64, NodeId, B>,
+    /// Indexed layers, in creation order.
+    layers: Vec<Layer<B>>,
+    /// Index damage of layers removed since the last commit.
+    retired_layers: Vec<LayerDamage>,
+    tie_break: TieBreakPolicy,
+    /// Live nodes in paint order (back to front) as of the last commit.
+    paint_order: Vec<NodeId>,
+    /// Set when structure or z-order changed since the paint order was computed.
+    paint_order_dirty: bool,
+    /// Whether `commit` reports per-node changes in [`Damage::changes`].
+    track_changes: bool,
+    /// Removals recorded since the last commit while tracking changes.
+    removed: Vec<NodeChange>,
+    /// Nodes whose world state the last commit recomputed from local state.
+    #[cfg(test)]
+    recomputed: usize,
 }
 
 impl<B: Backend<f64> + core::fmt::Debug> core::fmt::Debug for Tree<B> {
@@ -66,7 +88,9 @@ impl<B: Backend<f64> + core::fmt::Debug> core::fmt::Debug for Tree<B> {
             .field("nodes_alive", &alive)
             .field("free_list", &free)
             .field("epoch", &self.epoch)
+            .field("tie_break", &self.tie_break)
             .field("index", &self.index)
+            .field("layers", &self.layers.len())
             .finish_non_exhaustive()
     }
 }
@@ -91,23 +115,81 @@ pub struct Hit {
 
 /// Filters applied during hit testing and rectangle intersection.
 ///
-/// Used by [`Tree::hit_test_point`] and [`Tree::intersect_rect`] to restrict
-/// which nodes participate in queries.
-#[derive(Clone, Copy, Debug)]
-pub struct QueryFilter {
+/// Used by [`Tree::hit_test_point`], [`Tree::intersect_rect`], [`Tree::containing_point`], and
+/// the other queries taking a filter to restrict which nodes participate. A node passes when
+/// all of the following hold:
+///
+/// - it has every flag in `required_flags`;
+/// - it has at least one flag in `any_flags`, unless that is empty;
+/// - it has no flag in `excluded_flags`;
+/// - it is `scope` or one of its descendants, if `scope` is set;
+/// - `predicate` returns `true` for it, if set.
+///
+/// Use [`Tree::matches_filter`] to apply a filter outside of queries, for example while
+/// traversing with [`Tree::next_depth_first`].
+///
+/// ```rust
+/// use understory_box_tree::{LocalNode, NodeFlags, NodeId, QueryFilter, Tree};
+///
+/// const DISABLED: NodeFlags = NodeFlags::custom(0);
+///
+/// let mut tree = Tree::new();
+/// let dialog = tree.insert(None, LocalNode::default());
+/// let dragged = tree.insert(Some(dialog), LocalNode::default());
+/// tree.commit();
+///
+/// // Pickable, enabled nodes inside the dialog, except the one being dragged.
+/// let not_dragged = |id: NodeId| id != dragged;
+/// let filter = QueryFilter::new()
+///     .pickable()
+///     .none_of(DISABLED)
+///     .within(dialog)
+///     .predicate(&not_dragged);
+/// assert!(tree.matches_filter(dialog, filter));
+/// assert!(!tree.matches_filter(dragged, filter));
+/// assert!(!filter.matches(NodeFlags::PICKABLE | DISABLED));
+/// ```
+#[derive(Clone, Copy)]
+pub struct QueryFilter<'a> {
     /// Bitfield of required node flags. Only nodes containing all these flags will be included.
     pub required_flags: NodeFlags,
+    /// If non-empty, only nodes containing at least one of these flags will be included.
+    pub any_flags: NodeFlags,
+    /// Nodes containing any of these flags will be excluded.
+    pub excluded_flags: NodeFlags,
+    /// If set, only this node and its descendants will be included.
+    pub scope: Option<NodeId>,
+    /// If set, only nodes for which this returns `true` will be included.
+    ///
+    /// It is called after the flag and scope checks pass, so it can be relatively expensive.
+    pub predicate: Option<&'a dyn Fn(NodeId) -> bool>,
 }
 
-impl Default for QueryFilter {
+impl Default for QueryFilter<'_> {
     fn default() -> Self {
         Self {
             required_flags: NodeFlags::empty(),
+            any_flags: NodeFlags::empty(),
+            excluded_flags: NodeFlags::empty(),
+            scope: None,
+            predicate: None,
         }
     }
 }
 
-impl QueryFilter {
+impl core::fmt::Debug for QueryFilter<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("QueryFilter")
+            .field("required_flags", &self.required_flags)
+            .field("any_flags", &self.any_flags)
+            .field("excluded_flags", &self.excluded_flags)
+            .field("scope", &self.scope)
+            .field("predicate", &self.predicate.map(|_| ".."))
+            .finish()
+    }
+}
+
+impl<'a> QueryFilter<'a> {
     /// Create a new empty filter (includes all nodes).
     pub fn new() -> Self {
         Self::default()
@@ -131,29 +213,204 @@ impl QueryFilter {
         self
     }
 
+    /// Filter to only nodes containing all of `flags`.
+    pub fn all_of(mut self, flags: NodeFlags) -> Self {
+        self.required_flags |= flags;
+        self
+    }
+
+    /// Filter to only nodes containing at least one of `flags`.
+    ///
+    /// Repeated calls widen the set of accepted flags.
+    pub fn any_of(mut self, flags: NodeFlags) -> Self {
+        self.any_flags |= flags;
+        self
+    }
+
+    /// Filter out nodes containing any of `flags`.
+    pub fn none_of(mut self, flags: NodeFlags) -> Self {
+        self.excluded_flags |= flags;
+        self
+    }
+
+    /// Filter to only `scope` and its descendants.
+    pub fn within(mut self, scope: NodeId) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Filter to only nodes for which `predicate` returns `true`.
+    ///
+    /// The predicate may capture a reference to the tree, for example to inspect attachments
+    /// with [`Tree::attachment`]. It replaces any previously set predicate.
+    pub fn predicate(mut self, predicate: &'a dyn Fn(NodeId) -> bool) -> Self {
+        self.predicate = Some(predicate);
+        self
+    }
+
     /// Check if a node's flags satisfy this filter.
+    ///
+    /// This only checks `required_flags`, `any_flags`, and `excluded_flags`; use
+    /// [`Tree::matches_filter`] to also apply `scope` and `predicate`.
     pub fn matches(&self, node_flags: NodeFlags) -> bool {
         node_flags.contains(self.required_flags)
+            && (self.any_flags.is_empty() || node_flags.intersects(self.any_flags))
+            && !node_flags.intersects(self.excluded_flags)
     }
 }
 
+/// A subtree with its own spatial index, rooted at `root`.
+#[derive(Debug)]
+struct Layer<B: Backend<f64>> {
+    root: NodeId,
+    index: IndexGeneric<f64, NodeId, B>,
+}
+
 #[derive(Clone, Debug, Default)]
 struct WorldNode {
     world_transform: Affine,
     world_bounds: Rect, // AABB of transformed (and clipped) local bounds
     world_clip: Option<Rect>,
+    /// AABB of the transformed local bounds, before clipping.
+    content_bounds: Rect,
+    /// AABB of this node's own `local_clip` in world space, if it has one.
+    clip_bounds: Option<Rect>,
+    /// The scroll offset applied to the children's inherited transform.
+    scroll_offset: Vec2,
+    /// Nearest node (self or ancestor) with a local clip: the head of this node's clip chain.
+    ///
+    /// Each link stores its clip as `clip_quad` and the next link as `clip_parent`, both as of
+    /// the last commit, so the chain stays consistent until the next commit.
+    clip_node: Option<NodeId>,
+    /// This node's `local_clip` rect transformed into world space, if it had one.
+    clip_quad: Option<[Point; 4]>,
+    /// If this node has a clip, the head of its parent's clip chain.
+    clip_parent: Option<NodeId>,
+    /// Whether every clip in the chain is axis-aligned in world space, so `world_clip` is exact
+    /// (up to rounded corners).
+    clip_exact: bool,
+    /// Whether the clips in the chain have no common area, so `world_clip` is degenerate.
+    clip_empty: bool,
+    /// Whether the transformed bounds lie entirely outside the clip AABB. The clamped
+    /// `world_bounds` of such a node can still touch the clip edge, so rect queries check this.
+    clipped_out: bool,
+    /// Root of the layer whose index holds this node's key, or `None` for the base layer.
+    layer: Option<NodeId>,
+}
+
+impl WorldNode {
+    /// Recompute the clip and clipped bounds from `content_bounds`, `clip_bounds` and the
+    /// clip inherited from the parent.
+    fn apply_clips(&mut self, inherited: &Inherited) {
+        self.clip_empty = inherited.clip_empty;
+        self.world_clip = match (self.clip_bounds, inherited.clip) {
+            (Some(local), Some(parent)) => {
+                self.clip_empty |= !overlaps(local, parent);
+                Some(local.intersect(parent))
+            }
+            (Some(local), None) => Some(local),
+            (None, Some(parent)) => Some(parent),
+            (None, None) => None,
+        };
+        let content = self.content_bounds;
+        self.world_bounds = self.world_clip.map_or(content, |c| content.intersect(c));
+        self.clipped_out =
+            self.clip_empty || self.world_clip.is_some_and(|c| !overlaps(content, c));
+    }
+
+    /// Translate everything in world space by `delta`, then re-clip against the (possibly
+    /// unmoved) inherited clip.
+    fn shift(&mut self, delta: Vec2, inherited: &Inherited) {
+        self.world_transform = Affine::translate(delta) * self.world_transform;
+        self.content_bounds = self.content_bounds + delta;
+        self.clip_bounds = self.clip_bounds.map(|r| r + delta);
+        if let Some(quad) = &mut self.clip_quad {
+            for p in quad {
+                *p += delta;
+            }
+        }
+        self.apply_clips(inherited);
+    }
+}
+
+/// How the state a node inherits changed since the last commit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ParentChange {
+    /// Unchanged.
+    None,
+    /// Translated in world space by an ancestor's scroll, so the node can be shifted.
+    Shift(Vec2),
+    /// Anything else; the node is recomputed from its local state.
+    Any,
+}
+
+/// World-space state a node passes down to its children during commit.
+#[derive(Clone, Copy, Debug)]
+struct Inherited {
+    transform: Affine,
+    clip: Option<Rect>,
+    clip_node: Option<NodeId>,
+    clip_exact: bool,
+    clip_empty: bool,
+    layer: Option<NodeId>,
+}
+
+impl Inherited {
+    const ROOT: Self = Self {
+        transform: Affine::IDENTITY,
+        clip: None,
+        clip_node: None,
+        clip_exact: true,
+        clip_empty: false,
+        layer: None,
+    };
 }
 
 #[derive(Clone, Copy, Debug, Default)]
 struct Dirty {
+    /// Inserted since the last commit.
+    added: bool,
     layout: bool,
     transform: bool,
     clip: bool,
     z: bool,
     index: bool,
+    flags: bool,
+    scroll: bool,
 }
 
-#[derive(Clone, Debug)]
+impl Dirty {
+    fn any(self) -> bool {
+        self.added
+            || self.layout
+            || self.transform
+            || self.clip
+            || self.z
+            || self.index
+            || self.flags
+            || self.scroll
+    }
+}
+
+/// Optional narrow-phase hit shape attached to a node, in local coordinates.
+struct HitShape(Box<dyn PreciseHitTest + Send + Sync>);
+
+impl core::fmt::Debug for HitShape {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("HitShape(..)")
+    }
+}
+
+/// Host data attached to a node, keyed by its type.
+struct Attachment(Box<dyn Any + Send + Sync>);
+
+impl core::fmt::Debug for Attachment {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Attachment(..)")
+    }
+}
+
+#[derive(Debug)]
 pub(crate) struct Node {
     generation: u32,
     parent: Option<NodeId>,
@@ -161,10 +418,29 @@ pub(crate) struct Node {
     local: LocalNode,
     world: WorldNode,
     dirty: Dirty,
+    /// Some descendant is dirty; `commit` must descend into this subtree.
+    subtree_dirty: bool,
     index_key: Option<AabbKey>,
+    hit_shape: Option<HitShape>,
+    /// At most one attachment per type.
+    attachments: Vec<Attachment>,
+    /// Position in the cached paint order; valid after a commit.
+    paint_rank: u32,
 }
 
 impl Node {
+    /// The state this node's children inherit, as of its last world update.
+    fn inherited(&self) -> Inherited {
+        Inherited {
+            transform: self.world.world_transform * Affine::translate(-self.world.scroll_offset),
+            clip: self.world.world_clip,
+            clip_node: self.world.clip_node,
+            clip_exact: self.world.clip_exact,
+            clip_empty: self.world.clip_empty,
+            layer: self.world.layer,
+        }
+    }
+
     fn new(generation: u32, local: LocalNode) -> Self {
         Self {
             generation,
@@ -173,13 +449,20 @@ impl Node {
             local,
             world: WorldNode::default(),
             dirty: Dirty {
+                added: true,
                 layout: true,
                 transform: true,
                 clip: true,
                 z: true,
                 index: true,
+                flags: false,
+                scroll: false,
             },
+            subtree_dirty: false,
             index_key: None,
+            hit_shape: None,
+            attachments: Vec::new(),
+            paint_rank: u32::MAX,
         }
     }
 }
@@ -197,6 +480,15 @@ impl Tree {
             free_list: Vec::new(),
             epoch: 0,
             index: IndexGeneric::new(),
+            layers: Vec::new(),
+            retired_layers: Vec::new(),
+            tie_break: TieBreakPolicy::Newer,
+            paint_order: Vec::new(),
+            paint_order_dirty: false,
+            track_changes: false,
+            removed: Vec::new(),
+            #[cfg(test)]
+            recomputed: 0,
         }
     }
 }
@@ -210,9 +502,50 @@ impl<B: Backend<f64>> Tree<B> {
             free_list: Vec::new(),
             epoch: 0,
             index: IndexGeneric::with_backend(backend),
+            layers: Vec::new(),
+            retired_layers: Vec::new(),
+            tie_break: TieBreakPolicy::Newer,
+            paint_order: Vec::new(),
+            paint_order_dirty: false,
+            track_changes: false,
+            removed: Vec::new(),
+            #[cfg(test)]
+            recomputed: 0,
+        }
+    }
+
+    /// Returns the policy used to break hit-testing ties after equal `z_index` and depth.
+    pub fn tie_break(&self) -> TieBreakPolicy {
+        self.tie_break
+    }
+
+    /// Returns whether [`Tree::commit`] reports per-node changes.
+    pub fn change_tracking(&self) -> bool {
+        self.track_changes
+    }
+
+    /// Enable or disable per-node change reporting in [`Damage::changes`].
+    ///
+    /// While enabled, each [`Tree::commit`] lists the nodes that were added, removed,
+    /// moved, re-clipped, re-flagged, or re-ordered since the previous commit, with their
+    /// old and new world bounds. Removals are recorded when [`Tree::remove`] is called, so
+    /// enable tracking before making the changes you want reported. Disabling tracking
+    /// discards any removals recorded so far.
+    pub fn set_change_tracking(&mut self, enabled: bool) {
+        self.track_changes = enabled;
+        if !enabled {
+            self.removed.clear();
         }
     }
 
+    /// Set the policy used to break hit-testing ties after equal `z_index` and depth.
+    ///
+    /// This affects [`Tree::hit_test_point`] and [`Tree::hit_test_point_precise`]. It takes
+    /// effect immediately.
+    pub fn set_tie_break(&mut self, policy: TieBreakPolicy) {
+        self.tie_break = policy;
+    }
+
     fn mark_subtree_dirty(&mut self, id: NodeId, flags: Dirty) {
         if !self.is_alive(id) {
             return;
@@ -224,6 +557,9 @@ impl<B: Backend<f64>> Tree<B> {
             n.dirty.clip |= flags.clip;
             n.dirty.z |= flags.z;
             n.dirty.index |= flags.index;
+            n.dirty.flags |= flags.flags;
+            n.dirty.scroll |= flags.scroll;
+            n.subtree_dirty = true;
             n.children.clone()
         };
         for c in children {
@@ -231,6 +567,22 @@ impl<B: Backend<f64>> Tree<B> {
         }
     }
 
+    /// Record that `id` has dirty state so that [`Tree::commit`] visits it.
+    ///
+    /// Marks every ancestor as having a dirty descendant, stopping early at one that is
+    /// already marked.
+    fn mark_ancestors_dirty(&mut self, id: NodeId) {
+        let mut current = self.node(id).parent;
+        while let Some(p) = current {
+            let n = self.node_mut(p);
+            if n.subtree_dirty {
+                break;
+            }
+            n.subtree_dirty = true;
+            current = n.parent;
+        }
+    }
+
     /// Insert a new node as a child of `parent` (or as a root if `None`).
     ///
     /// The returned [`NodeId`] becomes live immediately, but world-space data
@@ -260,6 +612,8 @@ impl<B: Backend<f64>> Tree<B> {
         if let Some(p) = parent {
             self.link_parent(id, p);
         }
+        self.mark_ancestors_dirty(id);
+        self.paint_order_dirty = true;
         id
     }
 
@@ -279,10 +633,149 @@ impl<B: Backend<f64>> Tree<B> {
             self.remove(child);
         }
         if let Some(key) = self.node(id).index_key {
-            self.index.remove(key);
+            let layer = self.node(id).world.layer;
+            self.layer_index_mut(layer).remove(key);
+        }
+        // Nodes never committed were never reported as added, so they are not reported as removed.
+        if self.track_changes && !self.node(id).dirty.added {
+            self.removed.push(NodeChange {
+                id,
+                kind: ChangeKind::REMOVED,
+                old_bounds: Some(self.node(id).world.world_bounds),
+                new_bounds: None,
+            });
+        }
+        if let Some(pos) = self.layers.iter().position(|l| l.root == id) {
+            let mut layer = self.layers.remove(pos);
+            if let Some(u) = layer.index.commit().union() {
+                self.retired_layers.push(LayerDamage {
+                    layer: Some(id),
+                    rect: Rect::new(u.min_x, u.min_y, u.max_x, u.max_y),
+                });
+            }
+            self.detach_layer_entries(id);
         }
         self.nodes[id.idx()] = None;
         self.free_list.push(id.idx());
+        self.paint_order_dirty = true;
+    }
+
+    /// Make `id` the root of an indexed layer backed by `backend`.
+    ///
+    /// Nodes in the subtree of `id` (including `id` itself, and excluding nested layers) are
+    /// indexed in the layer's own spatial index instead of the index of the enclosing layer,
+    /// so changes inside one layer do not churn the others. This suits content with very
+    /// different sizes or update rates, such as a large map, a few overlays, and a drag layer.
+    ///
+    /// All layers share the backend type `B`, but each gets its own instance, so backend
+    /// parameters such as grid cell sizes can be tuned per layer. If `id` is already a layer
+    /// root, its backend is replaced. Layers do not affect paint or hit-testing order: queries
+    /// combine results from all layers, ordered as usual by `z_index` and stacking contexts.
+    ///
+    /// Entries move between indexes on the next [`Tree::commit`], which reports each layer's
+    /// index damage in [`Damage::layers`]. Does nothing if `id` is stale.
+    pub fn set_layer(&mut self, id: NodeId, backend: B) {
+        if !self.is_alive(id) {
+            return;
+        }
+        let index = IndexGeneric::with_backend(backend);
+        if let Some(layer) = self.layers.iter_mut().find(|l| l.root == id) {
+            layer.index = index;
+            self.detach_layer_entries(id);
+        } else {
+            self.layers.push(Layer { root: id, index });
+        }
+        self.node_mut(id).dirty.index = true;
+        self.mark_ancestors_dirty(id);
+    }
+
+    /// Stop indexing the subtree of `id` as a separate layer.
+    ///
+    /// Its nodes move back into the enclosing layer's index on the next [`Tree::commit`].
+    /// Does nothing if `id` is stale or not a layer root.
+    pub fn clear_layer(&mut self, id: NodeId) {
+        let Some(pos) = self.layers.iter().position(|l| l.root == id) else {
+            return;
+        };
+        self.layers.remove(pos);
+        self.detach_layer_entries(id);
+        self.node_mut(id).dirty.index = true;
+        self.mark_ancestors_dirty(id);
+    }
+
+    /// Returns `true` if `id` is live and the root of an indexed layer.
+    pub fn is_layer(&self, id: NodeId) -> bool {
+        self.is_alive(id) && self.layers.iter().any(|l| l.root == id)
+    }
+
+    /// Returns the root of the layer indexing a live node as of the last [`Tree::commit`].
+    ///
+    /// Returns `None` for nodes in the base layer and for stale identifiers.
+    pub fn layer_of(&self, id: NodeId) -> Option<NodeId> {
+        if !self.is_alive(id) {
+            return None;
+        }
+        self.node(id).world.layer
+    }
+
+    /// Iterate the roots of all indexed layers in paint order (back to front) as of the
+    /// last [`Tree::commit`].
+    ///
+    /// Layers created since the last commit come last.
+    pub fn layers(&self) -> impl Iterator<Item = NodeId> + '_ {
+        let mut roots: Vec<(u32, NodeId)> = self
+            .layers
+            .iter()
+            .map(|l| (self.node(l.root).paint_rank, l.root))
+            .collect();
+        roots.sort_by_key(|&(rank, _)| rank);
+        roots.into_iter().map(|(_, id)| id)
+    }
+
+    /// The index holding nodes of `layer`; `None` is the base layer.
+    ///
+    /// Panics if `layer` is not a layer root.
+    fn layer_index_mut(&mut self, layer: Option<NodeId>) -> &mut IndexGeneric<f64, NodeId, B> {
+        match layer {
+            None => &mut self.index,
+            Some(root) => {
+                &mut self
+                    .layers
+                    .iter_mut()
+                    .find(|l| l.root == root)
+                    .expect("node indexed in a missing layer")
+                    .index
+            }
+        }
+    }
+
+    /// Iterate the indexes of all layers, base layer first.
+    fn layer_indexes(&self) -> impl Iterator<Item = &IndexGeneric<f64, NodeId, B>> + '_ {
+        core::iter::once(&self.index).chain(self.layers.iter().map(|l| &l.index))
+    }
+
+    /// Forget the index keys of nodes held by the index of `layer`, which was dropped.
+    ///
+    /// The nodes are re-inserted into the index of their current layer on the next commit.
+    fn detach_layer_entries(&mut self, layer: NodeId) {
+        let mut detached = Vec::new();
+        for (i, slot) in self.nodes.iter_mut().enumerate() {
+            if let Some(n) = slot
+                && n.world.layer == Some(layer)
+                && n.index_key.is_some()
+            {
+                n.index_key = None;
+                n.dirty.index = true;
+                #[allow(
+                    clippy::cast_possible_truncation,
+                    reason = "NodeId uses 32-bit indices by design."
+                )]
+                detached.push(NodeId::new(i as u32, n.generation));
+            }
+        }
+        for id in detached {
+            self.mark_ancestors_dirty(id);
+        }
     }
 
     /// Reparent `id` under `new_parent`.
@@ -299,16 +792,21 @@ impl<B: Backend<f64>> Tree<B> {
         if let Some(p) = new_parent {
             self.link_parent(id, p);
         }
+        self.paint_order_dirty = true;
         self.mark_subtree_dirty(
             id,
             Dirty {
+                added: false,
                 layout: true,
                 transform: true,
                 clip: true,
                 z: true,
                 index: true,
+                flags: false,
+                scroll: false,
             },
         );
+        self.mark_ancestors_dirty(id);
     }
 
     /// Update local transform.
@@ -319,6 +817,7 @@ impl<B: Backend<f64>> Tree<B> {
             n.local.local_transform = tf;
             n.dirty.transform = true;
             n.dirty.index = true;
+            self.mark_ancestors_dirty(id);
         }
     }
 
@@ -330,9 +829,33 @@ impl<B: Backend<f64>> Tree<B> {
             n.local.local_clip = clip;
             n.dirty.clip = true;
             n.dirty.index = true;
+            self.mark_ancestors_dirty(id);
+        }
+    }
+
+    /// Update the scroll offset of a node, which translates its descendants by `-offset`.
+    ///
+    /// The node itself (its bounds and clip) does not move. Only the node is marked dirty;
+    /// on the next [`Tree::commit`] the world state, index entries and damage of its clean
+    /// descendants are shifted by the scroll delta instead of being recomputed, and only
+    /// descendants that are dirty themselves are recomputed. Give scroll
+    /// containers a `local_clip` (their viewport) so that hit testing and visibility queries
+    /// exclude content scrolled out of view.
+    pub fn set_scroll_offset(&mut self, id: NodeId, offset: Vec2) {
+        if let Some(n) = self.node_opt_mut(id)
+            && n.local.scroll_offset != offset
+        {
+            n.local.scroll_offset = offset;
+            n.dirty.scroll = true;
+            self.mark_ancestors_dirty(id);
         }
     }
 
+    /// Returns the scroll offset of a node if the identifier is live.
+    pub fn scroll_offset(&self, id: NodeId) -> Option<Vec2> {
+        self.is_alive(id).then(|| self.node(id).local.scroll_offset)
+    }
+
     /// Update z index.
     pub fn set_z_index(&mut self, id: NodeId, z: i32) {
         if let Some(n) = self.node_opt_mut(id)
@@ -340,6 +863,8 @@ impl<B: Backend<f64>> Tree<B> {
         {
             n.local.z_index = z;
             n.dirty.z = true;
+            self.paint_order_dirty = true;
+            self.mark_ancestors_dirty(id);
         }
     }
 
@@ -351,6 +876,7 @@ impl<B: Backend<f64>> Tree<B> {
             n.local.local_bounds = bounds;
             n.dirty.layout = true;
             n.dirty.index = true;
+            self.mark_ancestors_dirty(id);
         }
     }
 
@@ -359,11 +885,95 @@ impl<B: Backend<f64>> Tree<B> {
         if let Some(n) = self.node_opt_mut(id)
             && n.local.flags != flags
         {
+            let stacking_changed = (n.local.flags ^ flags).contains(NodeFlags::STACKING_CONTEXT);
             n.local.flags = flags;
             n.dirty.index = true;
+            n.dirty.flags = true;
+            self.paint_order_dirty |= stacking_changed;
+            self.mark_ancestors_dirty(id);
+        }
+    }
+
+    /// Attach a precise hit shape to a node, in the node's local coordinates.
+    ///
+    /// The shape is only consulted by [`Tree::hit_test_point_precise`]; it does
+    /// not affect world bounds, the spatial index, or damage, so it takes effect
+    /// immediately without a [`Tree::commit`]. The node's `local_bounds` remain
+    /// the broad phase, so the shape should lie within them.
+    pub fn set_hit_shape<S>(&mut self, id: NodeId, shape: S)
+    where
+        S: PreciseHitTest + Send + Sync + 'static,
+    {
+        if let Some(n) = self.node_opt_mut(id) {
+            n.hit_shape = Some(HitShape(Box::new(shape)));
+        }
+    }
+
+    /// Remove a node's precise hit shape, falling back to its local bounds.
+    pub fn clear_hit_shape(&mut self, id: NodeId) {
+        if let Some(n) = self.node_opt_mut(id) {
+            n.hit_shape = None;
         }
     }
 
+    /// Returns `true` if the node is live and carries a precise hit shape.
+    pub fn has_hit_shape(&self, id: NodeId) -> bool {
+        self.is_alive(id) && self.node(id).hit_shape.is_some()
+    }
+
+    /// Attach a value of type `T` to a node, replacing and returning any previous `T`.
+    ///
+    /// Each node holds at most one value per type, so hosts can attach several independent
+    /// components (for example a widget handle and an accessibility role) without a side table.
+    /// Attachments live in the node's slot: they are dropped by [`Tree::remove`] and never
+    /// leak to a node that later reuses the slot. They do not affect world data or damage, so
+    /// they take effect immediately without a [`Tree::commit`]. Returns `Some(value)` back
+    /// if `id` is stale.
+    pub fn set_attachment<T>(&mut self, id: NodeId, value: T) -> Option<T>
+    where
+        T: Any + Send + Sync,
+    {
+        let Some(n) = self.node_opt_mut(id) else {
+            return Some(value);
+        };
+        if let Some(slot) = n
+            .attachments
+            .iter_mut()
+            .find_map(|a| a.0.downcast_mut::<T>())
+        {
+            return Some(core::mem::replace(slot, value));
+        }
+        n.attachments.push(Attachment(Box::new(value)));
+        None
+    }
+
+    /// Borrow the value of type `T` attached to a live node, if any.
+    pub fn attachment<T: Any>(&self, id: NodeId) -> Option<&T> {
+        if !self.is_alive(id) {
+            return None;
+        }
+        self.node(id)
+            .attachments
+            .iter()
+            .find_map(|a| a.0.downcast_ref::<T>())
+    }
+
+    /// Mutably borrow the value of type `T` attached to a live node, if any.
+    pub fn attachment_mut<T: Any>(&mut self, id: NodeId) -> Option<&mut T> {
+        self.node_opt_mut(id)?
+            .attachments
+            .iter_mut()
+            .find_map(|a| a.0.downcast_mut::<T>())
+    }
+
+    /// Detach and return the value of type `T` attached to a live node, if any.
+    pub fn remove_attachment<T: Any>(&mut self, id: NodeId) -> Option<T> {
+        let n = self.node_opt_mut(id)?;
+        let pos = n.attachments.iter().position(|a| a.0.is::<T>())?;
+        let boxed: Box<dyn Any> = n.attachments.swap_remove(pos).0;
+        boxed.downcast::<T>().ok().map(|b| *b)
+    }
+
     /// Return the world transform for a live node as of the last [`Tree::commit`].
     ///
     /// The returned [`Affine`] maps from the node's local coordinate space into
@@ -412,8 +1022,18 @@ impl<B: Backend<f64>> Tree<B> {
     /// index, and returns a [`Damage`] summary capturing added/removed/moved
     /// regions. Call this after mutating any `LocalNode` fields or tree
     /// structure before issuing queries.
+    ///
+    /// With [`Tree::set_change_tracking`] enabled, [`Damage::changes`] also lists
+    /// each changed node with its [`ChangeKind`] and old/new world bounds.
     pub fn commit(&mut self) -> Damage {
-        let mut damage = Damage::default();
+        let mut damage = Damage {
+            changes: core::mem::take(&mut self.removed),
+            ..Damage::default()
+        };
+        #[cfg(test)]
+        {
+            self.recomputed = 0;
+        }
         let roots: Vec<NodeId> = self
             .nodes
             .iter()
@@ -431,15 +1051,27 @@ impl<B: Backend<f64>> Tree<B> {
             })
             .collect();
 
-        for root in roots {
-            self.update_world_recursive(root, Affine::IDENTITY, None, &mut damage);
+        for &root in &roots {
+            self.update_world_recursive(root, &mut damage);
+        }
+
+        if self.paint_order_dirty {
+            self.update_paint_order(&roots);
+            self.paint_order_dirty = false;
         }
 
-        let idx_damage = self.index.commit();
-        if let Some(u) = idx_damage.union() {
-            let r = Rect::new(u.min_x, u.min_y, u.max_x, u.max_y);
-            damage.dirty_rects.push(r);
+        let layers = core::iter::once((None, &mut self.index))
+            .chain(self.layers.iter_mut().map(|l| (Some(l.root), &mut l.index)));
+        for (layer, index) in layers {
+            if let Some(u) = index.commit().union() {
+                let rect = Rect::new(u.min_x, u.min_y, u.max_x, u.max_y);
+                damage.layers.push(LayerDamage { layer, rect });
+            }
         }
+        damage.layers.append(&mut self.retired_layers);
+        damage
+            .dirty_rects
+            .extend(damage.layers.iter().map(|l| l.rect));
 
         damage
     }
@@ -451,57 +1083,50 @@ impl<B: Backend<f64>> Tree<B> {
     /// - Nodes must satisfy the [`QueryFilter`] and contain the point within their
     ///   world-space bounds and clip to be eligible.
     /// - Among candidates, higher `z_index` wins; if `z_index` ties, deeper nodes
-    ///   in the tree win; if that also ties, the newer [`NodeId`] wins.
+    ///   in the tree win; if that also ties, the [`TieBreakPolicy`] set with
+    ///   [`Tree::set_tie_break`] decides (by default, the newer [`NodeId`] wins).
+    /// - `z_index` is compared within stacking contexts: nodes inside a context
+    ///   (see [`NodeFlags::STACKING_CONTEXT`]) are ranked against outside nodes
+    ///   by the `z_index` of the context's root.
     ///
-    /// This tie-break is intentionally deterministic for now. In the future this
-    /// may be made configurable (for example via a `TieBreakPolicy`).
-    pub fn hit_test_point(&self, point: Point, filter: QueryFilter) -> Option<Hit> {
-        /// Walk the tree upward from the given node (given its `NodeId` and `&Node` data),
-        /// checking whether `point` is within its bounds, its clip, and all its ancestors' clips.
-        ///
-        /// `path` should be empty when calling this function. The path from the node to the root
-        /// of its tree will be contained in `path` (in order from node to root) if and only if the
-        /// point is within all the aforementioned geometry. If `path` is empty after calling this
-        /// function, the point is not within the geometry.
-        fn walk_tree_and_check_geometry<B: Backend<f64>>(
-            tree: &Tree<B>,
-            point: Point,
-            id: NodeId,
-            node: &Node,
-            path: &mut Vec<NodeId>,
-        ) {
-            let local_point = node.world.world_transform.inverse() * point;
-            if !node.local.local_bounds.contains(local_point) {
-                return;
-            }
-
-            if let Some(local_clip) = node.local.local_clip
-                && !local_clip.contains(local_point)
-            {
-                return;
-            }
-
-            path.push(id);
-
-            // Walk the parents up to this node's tree root node, checking each parent's clip.
-            let mut current = node.parent;
-            while let Some(parent_id) = current {
-                let Some(parent) = &tree.nodes[parent_id.idx()] else {
-                    unreachable!("parent slot is unoccupied");
-                };
-                if let Some(clip) = parent.local.local_clip {
-                    let parent_local_point = parent.world.world_transform.inverse() * point;
-                    if !clip.contains(parent_local_point) {
-                        path.clear();
-                        return;
-                    }
-                }
-                path.push(parent_id);
-                current = parent.parent;
-            }
-        }
+    /// Use [`Tree::hit_test_point_all`] to get every matching node instead.
+    pub fn hit_test_point(&self, point: Point, filter: QueryFilter<'_>) -> Option<Hit> {
+        self.hit_test_point_by(point, filter, |_| true)
+    }
 
-        let mut best: Option<(NodeId, i32, Vec<NodeId>)> = None;
+    /// Hit test a world-space point among nodes carrying an attachment of type `T` that
+    /// satisfies `predicate`, returning the topmost such node and its attachment.
+    ///
+    /// Candidates must also satisfy the [`QueryFilter`] and are ranked exactly as in
+    /// [`Tree::hit_test_point`]. Nodes without a `T` attached are skipped, so a node below
+    /// them can win. See [`Tree::set_attachment`].
+    pub fn hit_test_point_with<T, F>(
+        &self,
+        point: Point,
+        filter: QueryFilter<'_>,
+        mut predicate: F,
+    ) -> Option<(Hit, &T)>
+    where
+        T: Any,
+        F: FnMut(NodeId, &T) -> bool,
+    {
+        let hit = self.hit_test_point_by(point, filter, |id| {
+            self.attachment::<T>(id)
+                .is_some_and(|data| predicate(id, data))
+        })?;
+        let data = self.attachment::<T>(hit.node)?;
+        Some((hit, data))
+    }
+
+    /// Shared implementation of the single-result hit tests; `accept` further restricts
+    /// the candidates that satisfy `filter`.
+    fn hit_test_point_by(
+        &self,
+        point: Point,
+        filter: QueryFilter<'_>,
+        mut accept: impl FnMut(NodeId) -> bool,
+    ) -> Option<Hit> {
+        let mut best: Option<(NodeId, Vec<NodeId>)> = None;
         let mut path_buf: Vec<NodeId> = Vec::new();
 
         for id in self.containing_point(point, filter) {
@@ -509,36 +1134,30 @@ impl<B: Backend<f64>> Tree<B> {
                 unreachable!("`self.containing_point` only returns live nodes");
             };
 
-            walk_tree_and_check_geometry(self, point, id, node, &mut path_buf);
+            let local_point = node.world.world_transform.inverse() * point;
+            if !node.local.local_bounds.contains(local_point) || !accept(id) {
+                continue;
+            }
+            self.clip_path(point, local_point, id, node, &mut path_buf);
             if path_buf.is_empty() {
-                // The point is not within the node's geometry (either its bounds, its clip, or its
-                // ancestors' clips).
+                // The point is not within the node's clip or its ancestors' clips.
                 continue;
             }
 
             match best {
                 None => {
-                    best = Some((id, node.local.z_index, core::mem::take(&mut path_buf)));
+                    best = Some((id, core::mem::take(&mut path_buf)));
                 }
-                Some((ref mut id_best, ref mut z_best, ref mut path_best)) => {
-                    let z = node.local.z_index;
-                    let depth_best = path_best.len();
-                    let depth = path_buf.len();
-                    if z > *z_best
-                        || (z == *z_best
-                            && (depth > depth_best
-                                || (depth == depth_best && id_is_newer(id, *id_best))))
-                    {
+                Some((ref mut id_best, ref mut path_best)) => {
+                    if self.hit_cmp(&path_buf, path_best).is_gt() {
                         core::mem::swap(&mut path_buf, path_best);
-                        path_buf.clear();
                         *id_best = id;
-                        *z_best = z;
                     }
                 }
             }
         }
 
-        best.map(|(node, _, mut path)| {
+        best.map(|(node, mut path)| {
             // Reverse the path we found earlier, as `Hit::path` paths are from the root to the
             // node.
             path.reverse();
@@ -546,67 +1165,426 @@ impl<B: Backend<f64>> Tree<B> {
         })
     }
 
-    /// Iterate live nodes whose world-space bounds intersect a world-space rectangle.
+    /// Hit test a world-space point and return every matching node in paint order.
     ///
-    /// Edges of the rectangle and bounding boxes are included in the intersection, meaning that a
-    /// rectangle and bounding box that share (part of) an edge are considered to overlap.
+    /// Eligibility is the same as for [`Tree::hit_test_point`]: nodes must satisfy the
+    /// [`QueryFilter`] and contain the point within their bounds, their clip, and all
+    /// ancestor clips. Results are in paint order (back to front) as of the last
+    /// [`Tree::commit`], as in [`Tree::visible_in_paint_order`]. Iterate in reverse for a
+    /// topmost-first list (for example, a context menu of overlapping objects, or click-through
+    /// to the next node down).
     ///
-    /// - `rect` is interpreted in world coordinates.
-    /// - Nodes must satisfy the [`QueryFilter`] and have a non-empty intersection
-    ///   between their world-space bounds and the supplied rectangle to be yielded.
-    /// - The returned [`NodeId`]s are in an unspecified order; no z-sorting is applied.
-    pub fn intersect_rect<'a>(
-        &'a self,
-        rect: Rect,
-        filter: QueryFilter,
-    ) -> impl Iterator<Item = NodeId> + 'a {
-        let q = rect_to_aabb(rect);
-        self.index
-            .query_rect(q)
-            .map(|(_, id)| id)
-            .filter(move |id| {
-                let Some(node) = self.nodes[id.idx()].as_ref() else {
-                    return false;
-                };
-                filter.matches(node.local.flags)
-            })
+    /// Paint order ranks equal `z_index` by tree order, whereas [`Tree::hit_test_point`] prefers
+    /// deeper nodes, so the last entry is not always its result: a child of an earlier sibling
+    /// paints below a later sibling but wins the hit test.
+    pub fn hit_test_point_all(&self, point: Point, filter: QueryFilter<'_>) -> Vec<Hit> {
+        let mut hits: Vec<Hit> = Vec::new();
+        let mut path_buf: Vec<NodeId> = Vec::new();
+
+        for id in self.containing_point(point, filter) {
+            let Some(node) = self.nodes[id.idx()].as_ref() else {
+                unreachable!("`self.containing_point` only returns live nodes");
+            };
+
+            let local_point = node.world.world_transform.inverse() * point;
+            if !node.local.local_bounds.contains(local_point) {
+                continue;
+            }
+            self.clip_path(point, local_point, id, node, &mut path_buf);
+            if path_buf.is_empty() {
+                continue;
+            }
+            hits.push(Hit {
+                node: id,
+                path: core::mem::take(&mut path_buf),
+            });
+        }
+
+        hits.sort_unstable_by_key(|hit| self.node(hit.node).paint_rank);
+        for hit in &mut hits {
+            hit.path.reverse();
+        }
+        hits
     }
 
-    /// Iterate live nodes whose world-space bounds contain a world-space point.
+    /// Hit test a world-space point against each node's precise hit shape and,
+    /// if any node matches, return the topmost [`Hit`] with its [`HitScore`].
     ///
-    /// Edges of the bounding boxes are included in the contains-check, having the same semantics
-    /// as [`Aabb2D::contains_point`][understory_index::Aabb2D::contains_point], meaning that a
-    /// point exactly on the edge of a bounding box is contained by that bounding box.
+    /// - The spatial index is used as the broad phase, exactly as in
+    ///   [`Tree::hit_test_point`].
+    /// - Each candidate's world transform is inverted to localize `point`, which
+    ///   is then tested against the shape set with [`Tree::set_hit_shape`]
+    ///   (or the node's `local_bounds` when it has none) using `params`.
+    /// - Local and ancestor clips are respected as in [`Tree::hit_test_point`].
+    /// - Among candidates, higher `z_index` (compared within stacking contexts) wins; if
+    ///   `z_index` ties, the smaller [`HitScore::distance`] wins, then deeper nodes, then the
+    ///   [`TieBreakPolicy`].
     ///
-    /// - `point` is interpreted in world coordinates.
-    /// - Nodes must satisfy the [`QueryFilter`] and contain the given point to be yielded.
-    /// - The returned [`NodeId`]s are in an unspecified order; no z-sorting is applied.
-    pub fn containing_point<'a>(
-        &'a self,
+    /// Tolerances in [`HitParams`] only apply within a node's world-space AABB,
+    /// since points outside it are culled by the broad phase.
+    pub fn hit_test_point_precise(
+        &self,
         point: Point,
-        filter: QueryFilter,
-    ) -> impl Iterator<Item = NodeId> + 'a {
-        self.index
-            .query_point(point.x, point.y)
-            .map(|(_, id)| id)
-            .filter(move |id| {
-                let Some(node) = self.nodes[id.idx()].as_ref() else {
-                    return false;
-                };
-                filter.matches(node.local.flags)
-            })
-    }
-}
-
-#[inline]
-fn id_is_newer(a: NodeId, b: NodeId) -> bool {
-    (a.1 > b.1) || (a.1 == b.1 && a.0 > b.0)
-}
+        filter: QueryFilter<'_>,
+        params: &HitParams,
+    ) -> Option<(Hit, HitScore)> {
+        let mut best: Option<(NodeId, HitScore, Vec<NodeId>)> = None;
+        let mut path_buf: Vec<NodeId> = Vec::new();
 
-impl<B: Backend<f64>> Tree<B> {
-    // --- internals ---
+        for id in self.containing_point(point, filter) {
+            let Some(node) = self.nodes[id.idx()].as_ref() else {
+                unreachable!("`self.containing_point` only returns live nodes");
+            };
 
-    /// Returns true if `id` refers to a live node.
+            let local_point = node.world.world_transform.inverse() * point;
+            let score = match &node.hit_shape {
+                Some(shape) => shape.0.hit_test_local(local_point, params),
+                None => node.local.local_bounds.hit_test_local(local_point, params),
+            };
+            let Some(score) = score else {
+                continue;
+            };
+            self.clip_path(point, local_point, id, node, &mut path_buf);
+            if path_buf.is_empty() {
+                continue;
+            }
+
+            let better = match &best {
+                None => true,
+                Some((id_best, score_best, path_best)) => self
+                    .stack_cmp(&path_buf, path_best)
+                    .then_with(|| score_best.cmp_distance(&score))
+                    .then_with(|| path_buf.len().cmp(&path_best.len()))
+                    .then_with(|| self.id_order(id, *id_best))
+                    .is_gt(),
+            };
+            if better {
+                let path = core::mem::take(&mut path_buf);
+                if let Some((_, _, old)) = best.replace((id, score, path)) {
+                    path_buf = old;
+                }
+            }
+        }
+
+        best.map(|(node, score, mut path)| {
+            path.reverse();
+            (Hit { node, path }, score)
+        })
+    }
+
+    /// Returns `true` if the node's transformed `local_bounds`, clipped by its whole clip chain
+    /// (and by `rect`, if given), are non-empty.
+    ///
+    /// The chain is read as of the last commit. If a link has since been removed, the rest of
+    /// the chain is approximated by the node's committed `world_clip`.
+    fn clip_chain_intersects(&self, node: &Node, rect: Option<Rect>) -> bool {
+        let mut poly =
+            transform_rect_quad(node.world.world_transform, node.local.local_bounds).to_vec();
+        if let Some(rect) = rect {
+            clip_polygon(&mut poly, &transform_rect_quad(Affine::IDENTITY, rect));
+        }
+        let mut link = node.world.clip_node;
+        while let Some(clip_id) = link
+            && !poly.is_empty()
+        {
+            let committed = self
+                .is_alive(clip_id)
+                .then(|| &self.node(clip_id).world)
+                .and_then(|w| Some((w.clip_quad?, w.clip_parent)));
+            let Some((quad, next)) = committed else {
+                if let Some(clip) = node.world.world_clip {
+                    clip_polygon(&mut poly, &transform_rect_quad(Affine::IDENTITY, clip));
+                }
+                break;
+            };
+            clip_polygon(&mut poly, &quad);
+            link = next;
+        }
+        !poly.is_empty()
+    }
+
+    /// Walk the tree upward from the given node, checking whether `point` is within its clip and
+    /// all its ancestors' clips. `local_point` is `point` in the node's local coordinates.
+    ///
+    /// `path` is cleared first. The path from the node to the root of its tree will be contained
+    /// in `path` (in order from node to root) if and only if the point is within all the clips.
+    /// If `path` is empty after calling this function, the point is clipped out.
+    fn clip_path(
+        &self,
+        point: Point,
+        local_point: Point,
+        id: NodeId,
+        node: &Node,
+        path: &mut Vec<NodeId>,
+    ) {
+        path.clear();
+        if let Some(local_clip) = node.local.local_clip
+            && !local_clip.contains(local_point)
+        {
+            return;
+        }
+
+        path.push(id);
+
+        // Walk the parents up to this node's tree root node, checking each parent's clip.
+        let mut current = node.parent;
+        while let Some(parent_id) = current {
+            let Some(parent) = &self.nodes[parent_id.idx()] else {
+                unreachable!("parent slot is unoccupied");
+            };
+            if let Some(clip) = parent.local.local_clip {
+                let parent_local_point = parent.world.world_transform.inverse() * point;
+                if !clip.contains(parent_local_point) {
+                    path.clear();
+                    return;
+                }
+            }
+            path.push(parent_id);
+            current = parent.parent;
+        }
+    }
+
+    /// Iterate live nodes whose world-space bounds intersect a world-space rectangle.
+    ///
+    /// Edges of the rectangle and bounding boxes are included in the intersection, meaning that a
+    /// rectangle and bounding box that share (part of) an edge are considered to overlap.
+    ///
+    /// - `rect` is interpreted in world coordinates.
+    /// - Nodes must satisfy the [`QueryFilter`] and have a non-empty intersection
+    ///   between their world-space bounds and the supplied rectangle to be yielded.
+    /// - Nodes whose transformed bounds lie entirely outside their clip (for example, content
+    ///   scrolled out of a scroll container's viewport) are excluded.
+    /// - Under a rotated or sheared clip, the world-space AABB is only the broad phase: the
+    ///   node's transformed bounds are then clipped exactly by its clip chain (see
+    ///   [`Tree::is_visible`]) and must still intersect the rectangle.
+    /// - The returned [`NodeId`]s are in an unspecified order; no z-sorting is applied.
+    ///   Use [`Tree::visible_in_paint_order`] for a back-to-front ordering.
+    pub fn intersect_rect<'a>(
+        &'a self,
+        rect: Rect,
+        filter: QueryFilter<'a>,
+    ) -> impl Iterator<Item = NodeId> + 'a {
+        let q = rect_to_aabb(rect);
+        self.layer_indexes()
+            .flat_map(move |index| index.query_rect(q))
+            .map(|(_, id)| id)
+            .filter(move |&id| {
+                let Some(node) = self.nodes[id.idx()].as_ref() else {
+                    return false;
+                };
+                self.node_matches(id, node, filter)
+                    && !node.world.clipped_out
+                    && (node.world.clip_exact || self.clip_chain_intersects(node, Some(rect)))
+            })
+    }
+
+    /// Iterate live nodes intersecting a world-space rectangle that carry an attachment of
+    /// type `T` satisfying `predicate`, together with that attachment.
+    ///
+    /// Candidates are those of [`Tree::intersect_rect`], in the same unspecified order;
+    /// nodes without a `T` attached are skipped. See [`Tree::set_attachment`].
+    pub fn intersect_rect_with<'a, T, F>(
+        &'a self,
+        rect: Rect,
+        filter: QueryFilter<'a>,
+        mut predicate: F,
+    ) -> impl Iterator<Item = (NodeId, &'a T)> + 'a
+    where
+        T: Any,
+        F: FnMut(NodeId, &T) -> bool + 'a,
+    {
+        self.intersect_rect(rect, filter).filter_map(move |id| {
+            let data = self.attachment::<T>(id)?;
+            predicate(id, data).then_some((id, data))
+        })
+    }
+
+    /// Returns `true` if a live node is visible as of the last [`Tree::commit`].
+    ///
+    /// A node is visible when it has [`NodeFlags::VISIBLE`] and its transformed `local_bounds`
+    /// are not entirely clipped away by its own and its ancestors' clips. Clips are tested
+    /// exactly as transformed quads, so a node inside the AABB of a rotated clip but outside
+    /// the clip itself is not visible. Rounded clip corners are treated as square, and edges are
+    /// inclusive, as in [`Tree::intersect_rect`]. Returns `false` for stale identifiers.
+    pub fn is_visible(&self, id: NodeId) -> bool {
+        if !self.is_alive(id) {
+            return false;
+        }
+        let node = self.node(id);
+        node.local.flags.contains(NodeFlags::VISIBLE) && self.clip_chain_intersects(node, None)
+    }
+
+    /// Iterate live nodes whose world-space bounds intersect a world-space rectangle, in paint
+    /// order (back to front).
+    ///
+    /// This yields the same nodes as [`Tree::intersect_rect`], ordered by `z_index` and then by
+    /// tree order (a depth-first pre-order walk, so parents paint before their children and
+    /// earlier siblings before later ones). It is suitable for feeding a display list directly.
+    ///
+    /// A node flagged with [`NodeFlags::STACKING_CONTEXT`] paints its whole subtree as a unit
+    /// at its own position: its descendants are ordered among themselves after the node, and
+    /// their `z_index` values cannot move them above or below nodes outside the context.
+    ///
+    /// Sort keys are cached and only recomputed by [`Tree::commit`] after insertions, removals,
+    /// reparenting, or `z_index` changes, so each query only sorts the matching nodes.
+    pub fn visible_in_paint_order(
+        &self,
+        rect: Rect,
+        filter: QueryFilter<'_>,
+    ) -> impl Iterator<Item = NodeId> + '_ {
+        let mut ranked: Vec<(u32, NodeId)> = self
+            .intersect_rect(rect, filter)
+            .map(|id| (self.node(id).paint_rank, id))
+            .collect();
+        ranked.sort_unstable_by_key(|&(rank, _)| rank);
+        ranked.into_iter().map(|(_, id)| id)
+    }
+
+    /// Iterate all live nodes in paint order (back to front) as of the last [`Tree::commit`].
+    ///
+    /// See [`Tree::visible_in_paint_order`] for the ordering. Nodes removed since the last
+    /// commit are skipped; nodes inserted since then are not yet included.
+    pub fn paint_order(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.paint_order
+            .iter()
+            .copied()
+            .filter(|&id| self.is_alive(id))
+    }
+
+    /// Returns a node's position in paint order as of the last [`Tree::commit`].
+    ///
+    /// Nodes painted later have higher ranks, so ranks compare like [`Tree::paint_order`]
+    /// positions and respect stacking contexts. Returns `None` for stale ids and for nodes
+    /// inserted since the last commit.
+    pub fn paint_rank(&self, id: NodeId) -> Option<u32> {
+        self.is_alive(id)
+            .then(|| self.node(id).paint_rank)
+            .filter(|&rank| rank != u32::MAX)
+    }
+
+    /// Iterate live nodes whose world-space bounds contain a world-space point.
+    ///
+    /// Edges of the bounding boxes are included in the contains-check, having the same semantics
+    /// as [`Aabb2D::contains_point`][understory_index::Aabb2D::contains_point], meaning that a
+    /// point exactly on the edge of a bounding box is contained by that bounding box.
+    ///
+    /// - `point` is interpreted in world coordinates.
+    /// - Nodes must satisfy the [`QueryFilter`] and contain the given point to be yielded.
+    /// - The returned [`NodeId`]s are in an unspecified order; no z-sorting is applied.
+    pub fn containing_point<'a>(
+        &'a self,
+        point: Point,
+        filter: QueryFilter<'a>,
+    ) -> impl Iterator<Item = NodeId> + 'a {
+        self.layer_indexes()
+            .flat_map(move |index| index.query_point(point.x, point.y))
+            .map(|(_, id)| id)
+            .filter(move |&id| {
+                let Some(node) = self.nodes[id.idx()].as_ref() else {
+                    return false;
+                };
+                self.node_matches(id, node, filter)
+            })
+    }
+
+    /// Returns `true` if a live node passes every condition of `filter`, including its scope
+    /// and predicate. Returns `false` for stale identifiers.
+    ///
+    /// Queries apply filters the same way; this is for code that visits nodes by other
+    /// means, such as [`Tree::next_depth_first`] and [`Tree::prev_depth_first`].
+    pub fn matches_filter(&self, id: NodeId, filter: QueryFilter<'_>) -> bool {
+        self.is_alive(id) && self.node_matches(id, self.node(id), filter)
+    }
+
+    fn node_matches(&self, id: NodeId, node: &Node, filter: QueryFilter<'_>) -> bool {
+        filter.matches(node.local.flags)
+            && filter.scope.is_none_or(|scope| self.is_within(id, scope))
+            && filter.predicate.is_none_or(|predicate| predicate(id))
+    }
+
+    /// Returns `true` if `id` is `scope` or one of its descendants.
+    fn is_within(&self, id: NodeId, scope: NodeId) -> bool {
+        let mut current = Some(id);
+        while let Some(n) = current {
+            if n == scope {
+                return true;
+            }
+            current = self.node(n).parent;
+        }
+        false
+    }
+}
+
+/// Inclusive overlap test: rects that share an edge overlap.
+#[inline]
+fn overlaps(a: Rect, b: Rect) -> bool {
+    a.x0 <= b.x1 && b.x0 <= a.x1 && a.y0 <= b.y1 && b.y0 <= a.y1
+}
+
+#[inline]
+fn id_is_newer(a: NodeId, b: NodeId) -> bool {
+    (a.1 > b.1) || (a.1 == b.1 && a.0 > b.0)
+}
+
+impl<B: Backend<f64>> Tree<B> {
+    /// Order two node ids under the current [`TieBreakPolicy`]; `Greater` means `a` wins.
+    fn id_order(&self, a: NodeId, b: NodeId) -> Ordering {
+        match self.tie_break {
+            TieBreakPolicy::Newer => id_is_newer(a, b).cmp(&id_is_newer(b, a)),
+            TieBreakPolicy::Older => id_is_newer(b, a).cmp(&id_is_newer(a, b)),
+            TieBreakPolicy::MinId => (b.0, b.1).cmp(&(a.0, a.1)),
+            TieBreakPolicy::MaxId => (a.0, a.1).cmp(&(b.0, b.1)),
+        }
+    }
+
+    /// Compare two hit candidates given their node-to-root paths; `Greater` means `a` is on top.
+    ///
+    /// Ranks by stacking order, then depth, then the [`TieBreakPolicy`].
+    fn hit_cmp(&self, a: &[NodeId], b: &[NodeId]) -> Ordering {
+        self.stack_cmp(a, b)
+            .then_with(|| a.len().cmp(&b.len()))
+            .then_with(|| self.id_order(a[0], b[0]))
+    }
+
+    /// Compare the stacking order of two nodes given their node-to-root paths.
+    ///
+    /// Each node is ranked by its chain of enclosing stacking context roots, outermost first,
+    /// followed by the node itself. At the first entry where the chains diverge, both entries
+    /// belong to the same context and their `z_index` values are compared. Returns `Equal` when
+    /// those tie or when one node is the root of a context containing the other.
+    fn stack_cmp(&self, a: &[NodeId], b: &[NodeId]) -> Ordering {
+        for (x, y) in self.stacking_chain(a).zip(self.stacking_chain(b)) {
+            if x != y {
+                return self.node(x).local.z_index.cmp(&self.node(y).local.z_index);
+            }
+        }
+        Ordering::Equal
+    }
+
+    /// The enclosing stacking context roots of a node (outermost first), then the node itself.
+    fn stacking_chain<'a>(&'a self, path: &'a [NodeId]) -> impl Iterator<Item = NodeId> + 'a {
+        let (&node, ancestors) = path.split_first().expect("hit paths are non-empty");
+        ancestors
+            .iter()
+            .rev()
+            .copied()
+            .filter(|&id| self.creates_stacking_context(id))
+            .chain(core::iter::once(node))
+    }
+
+    #[inline]
+    fn creates_stacking_context(&self, id: NodeId) -> bool {
+        self.node(id)
+            .local
+            .flags
+            .contains(NodeFlags::STACKING_CONTEXT)
+    }
+}
+
+impl<B: Backend<f64>> Tree<B> {
+    // --- internals ---
+
+    /// Returns true if `id` refers to a live node.
     ///
     /// A `NodeId` is considered live if its slot exists and its generation matches
     /// the current generation stored in that slot.
@@ -763,59 +1741,207 @@ impl<B: Backend<f64>> Tree<B> {
         self.node_mut(id).parent = None;
     }
 
-    fn update_world_recursive(
-        &mut self,
-        root_id: NodeId,
-        root_tf: Affine,
-        root_clip: Option<Rect>,
-        damage: &mut Damage,
-    ) {
+    /// Recompute the cached paint order and each node's paint rank.
+    fn update_paint_order(&mut self, roots: &[NodeId]) {
+        let mut order = core::mem::take(&mut self.paint_order);
+        order.clear();
+        self.paint_context(roots, &mut order);
+        for (rank, &id) in order.iter().enumerate() {
+            #[allow(
+                clippy::cast_possible_truncation,
+                reason = "Node counts are bounded by 32-bit NodeId indices."
+            )]
+            {
+                self.node_mut(id).paint_rank = rank as u32;
+            }
+        }
+        self.paint_order = order;
+    }
+
+    /// Append the members of one stacking context to `out` in paint order.
+    ///
+    /// Members are the nodes reachable from `starts` without entering a nested stacking
+    /// context. They are sorted by `z_index` (stable, so ties keep tree order), and each nested
+    /// context is painted atomically right after its root.
+    fn paint_context(&self, starts: &[NodeId], out: &mut Vec<NodeId>) {
+        let mut members = Vec::new();
+        let mut stack: Vec<NodeId> = starts.iter().rev().copied().collect();
+        while let Some(id) = stack.pop() {
+            members.push(id);
+            if !self.creates_stacking_context(id) {
+                stack.extend(self.node(id).children.iter().rev().copied());
+            }
+        }
+        members.sort_by_key(|&id| self.node(id).local.z_index);
+        for id in members {
+            out.push(id);
+            if self.creates_stacking_context(id) {
+                self.paint_context(&self.node(id).children, out);
+            }
+        }
+    }
+
+    fn update_world_recursive(&mut self, root_id: NodeId, damage: &mut Damage) {
         // The world is updated by walking the tree depth-first, propagating transforms and clips
-        // toward the leaves.
-        let mut stack = vec![(root_id, root_tf, root_clip)];
+        // toward the leaves. Clean subtrees whose ancestors did not change are skipped, and clean
+        // nodes under a scroll change are shifted rather than recomputed.
+        let mut stack = vec![(root_id, Inherited::ROOT, ParentChange::None)];
 
-        while let Some((id, current_tf, current_clip)) = stack.pop() {
+        while let Some((id, inherited, change)) = stack.pop() {
+            let is_layer = self.layers.iter().any(|l| l.root == id);
             let node = self.node_mut(id);
-            let old_world_bounds = node.world.world_bounds;
-            node.world.world_transform = current_tf * node.local.local_transform;
-            let mut world_bounds =
-                transform_rect_bbox(node.world.world_transform, node.local.local_bounds);
-            let local_clip = node
-                .local
-                .local_clip
-                .map(|rr| transform_rect_bbox(node.world.world_transform, rr.rect()));
-            let world_clip = match (local_clip, current_clip) {
-                (Some(local), Some(parent)) => Some(local.intersect(parent)),
-                (Some(local), None) => Some(local),
-                (None, Some(parent)) => Some(parent),
-                (None, None) => None,
-            };
-            if let Some(c) = world_clip {
-                world_bounds = world_bounds.intersect(c);
+            let dirty = core::mem::take(&mut node.dirty);
+            let descend = core::mem::take(&mut node.subtree_dirty);
+            if change == ParentChange::None && !dirty.any() {
+                if descend {
+                    let for_children = node.inherited();
+                    for &child in node.children.iter().rev() {
+                        stack.push((child, for_children, ParentChange::None));
+                    }
+                }
+                continue;
             }
-            node.world.world_bounds = world_bounds;
-            node.world.world_clip = world_clip;
-            let aabb = rect_to_aabb(world_bounds);
 
-            if old_world_bounds != node.world.world_bounds {
-                if old_world_bounds.width() > 0.0 && old_world_bounds.height() > 0.0 {
-                    damage.dirty_rects.push(old_world_bounds);
+            let old_world = node.world.clone();
+            let scroll_only = !Dirty {
+                scroll: false,
+                ..dirty
+            }
+            .any();
+            let children_change = match change {
+                ParentChange::None | ParentChange::Shift(_) if scroll_only => {
+                    let mut delta = Vec2::ZERO;
+                    if let ParentChange::Shift(d) = change {
+                        node.world.shift(d, &inherited);
+                        delta = d;
+                    }
+                    // Scrolling translates the content by the offset change, mapped to world space.
+                    let w = node.world.world_transform;
+                    let scroll = node.world.scroll_offset - node.local.scroll_offset;
+                    delta += (w * Affine::translate(scroll)).translation() - w.translation();
+                    node.world.scroll_offset = node.local.scroll_offset;
+                    if delta != Vec2::ZERO {
+                        ParentChange::Shift(delta)
+                    } else {
+                        ParentChange::None
+                    }
                 }
-                if node.world.world_bounds.width() > 0.0 && node.world.world_bounds.height() > 0.0 {
-                    damage.dirty_rects.push(node.world.world_bounds);
+                _ => {
+                    #[cfg(test)]
+                    {
+                        self.recomputed += 1;
+                    }
+                    let node = self.node_mut(id);
+                    node.world.world_transform = inherited.transform * node.local.local_transform;
+                    node.world.content_bounds =
+                        transform_rect_bbox(node.world.world_transform, node.local.local_bounds);
+                    node.world.clip_bounds = node
+                        .local
+                        .local_clip
+                        .map(|rr| transform_rect_bbox(node.world.world_transform, rr.rect()));
+                    node.world.scroll_offset = node.local.scroll_offset;
+                    node.world.apply_clips(&inherited);
+                    if let Some(clip) = node.local.local_clip {
+                        node.world.clip_node = Some(id);
+                        node.world.clip_quad =
+                            Some(transform_rect_quad(node.world.world_transform, clip.rect()));
+                        node.world.clip_parent = inherited.clip_node;
+                        node.world.clip_exact =
+                            inherited.clip_exact && is_axis_aligned(node.world.world_transform);
+                    } else {
+                        node.world.clip_node = inherited.clip_node;
+                        node.world.clip_quad = None;
+                        node.world.clip_parent = None;
+                        node.world.clip_exact = inherited.clip_exact;
+                    }
+                    node.world.layer = if is_layer { Some(id) } else { inherited.layer };
+                    // Children only need recomputing if what they inherit changed.
+                    let children_changed = dirty.added
+                        || dirty.scroll
+                        || old_world.world_transform != node.world.world_transform
+                        || old_world.world_clip != node.world.world_clip
+                        || old_world.clip_node != node.world.clip_node
+                        || old_world.clip_exact != node.world.clip_exact
+                        || old_world.clip_empty != node.world.clip_empty
+                        || old_world.layer != node.world.layer;
+                    if children_changed {
+                        ParentChange::Any
+                    } else {
+                        ParentChange::None
+                    }
                 }
-            }
+            };
 
-            // Push all children to the stack. The `.rev()` is not strictly necessary, but means we
+            // Push the children to the stack. The `.rev()` is not strictly necessary, but means we
             // visit the children in the order they are given in `node.children`.
-            for &child in node.children.iter().rev() {
-                stack.push((child, node.world.world_transform, world_clip));
+            let node = self.node(id);
+            if children_change != ParentChange::None || descend {
+                let for_children = node.inherited();
+                for &child in node.children.iter().rev() {
+                    stack.push((child, for_children, children_change));
+                }
+            }
+
+            self.sync_world(id, &old_world, dirty, damage);
+        }
+    }
+
+    /// Report damage and changes for a node whose world state was updated from `old_world`,
+    /// and move its entry in the spatial index.
+    fn sync_world(&mut self, id: NodeId, old_world: &WorldNode, dirty: Dirty, damage: &mut Damage) {
+        let track_changes = self.track_changes;
+        let node = self.node(id);
+        let old_world_bounds = old_world.world_bounds;
+        let world_bounds = node.world.world_bounds;
+        let added = dirty.added;
+
+        if old_world_bounds != world_bounds {
+            if old_world_bounds.width() > 0.0 && old_world_bounds.height() > 0.0 {
+                damage.dirty_rects.push(old_world_bounds);
+            }
+            if world_bounds.width() > 0.0 && world_bounds.height() > 0.0 {
+                damage.dirty_rects.push(world_bounds);
             }
+        }
 
-            if let Some(key) = node.index_key {
-                self.index.update(key, aabb);
+        if track_changes {
+            let mut kind = ChangeKind::empty();
+            if added {
+                kind |= ChangeKind::ADDED;
             } else {
-                let key = self.index.insert(aabb, id);
+                kind.set(ChangeKind::MOVED, old_world_bounds != world_bounds);
+                kind.set(
+                    ChangeKind::CLIPPED,
+                    dirty.clip || old_world.world_clip != node.world.world_clip,
+                );
+                kind.set(ChangeKind::FLAGS, dirty.flags);
+                kind.set(ChangeKind::Z_ORDER, dirty.z);
+            }
+            if !kind.is_empty() {
+                damage.changes.push(NodeChange {
+                    id,
+                    kind,
+                    old_bounds: (!added).then_some(old_world_bounds),
+                    new_bounds: Some(world_bounds),
+                });
+            }
+        }
+
+        let aabb = rect_to_aabb(world_bounds);
+        let layer = node.world.layer;
+        match node.index_key {
+            Some(key) if old_world.layer != layer => {
+                self.layer_index_mut(old_world.layer).remove(key);
+                let key = self.layer_index_mut(layer).insert(aabb, id);
+                self.node_mut(id).index_key = Some(key);
+            }
+            Some(key) => {
+                if old_world_bounds != world_bounds {
+                    self.layer_index_mut(layer).update(key, aabb);
+                }
+            }
+            None => {
+                let key = self.layer_index_mut(layer).insert(aabb, id);
                 self.node_mut(id).index_key = Some(key);
             }
         }
@@ -1830,4 +2956,984 @@ mod tests {
         let prev = tree.prev_depth_first(a).unwrap();
         assert_eq!(prev, root);
     }
+
+    #[test]
+    fn precise_hit_uses_shape_and_falls_through() {
+        use kurbo::Circle;
+        use understory_precise_hit::HitKind;
+
+        let mut tree = Tree::new();
+        let root = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 200.0, 200.0),
+                ..Default::default()
+            },
+        );
+        let icon = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 40.0, 40.0),
+                local_transform: Affine::translate(Vec2::new(50.0, 50.0)),
+                z_index: 1,
+                ..Default::default()
+            },
+        );
+        tree.set_hit_shape(icon, Circle::new((20.0, 20.0), 20.0));
+        assert!(tree.has_hit_shape(icon));
+        let _ = tree.commit();
+
+        let params = HitParams::default();
+
+        // Center of the circle hits the icon.
+        let (hit, score) = tree
+            .hit_test_point_precise(Point::new(70.0, 70.0), QueryFilter::new(), &params)
+            .unwrap();
+        assert_eq!(hit.node, icon);
+        assert_eq!(hit.path, vec![root, icon]);
+        assert_eq!(score.kind, HitKind::Fill);
+        assert_eq!(score.distance, 0.0);
+
+        // The box corner is outside the circle, so the hit falls through to the root.
+        let corner = Point::new(52.0, 52.0);
+        let (hit, _) = tree
+            .hit_test_point_precise(corner, QueryFilter::new(), &params)
+            .unwrap();
+        assert_eq!(hit.node, root);
+        // The bounds-only query still reports the icon there.
+        assert_eq!(
+            tree.hit_test_point(corner, QueryFilter::new())
+                .map(|h| h.node),
+            Some(icon)
+        );
+
+        // Without a shape, the node's local bounds are used.
+        tree.clear_hit_shape(icon);
+        let (hit, _) = tree
+            .hit_test_point_precise(corner, QueryFilter::new(), &params)
+            .unwrap();
+        assert_eq!(hit.node, icon);
+    }
+
+    #[test]
+    fn precise_hit_prefers_closer_score_at_equal_z() {
+        use kurbo::Circle;
+
+        let mut tree = Tree::new();
+        let a = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                ..Default::default()
+            },
+        );
+        let b = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                ..Default::default()
+            },
+        );
+        // `a` is hit exactly, `b` (newer) only within tolerance.
+        tree.set_hit_shape(a, Circle::new((50.0, 50.0), 10.0));
+        tree.set_hit_shape(b, Circle::new((50.0, 70.0), 8.0));
+        let _ = tree.commit();
+
+        let params = HitParams {
+            fill_tolerance: 5.0,
+            ..HitParams::default()
+        };
+        let (hit, score) = tree
+            .hit_test_point_precise(Point::new(50.0, 58.0), QueryFilter::new(), &params)
+            .unwrap();
+        assert_eq!(hit.node, a, "closer precise hit wins at equal z");
+        assert_eq!(score.distance, 0.0);
+
+        // Stale ids are ignored by the shape setters.
+        tree.remove(b);
+        tree.set_hit_shape(b, Circle::new((0.0, 0.0), 1.0));
+        assert!(!tree.has_hit_shape(b));
+    }
+
+    #[test]
+    fn hit_test_all_in_paint_order_with_tie_break() {
+        let mut tree = Tree::new();
+        let root = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 200.0, 200.0),
+                ..Default::default()
+            },
+        );
+        let a = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                ..Default::default()
+            },
+        );
+        let b = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                ..Default::default()
+            },
+        );
+        let top = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                z_index: 3,
+                ..Default::default()
+            },
+        );
+        // Outside the point; must not be reported.
+        let _far = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(150.0, 150.0, 160.0, 160.0),
+                ..Default::default()
+            },
+        );
+        let _ = tree.commit();
+
+        let pt = Point::new(50.0, 50.0);
+        let nodes = |tree: &Tree| -> Vec<NodeId> {
+            tree.hit_test_point_all(pt, QueryFilter::new())
+                .into_iter()
+                .map(|h| h.node)
+                .collect()
+        };
+
+        assert_eq!(tree.tie_break(), TieBreakPolicy::Newer);
+        assert_eq!(nodes(&tree), vec![root, a, b, top]);
+        let all = tree.hit_test_point_all(pt, QueryFilter::new());
+        assert_eq!(all[1].path, vec![root, a]);
+        assert_eq!(
+            all.last().map(|h| h.node),
+            tree.hit_test_point(pt, QueryFilter::new()).map(|h| h.node)
+        );
+
+        // Flip the tie-break between the equal-z, equal-depth siblings.
+        tree.set_tie_break(TieBreakPolicy::Older);
+        assert_eq!(
+            nodes(&tree),
+            vec![root, a, b, top],
+            "paint order ignores the tie-break"
+        );
+        tree.set_z_index(top, 0);
+        let _ = tree.commit();
+        // `top` is a shallower root now, so the deepest oldest sibling wins.
+        assert_eq!(
+            tree.hit_test_point(pt, QueryFilter::new()).map(|h| h.node),
+            Some(a)
+        );
+
+        tree.set_tie_break(TieBreakPolicy::MaxId);
+        assert_eq!(
+            tree.hit_test_point(pt, QueryFilter::new()).map(|h| h.node),
+            Some(b)
+        );
+        tree.set_tie_break(TieBreakPolicy::MinId);
+        assert_eq!(nodes(&tree), vec![root, a, b, top]);
+        assert_eq!(
+            tree.hit_test_point(pt, QueryFilter::new()).map(|h| h.node),
+            Some(a)
+        );
+    }
+
+    #[test]
+    fn hit_test_all_ranks_deep_child_below_later_sibling() {
+        let mut tree = Tree::new();
+        let bounds = LocalNode {
+            local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+            ..Default::default()
+        };
+        let root = tree.insert(None, bounds.clone());
+        let panel = tree.insert(Some(root), bounds.clone());
+        let deep = tree.insert(Some(panel), bounds.clone());
+        let sibling = tree.insert(Some(root), bounds.clone());
+        let later_root = tree.insert(None, bounds);
+        let _ = tree.commit();
+
+        // All at z 0: paint order is tree order, so the deep child paints below the later
+        // sibling and the later root, even though it wins the hit test by depth.
+        let pt = Point::new(50.0, 50.0);
+        let all: Vec<_> = tree
+            .hit_test_point_all(pt, QueryFilter::new())
+            .into_iter()
+            .map(|h| h.node)
+            .collect();
+        assert_eq!(all, vec![root, panel, deep, sibling, later_root]);
+        assert_eq!(all, tree.paint_order().collect::<Vec<_>>());
+        assert_eq!(
+            tree.hit_test_point(pt, QueryFilter::new()).map(|h| h.node),
+            Some(deep)
+        );
+    }
+
+    #[test]
+    fn visible_in_paint_order_sorts_by_z_then_tree_order() {
+        let mut tree = Tree::new();
+        let root = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                ..Default::default()
+            },
+        );
+        let a = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 10.0, 10.0),
+                z_index: 1,
+                ..Default::default()
+            },
+        );
+        let b = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 10.0, 10.0),
+                ..Default::default()
+            },
+        );
+        let b_child = tree.insert(
+            Some(b),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 10.0, 10.0),
+                ..Default::default()
+            },
+        );
+        let offscreen = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(500.0, 500.0, 510.0, 510.0),
+                ..Default::default()
+            },
+        );
+        let _ = tree.commit();
+
+        let viewport = Rect::new(0.0, 0.0, 50.0, 50.0);
+        let visible: Vec<_> = tree
+            .visible_in_paint_order(viewport, QueryFilter::new())
+            .collect();
+        assert_eq!(visible, vec![root, b, b_child, a]);
+        let all: Vec<_> = tree.paint_order().collect();
+        assert_eq!(all, vec![root, b, b_child, offscreen, a]);
+
+        // Z changes only take effect on commit.
+        tree.set_z_index(a, -1);
+        let stale: Vec<_> = tree
+            .visible_in_paint_order(viewport, QueryFilter::new())
+            .collect();
+        assert_eq!(stale, visible);
+        let _ = tree.commit();
+        let visible: Vec<_> = tree
+            .visible_in_paint_order(viewport, QueryFilter::new())
+            .collect();
+        assert_eq!(visible, vec![a, root, b, b_child]);
+
+        // Removed nodes drop out; reparenting moves the subtree in tree order.
+        tree.remove(offscreen);
+        tree.reparent(b_child, Some(root));
+        tree.reparent(b, None);
+        let _ = tree.commit();
+        let all: Vec<_> = tree.paint_order().collect();
+        assert_eq!(all, vec![a, root, b_child, b]);
+    }
+
+    #[test]
+    fn stacking_context_scopes_descendant_z() {
+        let mut tree = Tree::new();
+        let root = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                ..Default::default()
+            },
+        );
+        let dialog = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                z_index: 1,
+                ..Default::default()
+            },
+        );
+        let dialog_child = tree.insert(
+            Some(dialog),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                z_index: 1000,
+                ..Default::default()
+            },
+        );
+        let tooltip = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                z_index: 5,
+                ..Default::default()
+            },
+        );
+        let _ = tree.commit();
+
+        let pt = Point::new(50.0, 50.0);
+        let top = |tree: &Tree| tree.hit_test_point(pt, QueryFilter::new()).map(|h| h.node);
+
+        // Global z: the dialog's child escapes above the tooltip.
+        assert_eq!(top(&tree), Some(dialog_child));
+        let order: Vec<_> = tree.paint_order().collect();
+        assert_eq!(order, vec![root, dialog, tooltip, dialog_child]);
+
+        // Scoped z: the dialog subtree is ordered by the dialog's own z.
+        tree.set_flags(dialog, NodeFlags::default() | NodeFlags::STACKING_CONTEXT);
+        let _ = tree.commit();
+        assert_eq!(top(&tree), Some(tooltip));
+        let order: Vec<_> = tree.paint_order().collect();
+        assert_eq!(order, vec![root, dialog, dialog_child, tooltip]);
+        let all: Vec<_> = tree
+            .hit_test_point_all(pt, QueryFilter::new())
+            .into_iter()
+            .map(|h| h.node)
+            .collect();
+        assert_eq!(all, vec![root, dialog, dialog_child, tooltip]);
+
+        // Raising the dialog above the tooltip brings its whole subtree along.
+        tree.set_z_index(dialog, 10);
+        let _ = tree.commit();
+        assert_eq!(top(&tree), Some(dialog_child));
+        let order: Vec<_> = tree.paint_order().collect();
+        assert_eq!(order, vec![root, tooltip, dialog, dialog_child]);
+        let (hit, _) = tree
+            .hit_test_point_precise(pt, QueryFilter::new(), &HitParams::default())
+            .unwrap();
+        assert_eq!(hit.node, dialog_child);
+    }
+
+    #[test]
+    fn rotated_clip_is_exact_for_rect_queries_and_visibility() {
+        let mut tree = Tree::new();
+        // A 100x100 clip rotated by 45 degrees about the origin: a diamond whose AABB spans
+        // roughly (-70.7..70.7, 0..141.4).
+        let frame = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                local_transform: Affine::rotate(45_f64.to_radians()),
+                local_clip: Some(RoundedRect::from_rect(
+                    Rect::new(0.0, 0.0, 100.0, 100.0),
+                    0.0,
+                )),
+                ..Default::default()
+            },
+        );
+        // Undo the rotation so the content's children are placed in world coordinates.
+        let content = tree.insert(
+            Some(frame),
+            LocalNode {
+                local_bounds: Rect::new(-100.0, -100.0, 100.0, 200.0),
+                local_transform: Affine::rotate(-45_f64.to_radians()),
+                ..Default::default()
+            },
+        );
+        let corner = tree.insert(
+            Some(content),
+            LocalNode {
+                local_bounds: Rect::new(50.0, 5.0, 60.0, 15.0),
+                ..Default::default()
+            },
+        );
+        let center = tree.insert(
+            Some(content),
+            LocalNode {
+                local_bounds: Rect::new(-5.0, 65.0, 5.0, 75.0),
+                ..Default::default()
+            },
+        );
+        let _ = tree.commit();
+
+        // Both are inside the clip's AABB, so the index alone would report both.
+        let clip_aabb = tree.world_bounds(frame).unwrap();
+        assert!(clip_aabb.contains(Point::new(55.0, 10.0)));
+
+        let query = Rect::new(-100.0, 0.0, 100.0, 200.0);
+        let found: Vec<_> = tree.intersect_rect(query, QueryFilter::new()).collect();
+        assert!(found.contains(&center));
+        assert!(!found.contains(&corner), "outside the rotated clip");
+
+        assert!(tree.is_visible(center));
+        assert!(!tree.is_visible(corner));
+
+        tree.set_flags(center, NodeFlags::PICKABLE);
+        let _ = tree.commit();
+        assert!(!tree.is_visible(center), "hidden by flags");
+        tree.remove(center);
+        assert!(!tree.is_visible(center), "stale ids are not visible");
+    }
+
+    #[test]
+    fn clip_chain_uses_committed_clips_until_next_commit() {
+        let mut tree = Tree::new();
+        let frame = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                local_transform: Affine::rotate(45_f64.to_radians()),
+                local_clip: Some(RoundedRect::from_rect(
+                    Rect::new(0.0, 0.0, 100.0, 100.0),
+                    0.0,
+                )),
+                ..Default::default()
+            },
+        );
+        let content = tree.insert(
+            Some(frame),
+            LocalNode {
+                local_bounds: Rect::new(-100.0, -100.0, 100.0, 200.0),
+                local_transform: Affine::rotate(-45_f64.to_radians()),
+                ..Default::default()
+            },
+        );
+        // Inside the rotated clip's AABB, but outside the clip itself.
+        let corner = tree.insert(
+            Some(content),
+            LocalNode {
+                local_bounds: Rect::new(50.0, 5.0, 60.0, 15.0),
+                ..Default::default()
+            },
+        );
+        let _ = tree.commit();
+        let query = Rect::new(-100.0, 0.0, 100.0, 200.0);
+
+        // Removing the clip only takes effect on commit; queries must not panic meanwhile.
+        tree.set_local_clip(frame, None);
+        assert!(!tree.is_visible(corner));
+        assert!(
+            !tree
+                .intersect_rect(query, QueryFilter::new())
+                .any(|id| id == corner)
+        );
+        let _ = tree.commit();
+        assert!(tree.is_visible(corner));
+
+        // A removed link falls back to the committed clip AABB, which contains the node.
+        tree.set_local_clip(
+            frame,
+            Some(RoundedRect::from_rect(
+                Rect::new(0.0, 0.0, 100.0, 100.0),
+                0.0,
+            )),
+        );
+        let _ = tree.commit();
+        assert!(!tree.is_visible(corner));
+        tree.reparent(content, None);
+        tree.remove(frame);
+        assert!(tree.is_visible(corner));
+        let _ = tree
+            .intersect_rect(query, QueryFilter::new())
+            .collect::<Vec<_>>();
+        let _ = tree.commit();
+        assert!(tree.is_visible(corner));
+    }
+
+    #[test]
+    fn change_tracking_reports_per_node_changes() {
+        let mut tree = Tree::new();
+        tree.set_change_tracking(true);
+        let root = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                ..Default::default()
+            },
+        );
+        let a = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 10.0, 10.0),
+                ..Default::default()
+            },
+        );
+        let b = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(20.0, 20.0, 30.0, 30.0),
+                ..Default::default()
+            },
+        );
+        let dmg = tree.commit();
+        assert_eq!(dmg.changes.len(), 3);
+        assert!(dmg.changes.iter().all(|c| c.kind == ChangeKind::ADDED
+            && c.old_bounds.is_none()
+            && c.new_bounds.is_some()));
+
+        // Nothing changed: no entries.
+        assert!(tree.commit().changes.is_empty());
+
+        tree.set_local_transform(a, Affine::translate(Vec2::new(5.0, 0.0)));
+        tree.set_flags(a, NodeFlags::VISIBLE);
+        tree.set_z_index(root, 2);
+        tree.remove(b);
+        let dmg = tree.commit();
+        assert_eq!(
+            dmg.changes,
+            vec![
+                NodeChange {
+                    id: b,
+                    kind: ChangeKind::REMOVED,
+                    old_bounds: Some(Rect::new(20.0, 20.0, 30.0, 30.0)),
+                    new_bounds: None,
+                },
+                NodeChange {
+                    id: root,
+                    kind: ChangeKind::Z_ORDER,
+                    old_bounds: Some(Rect::new(0.0, 0.0, 100.0, 100.0)),
+                    new_bounds: Some(Rect::new(0.0, 0.0, 100.0, 100.0)),
+                },
+                NodeChange {
+                    id: a,
+                    kind: ChangeKind::MOVED | ChangeKind::FLAGS,
+                    old_bounds: Some(Rect::new(0.0, 0.0, 10.0, 10.0)),
+                    new_bounds: Some(Rect::new(5.0, 0.0, 15.0, 10.0)),
+                },
+            ]
+        );
+
+        tree.set_local_clip(
+            root,
+            Some(RoundedRect::from_rect(Rect::new(0.0, 0.0, 8.0, 8.0), 0.0)),
+        );
+        let dmg = tree.commit();
+        let kinds: Vec<_> = dmg.changes.iter().map(|c| (c.id, c.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (root, ChangeKind::MOVED | ChangeKind::CLIPPED),
+                (a, ChangeKind::MOVED | ChangeKind::CLIPPED),
+            ]
+        );
+
+        // Disabled tracking reports nothing.
+        tree.set_change_tracking(false);
+        tree.remove(a);
+        assert!(tree.commit().changes.is_empty());
+    }
+
+    #[test]
+    fn scroll_offset_moves_descendants_within_clip() {
+        let mut tree = Tree::new();
+        tree.set_change_tracking(true);
+        let viewport = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let scroller = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: viewport,
+                local_clip: Some(RoundedRect::from_rect(viewport, 0.0)),
+                ..Default::default()
+            },
+        );
+        let rows: Vec<NodeId> = (0..20)
+            .map(|i| {
+                let y = f64::from(i) * 50.0;
+                tree.insert(
+                    Some(scroller),
+                    LocalNode {
+                        local_bounds: Rect::new(0.0, y, 100.0, y + 50.0),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+        let sibling = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(200.0, 0.0, 300.0, 100.0),
+                ..Default::default()
+            },
+        );
+        let _ = tree.commit();
+
+        tree.set_scroll_offset(scroller, Vec2::new(0.0, 210.0));
+        assert_eq!(tree.scroll_offset(scroller), Some(Vec2::new(0.0, 210.0)));
+        let dmg = tree.commit();
+
+        // Only the scrolled content is reported; the scroller and its sibling did not move.
+        assert!(dmg.changes.iter().all(|c| rows.contains(&c.id)));
+        assert!(
+            !dmg.changes
+                .iter()
+                .any(|c| c.id == sibling || c.id == scroller)
+        );
+        assert_eq!(tree.world_bounds(scroller), Some(viewport));
+
+        // Row 4 (y 200..250) is now at 0..40 after clipping, row 5 at 40..90.
+        let hit = tree.hit_test_point(Point::new(10.0, 50.0), QueryFilter::new());
+        assert_eq!(hit.map(|h| h.node), Some(rows[5]));
+        assert_eq!(
+            tree.world_transform(rows[0]),
+            Some(Affine::translate(Vec2::new(0.0, -210.0)))
+        );
+
+        // Rows scrolled out of the viewport are excluded, although their clamped AABBs sit on its edge.
+        let visible: Vec<_> = tree
+            .visible_in_paint_order(viewport, QueryFilter::new())
+            .collect();
+        assert_eq!(visible, vec![scroller, rows[4], rows[5], rows[6]]);
+        assert!(!tree.is_visible(rows[0]));
+        assert!(tree.is_visible(rows[6]));
+
+        // A point in the viewport above the content hits the scroller, not the scrolled-out row.
+        tree.set_scroll_offset(scroller, Vec2::new(0.0, -60.0));
+        let _ = tree.commit();
+        let hit = tree.hit_test_point(Point::new(10.0, 30.0), QueryFilter::new());
+        assert_eq!(hit.map(|h| h.node), Some(scroller));
+        let hit = tree.hit_test_point(Point::new(10.0, 70.0), QueryFilter::new());
+        assert_eq!(hit.map(|h| h.node), Some(rows[0]));
+    }
+
+    #[test]
+    fn scroll_shifts_descendants_without_recomputing_them() {
+        let mut tree = Tree::new();
+        tree.set_change_tracking(true);
+        let viewport = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let scroller = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: viewport,
+                local_clip: Some(RoundedRect::from_rect(viewport, 0.0)),
+                local_transform: Affine::translate((10.0, 10.0)) * Affine::scale(2.0),
+                ..Default::default()
+            },
+        );
+        let card = tree.insert(
+            Some(scroller),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 200.0),
+                local_clip: Some(RoundedRect::from_rect(
+                    Rect::new(0.0, 0.0, 100.0, 100.0),
+                    0.0,
+                )),
+                ..Default::default()
+            },
+        );
+        let label = tree.insert(
+            Some(card),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 50.0, 50.0, 150.0),
+                ..Default::default()
+            },
+        );
+        let _ = tree.commit();
+        assert_eq!(tree.recomputed, 3);
+
+        // Only the scroller is dirty, and it and its descendants are all shifted.
+        tree.set_scroll_offset(scroller, Vec2::new(0.0, 40.0));
+        let dmg = tree.commit();
+        assert_eq!(tree.recomputed, 0);
+        assert_eq!(
+            tree.world_transform(label),
+            Some(Affine::translate((10.0, -70.0)) * Affine::scale(2.0))
+        );
+        // The card's clip moved with it: the label is cut at the card's bottom, then the viewport's.
+        assert_eq!(
+            tree.world_bounds(card),
+            Some(Rect::new(10.0, 10.0, 210.0, 130.0))
+        );
+        assert_eq!(
+            tree.world_bounds(label),
+            Some(Rect::new(10.0, 30.0, 110.0, 130.0))
+        );
+        let ids: Vec<_> = dmg.changes.iter().map(|c| c.id).collect();
+        assert!(set_equality(&ids, &[card, label]));
+        let hit = tree.hit_test_point(Point::new(20.0, 50.0), QueryFilter::new());
+        assert_eq!(hit.map(|h| h.node), Some(label));
+        let hit = tree.hit_test_point(Point::new(20.0, 150.0), QueryFilter::new());
+        assert_eq!(hit.map(|h| h.node), Some(scroller));
+
+        // A descendant dirtied in the same frame is still recomputed, on top of the shift.
+        tree.set_scroll_offset(scroller, Vec2::ZERO);
+        tree.set_local_transform(label, Affine::translate((0.0, -50.0)));
+        let _ = tree.commit();
+        assert_eq!(tree.recomputed, 1);
+        assert_eq!(
+            tree.world_bounds(label),
+            Some(Rect::new(10.0, 10.0, 110.0, 210.0))
+        );
+    }
+
+    #[test]
+    fn removal_after_clear_layer_is_tracked() {
+        let mut tree = Tree::new();
+        tree.set_change_tracking(true);
+        let root = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                ..Default::default()
+            },
+        );
+        let layer = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 50.0, 50.0),
+                ..Default::default()
+            },
+        );
+        let child = tree.insert(
+            Some(layer),
+            LocalNode {
+                local_bounds: Rect::new(10.0, 10.0, 20.0, 20.0),
+                ..Default::default()
+            },
+        );
+        tree.set_layer(layer, FlatVec::default());
+        let _ = tree.commit();
+
+        // Clearing the layer detaches the entries; removing before the next commit must still
+        // report both nodes.
+        tree.clear_layer(layer);
+        tree.remove(layer);
+        let dmg = tree.commit();
+        let removed: Vec<_> = dmg
+            .changes
+            .iter()
+            .filter(|c| c.kind == ChangeKind::REMOVED)
+            .map(|c| (c.id, c.old_bounds))
+            .collect();
+        assert!(removed.contains(&(child, Some(Rect::new(10.0, 10.0, 20.0, 20.0)))));
+        assert!(removed.contains(&(layer, Some(Rect::new(0.0, 0.0, 50.0, 50.0)))));
+        assert_eq!(removed.len(), 2);
+
+        // A node inserted and removed between commits is not reported.
+        let transient = tree.insert(Some(root), LocalNode::default());
+        tree.remove(transient);
+        assert!(tree.commit().changes.is_empty());
+    }
+
+    #[test]
+    fn layers_partition_the_index_and_report_damage() {
+        use understory_index::backends::GridF64;
+
+        let mut tree: Tree<GridF64> = Tree::with_backend(GridF64::new(50.0));
+        let root = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 1000.0, 1000.0),
+                ..Default::default()
+            },
+        );
+        let map = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 1000.0, 1000.0),
+                ..Default::default()
+            },
+        );
+        let tile = tree.insert(
+            Some(map),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 500.0, 500.0),
+                ..Default::default()
+            },
+        );
+        let overlay = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(100.0, 100.0, 200.0, 200.0),
+                z_index: 1,
+                ..Default::default()
+            },
+        );
+        tree.set_layer(map, GridF64::new(250.0));
+        tree.set_layer(overlay, GridF64::new(10.0));
+        let dmg = tree.commit();
+        assert_eq!(
+            dmg.layers.iter().map(|l| l.layer).collect::<Vec<_>>(),
+            vec![None, Some(map), Some(overlay)]
+        );
+        assert!(tree.is_layer(map) && !tree.is_layer(tile));
+        assert_eq!(tree.layer_of(root), None);
+        assert_eq!(tree.layer_of(tile), Some(map));
+        assert_eq!(tree.layers().collect::<Vec<_>>(), vec![map, overlay]);
+
+        // Queries combine all layers and rank across them by z.
+        let hit = tree.hit_test_point(Point::new(150.0, 150.0), QueryFilter::new());
+        assert_eq!(hit.map(|h| h.node), Some(overlay));
+        let found: Vec<_> = tree
+            .intersect_rect(Rect::new(140.0, 140.0, 160.0, 160.0), QueryFilter::new())
+            .collect();
+        assert!(set_equality(&found, &[root, map, tile, overlay]));
+
+        // Moving the overlay only damages its own layer.
+        tree.set_local_transform(overlay, Affine::translate((10.0, 0.0)));
+        let dmg = tree.commit();
+        assert_eq!(dmg.layers.len(), 1);
+        assert_eq!(dmg.layers[0].layer, Some(overlay));
+        assert_eq!(dmg.layers[0].rect, Rect::new(100.0, 100.0, 210.0, 200.0));
+
+        // Clearing a layer moves its nodes back into the enclosing index.
+        tree.clear_layer(map);
+        let _ = tree.commit();
+        assert_eq!(tree.layer_of(tile), None);
+        let hit = tree.hit_test_point(Point::new(400.0, 400.0), QueryFilter::new());
+        assert_eq!(hit.map(|h| h.node), Some(tile));
+
+        // Removing a layer root reports the removal against the stale layer.
+        tree.remove(overlay);
+        let dmg = tree.commit();
+        assert_eq!(dmg.layers.len(), 1);
+        assert_eq!(dmg.layers[0].layer, Some(overlay));
+        assert_eq!(tree.layers().count(), 0);
+        assert!(dmg.dirty_rects.contains(&dmg.layers[0].rect));
+    }
+
+    #[test]
+    fn attachments_live_with_the_node_and_filter_queries() {
+        #[derive(Debug, PartialEq)]
+        struct Role(&'static str);
+
+        let mut tree = Tree::new();
+        let back = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                ..Default::default()
+            },
+        );
+        let front = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                z_index: 1,
+                ..Default::default()
+            },
+        );
+        assert_eq!(tree.set_attachment(back, Role("button")), None);
+        assert_eq!(tree.set_attachment(front, Role("label")), None);
+        assert_eq!(tree.set_attachment(front, 7_u32), None);
+        assert_eq!(
+            tree.set_attachment(front, Role("tooltip")),
+            Some(Role("label"))
+        );
+        let _ = tree.commit();
+        assert_eq!(tree.attachment::<Role>(front), Some(&Role("tooltip")));
+        assert_eq!(tree.attachment::<u32>(back), None);
+        *tree.attachment_mut::<u32>(front).unwrap() += 1;
+        assert_eq!(tree.attachment::<u32>(front), Some(&8));
+
+        // The predicate skips the topmost node, so the one below it wins.
+        let pt = Point::new(50.0, 50.0);
+        let (hit, role) = tree
+            .hit_test_point_with::<Role, _>(pt, QueryFilter::new(), |_, r| r.0 == "button")
+            .unwrap();
+        assert_eq!((hit.node, role), (back, &Role("button")));
+        let (hit, _) = tree
+            .hit_test_point_with::<u32, _>(pt, QueryFilter::new(), |_, _| true)
+            .unwrap();
+        assert_eq!(hit.node, front);
+        let found: Vec<_> = tree
+            .intersect_rect_with::<u32, _>(
+                Rect::new(0.0, 0.0, 10.0, 10.0),
+                QueryFilter::new(),
+                |_, n| *n > 7,
+            )
+            .collect();
+        assert_eq!(found, vec![(front, &8)]);
+
+        // Attachments are dropped with the node and do not leak into a reused slot.
+        assert_eq!(tree.remove_attachment::<u32>(front), Some(8));
+        assert_eq!(tree.remove_attachment::<u32>(front), None);
+        tree.remove(back);
+        let reused = tree.insert(None, LocalNode::default());
+        assert_eq!(reused.idx(), back.idx());
+        assert_eq!(tree.attachment::<Role>(reused), None);
+        assert_eq!(tree.attachment::<Role>(back), None);
+        assert_eq!(tree.set_attachment(back, 1_u8), Some(1));
+    }
+
+    #[test]
+    fn query_filter_flag_sets_scope_and_predicate() {
+        const DISABLED: NodeFlags = NodeFlags::custom(0);
+        const DRAGGED: NodeFlags = NodeFlags::custom(15);
+
+        let mut tree = Tree::new();
+        let root = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                ..Default::default()
+            },
+        );
+        let panel = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 50.0, 50.0),
+                ..Default::default()
+            },
+        );
+        let button = tree.insert(
+            Some(panel),
+            LocalNode {
+                local_bounds: Rect::new(10.0, 10.0, 20.0, 20.0),
+                flags: NodeFlags::default() | NodeFlags::FOCUSABLE,
+                ..Default::default()
+            },
+        );
+        let disabled = tree.insert(
+            Some(panel),
+            LocalNode {
+                local_bounds: Rect::new(10.0, 10.0, 20.0, 20.0),
+                flags: NodeFlags::default() | NodeFlags::FOCUSABLE | DISABLED,
+                ..Default::default()
+            },
+        );
+        let dragged = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(10.0, 10.0, 20.0, 20.0),
+                z_index: 1,
+                flags: NodeFlags::default() | DRAGGED,
+                ..Default::default()
+            },
+        );
+        let _ = tree.commit();
+        assert_ne!(DISABLED, DRAGGED);
+        assert!(NodeFlags::all().contains(DRAGGED));
+
+        let pt = Point::new(15.0, 15.0);
+        let top = |filter: QueryFilter<'_>| tree.hit_test_point(pt, filter).map(|h| h.node);
+        assert_eq!(top(QueryFilter::new()), Some(dragged));
+        assert_eq!(top(QueryFilter::new().none_of(DRAGGED)), Some(disabled));
+        assert_eq!(
+            top(QueryFilter::new().none_of(DRAGGED | DISABLED)),
+            Some(button)
+        );
+        assert_eq!(
+            top(QueryFilter::new().any_of(DISABLED | DRAGGED)),
+            Some(dragged)
+        );
+
+        // Scope restricts to a subtree, inclusive of the scope node itself.
+        let scoped = QueryFilter::new().within(panel);
+        assert_eq!(top(scoped), Some(disabled));
+        let found: Vec<_> = tree.containing_point(pt, scoped).collect();
+        assert!(set_equality(&found, &[panel, button, disabled]));
+
+        // The predicate can exclude a specific node and inspect the tree.
+        let not_disabled = |id: NodeId| tree.flags(id).is_some_and(|f| !f.contains(DISABLED));
+        let filter = QueryFilter::new()
+            .focusable()
+            .within(panel)
+            .predicate(&not_disabled);
+        assert_eq!(top(filter), Some(button));
+        let found: Vec<_> = tree
+            .intersect_rect(Rect::new(0.0, 0.0, 100.0, 100.0), filter)
+            .collect();
+        assert_eq!(found, vec![button]);
+        assert!(tree.matches_filter(button, filter));
+        assert!(!tree.matches_filter(disabled, filter));
+        assert!(!tree.matches_filter(dragged, filter));
+        assert!(!filter.matches(NodeFlags::VISIBLE));
+    }
 }
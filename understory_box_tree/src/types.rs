@@ -3,7 +3,7 @@
 
 //! Public types for the box tree: node identifiers, flags, and local geometry.
 
-use kurbo::{Affine, Rect, RoundedRect};
+use kurbo::{Affine, Rect, RoundedRect, Vec2};
 
 /// Identifier for a node in the tree.
 ///
@@ -22,7 +22,7 @@ use kurbo::{Affine, Rect, RoundedRect};
 /// A `NodeId` is considered newer than another when it has a higher generation.
 /// If generations are equal, the one with the higher slot index is considered newer.
 /// This total order is used only for deterministic tie-breaks in
-/// [hit testing](crate::Tree::hit_test_point).
+/// [hit testing](crate::Tree::hit_test_point) under [`TieBreakPolicy::Newer`], the default.
 ///
 /// ### Liveness
 ///
@@ -48,14 +48,51 @@ impl NodeId {
 
 bitflags::bitflags! {
     /// Node flags controlling visibility, picking, and focus behavior.
+    ///
+    /// The low 16 bits are reserved for flags defined by this crate. The high 16 bits are
+    /// free for host-defined flags (for example "disabled" or "being dragged"), created with
+    /// [`NodeFlags::custom`] and matched by [`QueryFilter`](crate::QueryFilter) like any other
+    /// flag.
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-    pub struct NodeFlags: u8 {
+    pub struct NodeFlags: u32 {
         /// Node is visible (participates in rendering and intersection queries).
         const VISIBLE  = 0b0000_0001;
         /// Node is pickable (participates in hit testing).
         const PICKABLE = 0b0000_0010;
         /// Node is focusable (can receive keyboard focus).
         const FOCUSABLE = 0b0000_0100;
+        /// Node creates a stacking context: its descendants' `z_index` values are only
+        /// compared among themselves, and the subtree is ordered as a unit by this node's
+        /// own `z_index` in paint order and hit testing.
+        const STACKING_CONTEXT = 0b0000_1000;
+
+        // Host-defined flags; see `NodeFlags::custom`.
+        const _ = 0xFFFF_0000;
+    }
+}
+
+impl NodeFlags {
+    /// Number of host-defined flag bits available through [`NodeFlags::custom`].
+    pub const CUSTOM_BITS: u32 = 16;
+
+    /// Host-defined flag number `bit`, for `bit` in `0..NodeFlags::CUSTOM_BITS`.
+    ///
+    /// The tree attaches no meaning to these flags; they only participate in
+    /// [`QueryFilter`](crate::QueryFilter) matching. Hosts typically name them once:
+    ///
+    /// ```rust
+    /// use understory_box_tree::NodeFlags;
+    ///
+    /// const DISABLED: NodeFlags = NodeFlags::custom(0);
+    /// assert!(!NodeFlags::default().contains(DISABLED));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is out of range.
+    pub const fn custom(bit: u32) -> Self {
+        assert!(bit < Self::CUSTOM_BITS, "custom flag bit out of range");
+        Self::from_bits_retain(1 << (16 + bit))
     }
 }
 
@@ -66,6 +103,27 @@ impl Default for NodeFlags {
     }
 }
 
+/// Policy for breaking hit-testing ties after equal `z_index` and depth.
+///
+/// This mirrors `understory_responder::types::TieBreakPolicy` so that a box tree
+/// and a responder router configured with the same policy agree on which of two
+/// otherwise equal nodes is on top. Set it with
+/// [`Tree::set_tie_break`](crate::Tree::set_tie_break); the default is [`TieBreakPolicy::Newer`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum TieBreakPolicy {
+    /// Prefer the more recently created identifier (higher generation, then higher slot).
+    ///
+    /// See the [`NodeId`] docs for the definition of newer.
+    #[default]
+    Newer,
+    /// Prefer the less recently created identifier.
+    Older,
+    /// Prefer the smaller identifier (lower slot, then lower generation).
+    MinId,
+    /// Prefer the larger identifier (higher slot, then higher generation).
+    MaxId,
+}
+
 /// Local geometry for a node.
 #[derive(Clone, Debug)]
 pub struct LocalNode {
@@ -97,11 +155,13 @@ pub struct LocalNode {
     /// - Points outside `local_clip` (once transformed) cannot hit this node or any descendant.
     ///   Backends may still apply more precise clipping during rendering.
     pub local_clip: Option<RoundedRect>,
-    /// Z-order within the parent stacking context.
+    /// Z-order within the enclosing stacking context.
     ///
     /// - Higher values are drawn on top of siblings with lower values.
     /// - Hit testing also compares `z_index` across different parents when nodes overlap;
     ///   depth in the tree and insertion order are used as secondary tie-breakers.
+    /// - Without any [`NodeFlags::STACKING_CONTEXT`] nodes, this is a single global ordering.
+    ///   Inside a stacking context, it only orders nodes within that context.
     pub z_index: i32,
     /// Visibility and interaction flags.
     ///
@@ -111,6 +171,14 @@ pub struct LocalNode {
     ///
     /// Flags do not affect layout; they only influence queries and higher-level behavior.
     pub flags: NodeFlags,
+    /// Scroll offset applied to this node's descendants.
+    ///
+    /// - Descendants are translated by `-scroll_offset` in this node's local space, after
+    ///   `local_transform`; the node's own bounds and clip do not move.
+    /// - Pair it with a `local_clip` acting as the viewport so scrolled-out content is clipped.
+    /// - Prefer [`Tree::set_scroll_offset`](crate::Tree::set_scroll_offset) for updates; it only
+    ///   dirties this node instead of the whole subtree.
+    pub scroll_offset: Vec2,
 }
 
 impl Default for LocalNode {
@@ -122,6 +190,7 @@ impl Default for LocalNode {
             local_clip: None,
             z_index: 0,
             flags: NodeFlags::default(),
+            scroll_offset: Vec2::ZERO,
         }
     }
 }
@@ -1,7 +1,8 @@
 // Copyright 2025 the Understory Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use kurbo::{Affine, Rect};
+use alloc::vec::Vec;
+use kurbo::{Affine, Point, Rect};
 use understory_index::Aabb2D;
 
 /// Transform an axis-aligned [`Rect`] by an [`Affine`] and return a tight axis-aligned bounding
@@ -23,6 +24,66 @@ pub(crate) fn rect_to_aabb(r: Rect) -> Aabb2D<f64> {
     Aabb2D::new(r.x0, r.y0, r.x1, r.y1)
 }
 
+/// Returns `true` if the transform maps axis-aligned rectangles to axis-aligned rectangles
+/// (no rotation or shear), so [`transform_rect_bbox`] is exact.
+pub(crate) fn is_axis_aligned(affine: Affine) -> bool {
+    let [_, b, c, _, _, _] = affine.as_coeffs();
+    b == 0.0 && c == 0.0
+}
+
+/// Transform the corners of a [`Rect`] by an [`Affine`], producing a world-space quad.
+pub(crate) fn transform_rect_quad(affine: Affine, rect: Rect) -> [Point; 4] {
+    [
+        affine * Point::new(rect.x0, rect.y0),
+        affine * Point::new(rect.x1, rect.y0),
+        affine * Point::new(rect.x1, rect.y1),
+        affine * Point::new(rect.x0, rect.y1),
+    ]
+}
+
+/// Clip a polygon in place against a convex polygon (Sutherland–Hodgman).
+///
+/// Points on the clip boundary are kept, so polygons that only touch the clip are reduced
+/// to a degenerate but non-empty polygon, matching the inclusive-edge semantics of the
+/// spatial index. The clip polygon may have either winding; zero-length edges are ignored.
+pub(crate) fn clip_polygon(subject: &mut Vec<Point>, clip: &[Point]) {
+    let n = clip.len();
+    let mut area2 = 0.0;
+    for i in 0..n {
+        let (a, b) = (clip[i], clip[(i + 1) % n]);
+        area2 += a.x * b.y - b.x * a.y;
+    }
+    let sign = if area2 < 0.0 { -1.0 } else { 1.0 };
+
+    let mut input = Vec::with_capacity(subject.len() + n);
+    for i in 0..n {
+        let (a, b) = (clip[i], clip[(i + 1) % n]);
+        let edge = b - a;
+        if edge.hypot2() == 0.0 {
+            continue;
+        }
+        let side = |p: Point| sign * edge.cross(p - a);
+        core::mem::swap(&mut input, subject);
+        subject.clear();
+        let Some(&last) = input.last() else {
+            return;
+        };
+        let mut prev = last;
+        for &cur in &input {
+            let (sp, sc) = (side(prev), side(cur));
+            if sc >= 0.0 {
+                if sp < 0.0 {
+                    subject.push(prev.lerp(cur, sp / (sp - sc)));
+                }
+                subject.push(cur);
+            } else if sp >= 0.0 {
+                subject.push(prev.lerp(cur, sp / (sp - sc)));
+            }
+            prev = cur;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use kurbo::{Affine, Rect};
@@ -39,4 +100,32 @@ mod tests {
         let bbox_rotation = transform_rect_bbox(Affine::rotate(45_f64.to_radians()), rect);
         assert!((bbox_rotation.area() - 2. * rect.area()).abs() < 1e-8);
     }
+
+    #[test]
+    fn clip_polygon_against_rotated_quad() {
+        let diamond = transform_rect_quad(
+            Affine::rotate(45_f64.to_radians()),
+            Rect::new(-1.0, -1.0, 1.0, 1.0),
+        );
+
+        // A square in the diamond's bounding-box corner is clipped away entirely.
+        let mut corner =
+            transform_rect_quad(Affine::IDENTITY, Rect::new(1.0, 1.0, 1.4, 1.4)).to_vec();
+        clip_polygon(&mut corner, &diamond);
+        assert!(corner.is_empty(), "corner outside the rotated clip");
+
+        // A square at the center survives unchanged.
+        let mut center =
+            transform_rect_quad(Affine::IDENTITY, Rect::new(-0.1, -0.1, 0.1, 0.1)).to_vec();
+        clip_polygon(&mut center, &diamond);
+        assert_eq!(center.len(), 4);
+
+        // Reversed winding of the clip gives the same result.
+        let mut reversed = diamond;
+        reversed.reverse();
+        let mut corner =
+            transform_rect_quad(Affine::IDENTITY, Rect::new(1.0, 1.0, 1.4, 1.4)).to_vec();
+        clip_polygon(&mut corner, &reversed);
+        assert!(corner.is_empty(), "winding must not matter");
+    }
 }
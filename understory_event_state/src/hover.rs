@@ -42,9 +42,30 @@
 //!     HoverEvent::Enter(5)    // Enter sibling
 //! ]);
 //! ```
+//!
+//! ## Multiple pointers
+//!
+//! [`MultiHoverState`] keeps one path per pointer id and reports both
+//! per-pointer transitions and reference-counted "hovered by any pointer"
+//! transitions.
+//!
+//! ## Timed hover
+//!
+//! With the `hover_intent` feature, [`TimedHoverState`] layers time on top of
+//! [`HoverState`] for each pointer: rest detection for tooltips
+//! ([`TimedHoverEvent::HoverRest`]), grace periods before leave, and
+//! safe-triangle intent toward submenus ([`TimedHoverEvent::HoverIntentLeave`]).
+//! It is driven entirely by caller timestamps.
 
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
 
+#[cfg(feature = "hover_intent")]
+mod intent;
+
+#[cfg(feature = "hover_intent")]
+pub use intent::{TimedHoverConfig, TimedHoverEvent, TimedHoverState};
+
 /// A simple hover state machine over root→target paths.
 ///
 /// Tracks the current hovered path (root→target) and, when updated with a new
@@ -130,6 +151,165 @@ impl<K: Copy + Eq> HoverState<K> {
     }
 }
 
+/// A transition produced by [`MultiHoverState`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MultiHoverEvent<P, K> {
+    /// A transition in one pointer's own path.
+    Pointer(P, HoverEvent<K>),
+    /// A transition in the aggregate "hovered by any pointer" set.
+    ///
+    /// `Enter(k)` is emitted when the first pointer enters `k`, and
+    /// `Leave(k)` when the last pointer hovering `k` leaves it.
+    Any(HoverEvent<K>),
+}
+
+/// Hover tracking for several simultaneous pointers.
+///
+/// Pen, touch, mouse and remote collaborator cursors can all hover at once.
+/// This keeps an independent [`HoverState`] per pointer id and, on top of it,
+/// a reference count per node so that "is any pointer hovering `k`" changes
+/// only when the first pointer enters or the last pointer leaves.
+///
+/// Each update returns the pointer's own transitions, in [`HoverState`] order,
+/// with the aggregate transitions they cause interleaved directly after them.
+///
+/// ## Example
+///
+/// ```
+/// use understory_event_state::hover::{HoverEvent, MultiHoverEvent, MultiHoverState};
+///
+/// let mut hover: MultiHoverState<u8, u32> = MultiHoverState::new();
+/// hover.update_path(0, &[1, 2]);
+///
+/// // A second pointer over the same node only reports its own enter.
+/// let events = hover.update_path(1, &[1, 2]);
+/// assert_eq!(events, vec![
+///     MultiHoverEvent::Pointer(1, HoverEvent::Enter(1)),
+///     MultiHoverEvent::Pointer(1, HoverEvent::Enter(2)),
+/// ]);
+/// assert_eq!(hover.hover_count(&2), 2);
+///
+/// hover.remove_pointer(0);
+/// let events = hover.remove_pointer(1);
+/// assert_eq!(events, vec![
+///     MultiHoverEvent::Pointer(1, HoverEvent::Leave(2)),
+///     MultiHoverEvent::Any(HoverEvent::Leave(2)),
+///     MultiHoverEvent::Pointer(1, HoverEvent::Leave(1)),
+///     MultiHoverEvent::Any(HoverEvent::Leave(1)),
+/// ]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MultiHoverState<P: Copy + Ord, K: Copy + Eq> {
+    pointers: BTreeMap<P, HoverState<K>>,
+    /// Nodes hovered by at least one pointer, with the number of such pointers.
+    counts: Vec<(K, usize)>,
+}
+
+impl<P: Copy + Ord, K: Copy + Eq> MultiHoverState<P, K> {
+    /// Create an empty multi-pointer hover state.
+    pub fn new() -> Self {
+        Self {
+            pointers: BTreeMap::new(),
+            counts: Vec::new(),
+        }
+    }
+
+    /// Return the root→target path of `pointer` (empty if it is not hovering).
+    pub fn path(&self, pointer: P) -> &[K] {
+        self.pointers
+            .get(&pointer)
+            .map_or(&[], HoverState::current_path)
+    }
+
+    /// Iterate over the pointers that currently hover something.
+    pub fn pointers(&self) -> impl Iterator<Item = P> + '_ {
+        self.pointers.keys().copied()
+    }
+
+    /// Return how many pointers currently hover `node`.
+    pub fn hover_count(&self, node: &K) -> usize {
+        self.counts
+            .iter()
+            .find(|(k, _)| k == node)
+            .map_or(0, |&(_, n)| n)
+    }
+
+    /// Return whether any pointer currently hovers `node`.
+    pub fn is_hovered(&self, node: &K) -> bool {
+        self.hover_count(node) > 0
+    }
+
+    /// Update the path of `pointer` and return its transitions together with
+    /// the aggregate transitions they cause.
+    ///
+    /// An empty path removes the pointer, like [`MultiHoverState::remove_pointer`].
+    pub fn update_path(&mut self, pointer: P, new_path: &[K]) -> Vec<MultiHoverEvent<P, K>> {
+        let transitions = if new_path.is_empty() {
+            self.pointers
+                .remove(&pointer)
+                .map_or_else(Vec::new, |mut state| state.clear())
+        } else {
+            self.pointers
+                .entry(pointer)
+                .or_insert_with(HoverState::new)
+                .update_path(new_path)
+        };
+        self.aggregate(pointer, transitions)
+    }
+
+    /// Remove `pointer` (it left the surface or was cancelled), returning the
+    /// leaves for its whole path.
+    pub fn remove_pointer(&mut self, pointer: P) -> Vec<MultiHoverEvent<P, K>> {
+        self.update_path(pointer, &[])
+    }
+
+    /// Remove all pointers, returning their leaves in pointer order.
+    pub fn clear(&mut self) -> Vec<MultiHoverEvent<P, K>> {
+        let pointers: Vec<P> = self.pointers.keys().copied().collect();
+        pointers
+            .into_iter()
+            .flat_map(|p| self.remove_pointer(p))
+            .collect()
+    }
+
+    fn aggregate(
+        &mut self,
+        pointer: P,
+        transitions: Vec<HoverEvent<K>>,
+    ) -> Vec<MultiHoverEvent<P, K>> {
+        let mut out = Vec::with_capacity(transitions.len() * 2);
+        for t in transitions {
+            out.push(MultiHoverEvent::Pointer(pointer, t));
+            match t {
+                HoverEvent::Enter(k) => {
+                    if let Some((_, n)) = self.counts.iter_mut().find(|(c, _)| *c == k) {
+                        *n += 1;
+                    } else {
+                        self.counts.push((k, 1));
+                        out.push(MultiHoverEvent::Any(t));
+                    }
+                }
+                HoverEvent::Leave(k) => {
+                    if let Some(i) = self.counts.iter().position(|(c, _)| *c == k) {
+                        self.counts[i].1 -= 1;
+                        if self.counts[i].1 == 0 {
+                            self.counts.swap_remove(i);
+                            out.push(MultiHoverEvent::Any(t));
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+impl<P: Copy + Ord, K: Copy + Eq> Default for MultiHoverState<P, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -227,4 +407,67 @@ mod tests {
         assert!(second.is_empty());
         assert_eq!(h.current_path(), &[7, 8]);
     }
+
+    // Two pointers sharing ancestry: aggregate enters once and leaves on the last pointer.
+    #[test]
+    fn multi_hover_reference_counts() {
+        let mut h: MultiHoverState<u8, u32> = MultiHoverState::new();
+        let ev = h.update_path(0, &[1, 2]);
+        assert_eq!(
+            ev,
+            vec![
+                MultiHoverEvent::Pointer(0, HoverEvent::Enter(1)),
+                MultiHoverEvent::Any(HoverEvent::Enter(1)),
+                MultiHoverEvent::Pointer(0, HoverEvent::Enter(2)),
+                MultiHoverEvent::Any(HoverEvent::Enter(2)),
+            ]
+        );
+        let ev = h.update_path(1, &[1, 3]);
+        assert_eq!(
+            ev,
+            vec![
+                MultiHoverEvent::Pointer(1, HoverEvent::Enter(1)),
+                MultiHoverEvent::Pointer(1, HoverEvent::Enter(3)),
+                MultiHoverEvent::Any(HoverEvent::Enter(3)),
+            ]
+        );
+        assert_eq!(h.hover_count(&1), 2);
+        assert!(h.is_hovered(&3));
+
+        // Pointer 0 moves onto pointer 1's node.
+        let ev = h.update_path(0, &[1, 3]);
+        assert_eq!(
+            ev,
+            vec![
+                MultiHoverEvent::Pointer(0, HoverEvent::Leave(2)),
+                MultiHoverEvent::Any(HoverEvent::Leave(2)),
+                MultiHoverEvent::Pointer(0, HoverEvent::Enter(3)),
+            ]
+        );
+        assert_eq!(h.path(0), &[1, 3]);
+
+        let ev = h.remove_pointer(1);
+        assert_eq!(
+            ev,
+            vec![
+                MultiHoverEvent::Pointer(1, HoverEvent::Leave(3)),
+                MultiHoverEvent::Pointer(1, HoverEvent::Leave(1)),
+            ]
+        );
+        assert!(h.path(1).is_empty());
+        assert_eq!(h.pointers().collect::<Vec<_>>(), vec![0]);
+
+        let ev = h.clear();
+        assert_eq!(
+            ev,
+            vec![
+                MultiHoverEvent::Pointer(0, HoverEvent::Leave(3)),
+                MultiHoverEvent::Any(HoverEvent::Leave(3)),
+                MultiHoverEvent::Pointer(0, HoverEvent::Leave(1)),
+                MultiHoverEvent::Any(HoverEvent::Leave(1)),
+            ]
+        );
+        assert!(!h.is_hovered(&1));
+        assert!(h.remove_pointer(0).is_empty());
+    }
 }
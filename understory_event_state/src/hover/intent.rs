@@ -0,0 +1,584 @@
+// Copyright 2025 the Understory Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Timed hover layer: rest delays, leave grace periods and safe-triangle intent.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use kurbo::{Point, Rect};
+
+use super::{HoverEvent, HoverState};
+
+/// Timing and tolerance parameters for [`TimedHoverState`].
+///
+/// All durations are in milliseconds, matching the caller-supplied timestamps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimedHoverConfig {
+    /// How long the pointer must rest on a target before [`TimedHoverEvent::HoverRest`].
+    pub rest_delay: u64,
+    /// Movement (in pointer units) tolerated before a pending rest restarts.
+    pub rest_tolerance: f64,
+    /// Grace period before nodes the pointer left are reported as left.
+    ///
+    /// Returning to the nodes within the grace period cancels the leave.
+    pub leave_delay: u64,
+    /// Upper bound on how long a safe triangle can hold the hover path.
+    pub safe_triangle_timeout: u64,
+}
+
+impl Default for TimedHoverConfig {
+    fn default() -> Self {
+        Self {
+            rest_delay: 500,
+            rest_tolerance: 4.0,
+            leave_delay: 0,
+            safe_triangle_timeout: 500,
+        }
+    }
+}
+
+/// A transition produced by [`TimedHoverState`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimedHoverEvent<P, K> {
+    /// The pointer's intended hover path now includes `node` (outer→inner).
+    Enter {
+        /// Pointer the transition belongs to.
+        pointer: P,
+        /// Node that was entered.
+        node: K,
+    },
+    /// The pointer has left `node` for good, after any grace period or safe
+    /// triangle expired (inner→outer).
+    HoverIntentLeave {
+        /// Pointer the transition belongs to.
+        pointer: P,
+        /// Node that was left.
+        node: K,
+    },
+    /// The pointer rested on `target` (the innermost hovered node) for
+    /// [`TimedHoverConfig::rest_delay`]; show tooltips or previews now.
+    HoverRest {
+        /// Pointer the transition belongs to.
+        pointer: P,
+        /// Node the pointer rested on.
+        target: K,
+    },
+    /// A previously reported [`TimedHoverEvent::HoverRest`] no longer holds
+    /// because the pointer moved to another target or went away.
+    HoverRestCancel {
+        /// Pointer the transition belongs to.
+        pointer: P,
+        /// Node the pointer had rested on.
+        target: K,
+    },
+}
+
+/// Pending rest detection for one pointer.
+#[derive(Clone, Debug)]
+struct Rest<K> {
+    target: Option<K>,
+    anchor: Point,
+    since: u64,
+    fired: bool,
+}
+
+/// Keeps the intended hover path from following the raw path.
+#[derive(Clone, Debug)]
+struct Hold {
+    started: u64,
+    deadline: u64,
+    /// Apex and far corners of the safe triangle, plus the target it leads to.
+    safe: Option<([Point; 3], Rect)>,
+}
+
+impl Hold {
+    fn in_safe_zone(&self, position: Point) -> bool {
+        self.safe
+            .is_some_and(|(tri, rect)| rect.contains(position) || in_triangle(tri, position))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct PointerHover<K: Copy + Eq> {
+    raw: Vec<K>,
+    intent: HoverState<K>,
+    position: Point,
+    hold: Option<Hold>,
+    rest: Rest<K>,
+}
+
+/// A timed hover layer over [`HoverState`], tracked per pointer.
+///
+/// [`HoverState`] reports enter/leave as soon as the path changes. Tooltips,
+/// menus and delayed previews additionally need to know when the pointer has
+/// *rested* on a node, and want to ignore brief excursions. This type keeps,
+/// for each pointer, the raw hovered path plus an *intended* path that lags
+/// behind it:
+///
+/// - Nodes that were left stay in the intended path for
+///   [`TimedHoverConfig::leave_delay`]; returning within that time produces
+///   no transitions at all.
+/// - A node can register an intent target with [`TimedHoverState::set_intent_target`]
+///   (e.g. the bounds of its submenu). While the pointer moves inside the
+///   triangle between its exit point and that target, the intended path is
+///   held, so crossing sibling items on the way does not switch the hover.
+/// - After the pointer stays on its innermost node (within
+///   [`TimedHoverConfig::rest_tolerance`]) for [`TimedHoverConfig::rest_delay`],
+///   a [`TimedHoverEvent::HoverRest`] is emitted, and later cancelled with
+///   [`TimedHoverEvent::HoverRestCancel`].
+///
+/// Time only advances through caller timestamps (milliseconds). Feed pointer
+/// moves to [`TimedHoverState::update`], and call [`TimedHoverState::tick`]
+/// when [`TimedHoverState::next_deadline`] is reached.
+///
+/// Events for one call are ordered: rest cancellation, leaves (inner→outer),
+/// enters (outer→inner), then rest.
+///
+/// ## Example
+///
+/// ```
+/// use kurbo::Point;
+/// use understory_event_state::hover::{TimedHoverConfig, TimedHoverEvent, TimedHoverState};
+///
+/// let mut hover: TimedHoverState<u8, u32> = TimedHoverState::new(TimedHoverConfig {
+///     rest_delay: 300,
+///     ..TimedHoverConfig::default()
+/// });
+///
+/// let events = hover.update(0, &[1, 2], Point::new(10.0, 10.0), 1_000);
+/// assert_eq!(events, vec![
+///     TimedHoverEvent::Enter { pointer: 0, node: 1 },
+///     TimedHoverEvent::Enter { pointer: 0, node: 2 },
+/// ]);
+/// assert_eq!(hover.next_deadline(), Some(1_300));
+///
+/// let events = hover.tick(1_300);
+/// assert_eq!(events, vec![TimedHoverEvent::HoverRest { pointer: 0, target: 2 }]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TimedHoverState<P: Copy + Ord, K: Copy + Eq> {
+    config: TimedHoverConfig,
+    pointers: BTreeMap<P, PointerHover<K>>,
+    intent_targets: Vec<(K, Rect)>,
+}
+
+impl<P: Copy + Ord, K: Copy + Eq> TimedHoverState<P, K> {
+    /// Create an empty timed hover state with the given configuration.
+    pub fn new(config: TimedHoverConfig) -> Self {
+        Self {
+            config,
+            pointers: BTreeMap::new(),
+            intent_targets: Vec::new(),
+        }
+    }
+
+    /// Returns the configuration.
+    pub fn config(&self) -> &TimedHoverConfig {
+        &self.config
+    }
+
+    /// Replaces the configuration.
+    ///
+    /// The rest delay, rest tolerance and safe-triangle timeout also apply to
+    /// timers already running, since their deadlines are derived from the current
+    /// configuration. A pending leave keeps the deadline computed from the
+    /// `leave_delay` in effect when the pointer left.
+    pub fn set_config(&mut self, config: TimedHoverConfig) {
+        self.config = config;
+    }
+
+    /// Registers (or with `None`, removes) the region the pointer is expected
+    /// to move toward after leaving `node`, such as a submenu.
+    ///
+    /// The rectangle must be in the same coordinate space as the positions
+    /// passed to [`TimedHoverState::update`].
+    pub fn set_intent_target(&mut self, node: K, target: Option<Rect>) {
+        self.intent_targets.retain(|(k, _)| *k != node);
+        if let Some(rect) = target {
+            self.intent_targets.push((node, rect));
+        }
+    }
+
+    /// Returns the raw root→target path under `pointer`.
+    pub fn raw_path(&self, pointer: P) -> &[K] {
+        self.pointers.get(&pointer).map_or(&[], |p| &p.raw)
+    }
+
+    /// Returns the intended root→target path for `pointer`, which may lag
+    /// behind [`TimedHoverState::raw_path`] during a grace period or safe triangle.
+    pub fn intent_path(&self, pointer: P) -> &[K] {
+        self.pointers
+            .get(&pointer)
+            .map_or(&[], |p| p.intent.current_path())
+    }
+
+    /// Returns the node `pointer` has rested on, if a [`TimedHoverEvent::HoverRest`]
+    /// was reported and not yet cancelled.
+    pub fn rested_target(&self, pointer: P) -> Option<K> {
+        self.pointers
+            .get(&pointer)
+            .filter(|p| p.rest.fired)
+            .and_then(|p| p.rest.target)
+    }
+
+    /// Returns the earliest timestamp at which [`TimedHoverState::tick`] may
+    /// produce events, if any timer is pending.
+    pub fn next_deadline(&self) -> Option<u64> {
+        self.pointers
+            .values()
+            .flat_map(|p| {
+                let rest = (p.rest.target.is_some() && !p.rest.fired)
+                    .then(|| p.rest.since.saturating_add(self.config.rest_delay));
+                let hold = p.hold.as_ref().map(|h| {
+                    if h.in_safe_zone(p.position) {
+                        h.deadline
+                            .max(h.started.saturating_add(self.config.safe_triangle_timeout))
+                    } else {
+                        h.deadline
+                    }
+                });
+                rest.into_iter().chain(hold)
+            })
+            .min()
+    }
+
+    /// Process a pointer move: `path` is the root→target path under the
+    /// pointer at `position`, observed at `now` milliseconds.
+    pub fn update(
+        &mut self,
+        pointer: P,
+        path: &[K],
+        position: Point,
+        now: u64,
+    ) -> Vec<TimedHoverEvent<P, K>> {
+        let config = self.config;
+        let state = self
+            .pointers
+            .entry(pointer)
+            .or_insert_with(|| PointerHover {
+                raw: Vec::new(),
+                intent: HoverState::new(),
+                position,
+                hold: None,
+                rest: Rest {
+                    target: None,
+                    anchor: position,
+                    since: now,
+                    fired: false,
+                },
+            });
+        let mut out = Vec::new();
+
+        // Rest tracking follows the raw innermost target.
+        let target = path.last().copied();
+        if target != state.rest.target {
+            if state.rest.fired
+                && let Some(old) = state.rest.target
+            {
+                out.push(TimedHoverEvent::HoverRestCancel {
+                    pointer,
+                    target: old,
+                });
+            }
+            state.rest = Rest {
+                target,
+                anchor: position,
+                since: now,
+                fired: false,
+            };
+        } else if !state.rest.fired && state.rest.anchor.distance(position) > config.rest_tolerance
+        {
+            state.rest.anchor = position;
+            state.rest.since = now;
+        }
+
+        // Start holding the intended path when nodes are left.
+        let leaves_nodes = state
+            .intent
+            .current_path()
+            .iter()
+            .any(|k| !path.contains(k));
+        if leaves_nodes && state.hold.is_none() {
+            let safe = state
+                .intent
+                .current_path()
+                .iter()
+                .rev()
+                .filter(|k| !path.contains(k))
+                .find_map(|k| {
+                    self.intent_targets
+                        .iter()
+                        .find(|(node, _)| node == k)
+                        .map(|(_, rect)| *rect)
+                })
+                .map(|rect| (safe_triangle(state.position, rect), rect));
+            state.hold = Some(Hold {
+                started: now,
+                deadline: now.saturating_add(config.leave_delay),
+                safe,
+            });
+        }
+        state.raw.clear();
+        state.raw.extend_from_slice(path);
+        state.position = position;
+
+        advance(&config, pointer, state, now, &mut out);
+        out
+    }
+
+    /// Fire any timers that are due at `now` for all pointers.
+    pub fn tick(&mut self, now: u64) -> Vec<TimedHoverEvent<P, K>> {
+        let mut out = Vec::new();
+        for (&pointer, state) in &mut self.pointers {
+            advance(&self.config, pointer, state, now, &mut out);
+        }
+        out
+    }
+
+    /// Forget `pointer` (it left the surface or was cancelled), emitting any
+    /// rest cancellation and leaves immediately.
+    pub fn remove_pointer(&mut self, pointer: P) -> Vec<TimedHoverEvent<P, K>> {
+        let mut out = Vec::new();
+        if let Some(mut state) = self.pointers.remove(&pointer) {
+            if state.rest.fired
+                && let Some(target) = state.rest.target
+            {
+                out.push(TimedHoverEvent::HoverRestCancel { pointer, target });
+            }
+            push_transitions(pointer, state.intent.clear(), &mut out);
+        }
+        out
+    }
+
+    /// Forget all pointers, emitting their cancellations and leaves.
+    pub fn clear(&mut self) -> Vec<TimedHoverEvent<P, K>> {
+        let pointers: Vec<P> = self.pointers.keys().copied().collect();
+        pointers
+            .into_iter()
+            .flat_map(|p| self.remove_pointer(p))
+            .collect()
+    }
+}
+
+/// Resolve holds and rest timers for one pointer at `now`.
+fn advance<P: Copy, K: Copy + Eq>(
+    config: &TimedHoverConfig,
+    pointer: P,
+    state: &mut PointerHover<K>,
+    now: u64,
+    out: &mut Vec<TimedHoverEvent<P, K>>,
+) {
+    let held = state.hold.as_ref().is_some_and(|hold| {
+        let returned = state.intent.current_path() == state.raw.as_slice();
+        let in_safe_zone = now.saturating_sub(hold.started) < config.safe_triangle_timeout
+            && hold.in_safe_zone(state.position);
+        !returned && (in_safe_zone || now < hold.deadline)
+    });
+    if !held {
+        state.hold = None;
+        let transitions = state.intent.update_path(&state.raw);
+        push_transitions(pointer, transitions, out);
+    }
+
+    if !state.rest.fired
+        && let Some(target) = state.rest.target
+        && now.saturating_sub(state.rest.since) >= config.rest_delay
+    {
+        state.rest.fired = true;
+        out.push(TimedHoverEvent::HoverRest { pointer, target });
+    }
+}
+
+fn push_transitions<P: Copy, K>(
+    pointer: P,
+    transitions: Vec<HoverEvent<K>>,
+    out: &mut Vec<TimedHoverEvent<P, K>>,
+) {
+    out.extend(transitions.into_iter().map(|t| match t {
+        HoverEvent::Enter(node) => TimedHoverEvent::Enter { pointer, node },
+        HoverEvent::Leave(node) => TimedHoverEvent::HoverIntentLeave { pointer, node },
+    }));
+}
+
+/// The triangle from `apex` to the two corners of `target` that span the
+/// widest angle as seen from `apex`.
+fn safe_triangle(apex: Point, target: Rect) -> [Point; 3] {
+    let corners = [
+        Point::new(target.x0, target.y0),
+        Point::new(target.x1, target.y0),
+        Point::new(target.x1, target.y1),
+        Point::new(target.x0, target.y1),
+    ];
+    let mut best = (corners[0], corners[2]);
+    let mut best_cos = f64::INFINITY;
+    for i in 0..4 {
+        for j in (i + 1)..4 {
+            let (a, b) = (corners[i] - apex, corners[j] - apex);
+            let len = a.hypot() * b.hypot();
+            let cos = if len > 0.0 { a.dot(b) / len } else { 1.0 };
+            if cos < best_cos {
+                best_cos = cos;
+                best = (corners[i], corners[j]);
+            }
+        }
+    }
+    [apex, best.0, best.1]
+}
+
+fn in_triangle([a, b, c]: [Point; 3], p: Point) -> bool {
+    let d1 = (b - a).cross(p - a);
+    let d2 = (c - b).cross(p - b);
+    let d3 = (a - c).cross(p - c);
+    let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_neg && has_pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    type Ev = TimedHoverEvent<u8, u32>;
+
+    fn enter(node: u32) -> Ev {
+        TimedHoverEvent::Enter { pointer: 0, node }
+    }
+
+    fn leave(node: u32) -> Ev {
+        TimedHoverEvent::HoverIntentLeave { pointer: 0, node }
+    }
+
+    #[test]
+    fn rest_fires_after_delay_and_cancels_on_target_change() {
+        let mut h = TimedHoverState::new(TimedHoverConfig {
+            rest_delay: 100,
+            rest_tolerance: 2.0,
+            ..TimedHoverConfig::default()
+        });
+        h.update(0, &[1, 2], Point::new(0.0, 0.0), 0);
+        assert!(h.tick(99).is_empty());
+        // Moving beyond the tolerance restarts the timer.
+        assert!(h.update(0, &[1, 2], Point::new(5.0, 0.0), 50).is_empty());
+        assert!(h.tick(120).is_empty());
+        assert_eq!(h.next_deadline(), Some(150));
+        assert_eq!(
+            h.tick(150),
+            vec![TimedHoverEvent::HoverRest {
+                pointer: 0,
+                target: 2
+            }]
+        );
+        assert_eq!(h.rested_target(0), Some(2));
+        assert_eq!(h.next_deadline(), None);
+
+        // Small movements after resting keep the rest.
+        assert!(h.update(0, &[1, 2], Point::new(20.0, 0.0), 160).is_empty());
+        assert_eq!(
+            h.update(0, &[1, 3], Point::new(30.0, 0.0), 170),
+            vec![
+                TimedHoverEvent::HoverRestCancel {
+                    pointer: 0,
+                    target: 2
+                },
+                leave(2),
+                enter(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn grace_period_hides_brief_excursions() {
+        let mut h = TimedHoverState::new(TimedHoverConfig {
+            leave_delay: 100,
+            rest_delay: 10_000,
+            ..TimedHoverConfig::default()
+        });
+        assert_eq!(
+            h.update(0, &[1, 2], Point::ZERO, 0),
+            vec![enter(1), enter(2)]
+        );
+        assert!(h.update(0, &[1, 3], Point::ZERO, 10).is_empty());
+        assert_eq!(h.intent_path(0), &[1, 2]);
+        assert_eq!(h.raw_path(0), &[1, 3]);
+        // Coming back within the grace period cancels the pending leave.
+        assert!(h.update(0, &[1, 2], Point::ZERO, 50).is_empty());
+        assert_eq!(h.next_deadline(), Some(10_050));
+
+        assert!(h.update(0, &[1, 3], Point::ZERO, 60).is_empty());
+        assert_eq!(h.next_deadline(), Some(160));
+        assert!(h.tick(159).is_empty());
+        assert_eq!(h.tick(160), vec![leave(2), enter(3)]);
+
+        // Moving deeper is never delayed.
+        assert_eq!(h.update(0, &[1, 3, 4], Point::ZERO, 170), vec![enter(4)]);
+    }
+
+    #[test]
+    fn safe_triangle_holds_path_toward_submenu() {
+        let mut h = TimedHoverState::new(TimedHoverConfig {
+            rest_delay: 10_000,
+            safe_triangle_timeout: 300,
+            ..TimedHoverConfig::default()
+        });
+        h.set_intent_target(2, Some(Rect::new(100.0, 0.0, 200.0, 100.0)));
+        h.update(0, &[1, 2], Point::new(50.0, 50.0), 0);
+
+        // Crossing a sibling on the way to the submenu keeps item 2 hovered.
+        assert!(h.update(0, &[1, 3], Point::new(70.0, 55.0), 10).is_empty());
+        assert!(h.tick(100).is_empty());
+        // Reaching the submenu keeps holding until the timeout.
+        assert!(
+            h.update(0, &[1, 9], Point::new(120.0, 60.0), 150)
+                .is_empty()
+        );
+        assert_eq!(h.next_deadline(), Some(310));
+        assert_eq!(h.tick(310), vec![leave(2), enter(9)]);
+
+        // Leaving the triangle commits the switch immediately.
+        let mut h = TimedHoverState::new(TimedHoverConfig::default());
+        h.set_intent_target(2, Some(Rect::new(100.0, 0.0, 200.0, 100.0)));
+        h.update(0, &[1, 2], Point::new(50.0, 50.0), 0);
+        assert!(h.update(0, &[1, 3], Point::new(70.0, 55.0), 10).is_empty());
+        assert_eq!(
+            h.update(0, &[1, 4], Point::new(60.0, 90.0), 20),
+            vec![leave(2), enter(4)]
+        );
+    }
+
+    #[test]
+    fn pointers_are_independent() {
+        let mut h: TimedHoverState<u8, u32> = TimedHoverState::new(TimedHoverConfig {
+            rest_delay: 100,
+            ..TimedHoverConfig::default()
+        });
+        h.update(0, &[1], Point::ZERO, 0);
+        h.update(1, &[5], Point::ZERO, 50);
+        assert_eq!(
+            h.tick(100),
+            vec![TimedHoverEvent::HoverRest {
+                pointer: 0,
+                target: 1
+            }]
+        );
+        assert_eq!(
+            h.remove_pointer(0),
+            vec![
+                TimedHoverEvent::HoverRestCancel {
+                    pointer: 0,
+                    target: 1
+                },
+                leave(1),
+            ]
+        );
+        assert_eq!(h.intent_path(1), &[5]);
+        assert_eq!(
+            h.clear(),
+            vec![TimedHoverEvent::HoverIntentLeave {
+                pointer: 1,
+                node: 5
+            }]
+        );
+    }
+}
@@ -0,0 +1,511 @@
+// Copyright 2025 the Understory Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Keyboard state helper: pressed keys, modifiers, repeats and scoped shortcut chords.
+//!
+//! ## Usage
+//!
+//! 1) Forward key down/up events to [`KeyState::on_down`] / [`KeyState::on_up`] with the
+//!    platform's modifier state and a timestamp in milliseconds.
+//! 2) For non-modifier key presses, build a [`Chord`] and pass it to
+//!    [`ShortcutMatcher::on_chord`] together with [`FocusState::current_path`].
+//! 3) Act on [`ShortcutResult::Matched`]; let [`ShortcutResult::Unmatched`] keys continue
+//!    to normal text input.
+//! 4) Call [`ShortcutMatcher::tick`] when [`ShortcutMatcher::next_deadline`] is reached to
+//!    expire half-typed chords.
+//!
+//! Bindings are scoped to a node in the focus path (or global). Scopes are searched from
+//! the focused node outward, so inner scopes shadow outer bindings for the same keys.
+//!
+//! [`FocusState::current_path`]: crate::focus::FocusState::current_path
+//!
+//! ## Minimal example
+//!
+//! ```
+//! use understory_event_state::keyboard::{Chord, Modifiers, ShortcutMatcher, ShortcutResult};
+//!
+//! let mut shortcuts: ShortcutMatcher<u32, char, &str> = ShortcutMatcher::new();
+//! shortcuts.bind(None, [Chord::new(Modifiers::CTRL, 'k'), Chord::new(Modifiers::CTRL, 'c')], "comment");
+//! shortcuts.bind(Some(2), [Chord::new(Modifiers::CTRL, 's')], "save-editor");
+//!
+//! let focus_path = [1, 2];
+//! assert_eq!(shortcuts.on_chord(Chord::new(Modifiers::CTRL, 'k'), &focus_path, 0), ShortcutResult::Pending);
+//! assert_eq!(
+//!     shortcuts.on_chord(Chord::new(Modifiers::CTRL, 'c'), &focus_path, 200),
+//!     ShortcutResult::Matched("comment")
+//! );
+//! assert_eq!(
+//!     shortcuts.on_chord(Chord::new(Modifiers::CTRL, 's'), &focus_path, 300),
+//!     ShortcutResult::Matched("save-editor")
+//! );
+//! assert_eq!(shortcuts.on_chord(Chord::new(Modifiers::CTRL, 's'), &[1], 400), ShortcutResult::Unmatched);
+//! ```
+
+use alloc::vec::Vec;
+use core::ops::{BitOr, BitOrAssign};
+
+/// A set of keyboard modifiers.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    /// No modifiers.
+    pub const NONE: Self = Self(0);
+    /// Shift.
+    pub const SHIFT: Self = Self(1 << 0);
+    /// Control.
+    pub const CTRL: Self = Self(1 << 1);
+    /// Alt / Option.
+    pub const ALT: Self = Self(1 << 2);
+    /// Meta / Command / Super.
+    pub const META: Self = Self(1 << 3);
+
+    /// Return whether no modifier is set.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return whether all modifiers in `other` are set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Set the modifiers in `other`.
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Clear the modifiers in `other`.
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// The result of [`KeyState::on_down`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KeyDown {
+    /// The key was not pressed before.
+    Initial,
+    /// The key was already held: an auto-repeat.
+    Repeat {
+        /// Number of repeats so far, starting at 1.
+        count: u32,
+        /// Milliseconds since the previous down event for this key.
+        interval: u64,
+        /// Milliseconds since the key was first pressed.
+        held: u64,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Held<C> {
+    key: C,
+    down_time: u64,
+    last_time: u64,
+    repeats: u32,
+}
+
+/// Tracks which keys are held, the current modifiers, and auto-repeat.
+///
+/// A down event for a key that is already held is reported as a repeat, which
+/// also detects repeats on platforms that don't flag them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyState<C: Copy + Eq> {
+    held: Vec<Held<C>>,
+    modifiers: Modifiers,
+}
+
+impl<C: Copy + Eq> KeyState<C> {
+    /// Create a state with no keys held.
+    pub fn new() -> Self {
+        Self {
+            held: Vec::new(),
+            modifiers: Modifiers::NONE,
+        }
+    }
+
+    /// Process a key down at `timestamp` with the modifiers reported alongside it.
+    pub fn on_down(&mut self, key: C, modifiers: Modifiers, timestamp: u64) -> KeyDown {
+        self.modifiers = modifiers;
+        if let Some(held) = self.held.iter_mut().find(|h| h.key == key) {
+            held.repeats += 1;
+            let interval = timestamp.saturating_sub(held.last_time);
+            held.last_time = timestamp;
+            return KeyDown::Repeat {
+                count: held.repeats,
+                interval,
+                held: timestamp.saturating_sub(held.down_time),
+            };
+        }
+        self.held.push(Held {
+            key,
+            down_time: timestamp,
+            last_time: timestamp,
+            repeats: 0,
+        });
+        KeyDown::Initial
+    }
+
+    /// Process a key up at `timestamp`.
+    ///
+    /// Returns how long the key was held, or `None` if it was not held.
+    pub fn on_up(&mut self, key: C, modifiers: Modifiers, timestamp: u64) -> Option<u64> {
+        self.modifiers = modifiers;
+        let index = self.held.iter().position(|h| h.key == key)?;
+        let held = self.held.remove(index);
+        Some(timestamp.saturating_sub(held.down_time))
+    }
+
+    /// Return the current modifiers.
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    /// Overwrite the modifiers, e.g. from a pointer event or after regaining focus.
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    /// Return whether `key` is held.
+    pub fn is_pressed(&self, key: &C) -> bool {
+        self.held.iter().any(|h| h.key == *key)
+    }
+
+    /// Iterate over held keys in press order.
+    pub fn pressed_keys(&self) -> impl Iterator<Item = &C> {
+        self.held.iter().map(|h| &h.key)
+    }
+
+    /// Return the chord `key` forms with the current modifiers.
+    pub fn chord(&self, key: C) -> Chord<C> {
+        Chord::new(self.modifiers, key)
+    }
+
+    /// Release everything, e.g. when the window loses focus and key ups will be missed.
+    pub fn clear(&mut self) {
+        self.held.clear();
+        self.modifiers = Modifiers::NONE;
+    }
+}
+
+impl<C: Copy + Eq> Default for KeyState<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One step of a shortcut: a key together with the exact modifiers held.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Chord<C> {
+    /// Modifiers that must be held (exactly).
+    pub modifiers: Modifiers,
+    /// The key.
+    pub key: C,
+}
+
+impl<C> Chord<C> {
+    /// Create a chord.
+    pub const fn new(modifiers: Modifiers, key: C) -> Self {
+        Self { modifiers, key }
+    }
+}
+
+/// The result of [`ShortcutMatcher::on_chord`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ShortcutResult<A> {
+    /// A binding completed; the chord was consumed.
+    Matched(A),
+    /// The chord started or continued a multi-step binding; it was consumed.
+    Pending,
+    /// The chord broke off a pending sequence; it was consumed without action.
+    Cancelled,
+    /// No binding involves this chord; it should be handled normally.
+    Unmatched,
+}
+
+#[derive(Clone, Debug)]
+struct Binding<K, C, A> {
+    scope: Option<K>,
+    sequence: Vec<Chord<C>>,
+    action: A,
+}
+
+/// Resolves chord sequences like `Ctrl+K Ctrl+C` to actions, scoped to the focus path.
+///
+/// Each binding belongs to a scope node or is global (`None`). For every chord the
+/// scopes in the focus path are searched from the focused node outward, then the
+/// global scope; the first scope with a binding that starts with the typed sequence
+/// decides, so inner scopes shadow outer ones.
+///
+/// If a scope has both an exact binding and longer bindings with the same prefix,
+/// the matcher waits for the next chord; the exact binding fires from
+/// [`ShortcutMatcher::tick`] once [`ShortcutMatcher::timeout`] elapses.
+#[derive(Clone, Debug)]
+pub struct ShortcutMatcher<K, C, A> {
+    bindings: Vec<Binding<K, C, A>>,
+    timeout: u64,
+    pending: Vec<Chord<C>>,
+    deadline: u64,
+    fallback: Option<A>,
+}
+
+impl<K: Copy + Eq, C: Copy + Eq, A: Clone> ShortcutMatcher<K, C, A> {
+    /// Create an empty matcher with a 1000 ms chord timeout.
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+            timeout: 1000,
+            pending: Vec::new(),
+            deadline: 0,
+            fallback: None,
+        }
+    }
+
+    /// Return the time allowed between the steps of a chord, in milliseconds.
+    pub fn timeout(&self) -> u64 {
+        self.timeout
+    }
+
+    /// Set the time allowed between the steps of a chord, in milliseconds.
+    pub fn set_timeout(&mut self, timeout: u64) {
+        self.timeout = timeout;
+    }
+
+    /// Bind `sequence` in `scope` (or globally for `None`) to `action`,
+    /// replacing any existing binding for the same scope and sequence.
+    pub fn bind(
+        &mut self,
+        scope: Option<K>,
+        sequence: impl IntoIterator<Item = Chord<C>>,
+        action: A,
+    ) {
+        let sequence: Vec<Chord<C>> = sequence.into_iter().collect();
+        debug_assert!(!sequence.is_empty(), "shortcut sequence must not be empty");
+        self.unbind(scope, &sequence);
+        self.bindings.push(Binding {
+            scope,
+            sequence,
+            action,
+        });
+    }
+
+    /// Remove the binding for `sequence` in `scope`, returning its action.
+    pub fn unbind(&mut self, scope: Option<K>, sequence: &[Chord<C>]) -> Option<A> {
+        let index = self
+            .bindings
+            .iter()
+            .position(|b| b.scope == scope && b.sequence == sequence)?;
+        Some(self.bindings.remove(index).action)
+    }
+
+    /// Return the chords typed so far of a pending multi-step binding.
+    pub fn pending(&self) -> &[Chord<C>] {
+        &self.pending
+    }
+
+    /// Return whether a multi-step binding is in progress.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Return when the pending sequence expires, if any.
+    pub fn next_deadline(&self) -> Option<u64> {
+        self.is_pending().then_some(self.deadline)
+    }
+
+    /// Abandon any pending sequence.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.fallback = None;
+    }
+
+    /// Expire the pending sequence if its deadline has passed at `now`.
+    ///
+    /// Returns the action of an exact binding that was waiting on a longer
+    /// sequence, if any.
+    pub fn tick(&mut self, now: u64) -> Option<A> {
+        if !self.is_pending() || now < self.deadline {
+            return None;
+        }
+        self.pending.clear();
+        self.fallback.take()
+    }
+
+    /// Process a chord typed at `now` while `focus_path` (root→focused) has focus.
+    ///
+    /// A pending sequence whose deadline passed is dropped first, without firing
+    /// its fallback; call [`ShortcutMatcher::tick`] to receive that.
+    pub fn on_chord(&mut self, chord: Chord<C>, focus_path: &[K], now: u64) -> ShortcutResult<A> {
+        if self.is_pending() && now >= self.deadline {
+            self.reset();
+        }
+        let was_pending = self.is_pending();
+        self.pending.push(chord);
+
+        let scopes = focus_path.iter().rev().map(|&k| Some(k)).chain([None]);
+        for scope in scopes {
+            let mut exact = None;
+            let mut longer = false;
+            for binding in self
+                .bindings
+                .iter()
+                .filter(|b| b.scope == scope && b.sequence.starts_with(&self.pending))
+            {
+                if binding.sequence.len() == self.pending.len() {
+                    exact = Some(&binding.action);
+                } else {
+                    longer = true;
+                }
+            }
+            if longer {
+                self.fallback = exact.cloned();
+                self.deadline = now.saturating_add(self.timeout);
+                return ShortcutResult::Pending;
+            }
+            if let Some(action) = exact.cloned() {
+                self.reset();
+                return ShortcutResult::Matched(action);
+            }
+        }
+
+        self.reset();
+        if was_pending {
+            ShortcutResult::Cancelled
+        } else {
+            ShortcutResult::Unmatched
+        }
+    }
+}
+
+impl<K: Copy + Eq, C: Copy + Eq, A: Clone> Default for ShortcutMatcher<K, C, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    const CTRL_K: Chord<char> = Chord::new(Modifiers::CTRL, 'k');
+    const CTRL_C: Chord<char> = Chord::new(Modifiers::CTRL, 'c');
+
+    #[test]
+    fn key_state_tracks_repeats_and_modifiers() {
+        let mut keys: KeyState<char> = KeyState::new();
+        assert_eq!(keys.on_down('a', Modifiers::SHIFT, 100), KeyDown::Initial);
+        assert_eq!(
+            keys.on_down('a', Modifiers::SHIFT, 600),
+            KeyDown::Repeat {
+                count: 1,
+                interval: 500,
+                held: 500
+            }
+        );
+        assert_eq!(
+            keys.on_down('a', Modifiers::SHIFT, 633),
+            KeyDown::Repeat {
+                count: 2,
+                interval: 33,
+                held: 533
+            }
+        );
+        keys.on_down('b', Modifiers::SHIFT | Modifiers::CTRL, 650);
+        assert!(keys.modifiers().contains(Modifiers::CTRL));
+        assert_eq!(
+            keys.pressed_keys().copied().collect::<Vec<_>>(),
+            vec!['a', 'b']
+        );
+        assert_eq!(
+            keys.chord('b'),
+            Chord::new(Modifiers::SHIFT | Modifiers::CTRL, 'b')
+        );
+
+        assert_eq!(keys.on_up('a', Modifiers::CTRL, 700), Some(600));
+        assert_eq!(keys.on_up('a', Modifiers::CTRL, 710), None);
+        assert!(!keys.is_pressed(&'a'));
+        keys.clear();
+        assert!(keys.modifiers().is_empty());
+        assert_eq!(keys.pressed_keys().count(), 0);
+    }
+
+    #[test]
+    fn chords_complete_cancel_and_time_out() {
+        let mut m: ShortcutMatcher<u32, char, u8> = ShortcutMatcher::new();
+        m.set_timeout(500);
+        m.bind(None, [CTRL_K, CTRL_C], 1);
+
+        assert_eq!(m.on_chord(CTRL_K, &[], 0), ShortcutResult::Pending);
+        assert_eq!(m.next_deadline(), Some(500));
+        assert_eq!(m.on_chord(CTRL_C, &[], 100), ShortcutResult::Matched(1));
+        assert!(!m.is_pending());
+
+        assert_eq!(m.on_chord(CTRL_K, &[], 200), ShortcutResult::Pending);
+        let x = Chord::new(Modifiers::NONE, 'x');
+        assert_eq!(m.on_chord(x, &[], 300), ShortcutResult::Cancelled);
+        assert_eq!(m.on_chord(x, &[], 310), ShortcutResult::Unmatched);
+
+        // Too slow: the second step starts over.
+        assert_eq!(m.on_chord(CTRL_K, &[], 400), ShortcutResult::Pending);
+        assert_eq!(m.on_chord(CTRL_C, &[], 900), ShortcutResult::Unmatched);
+    }
+
+    #[test]
+    fn exact_binding_fires_on_timeout_when_longer_exists() {
+        let mut m: ShortcutMatcher<u32, char, u8> = ShortcutMatcher::new();
+        m.bind(None, [CTRL_K], 1);
+        m.bind(None, [CTRL_K, CTRL_C], 2);
+        assert_eq!(m.on_chord(CTRL_K, &[], 0), ShortcutResult::Pending);
+        assert_eq!(m.tick(999), None);
+        assert_eq!(m.tick(1000), Some(1));
+        assert!(!m.is_pending());
+        assert_eq!(m.tick(2000), None);
+    }
+
+    #[test]
+    fn inner_scopes_shadow_outer_bindings() {
+        let mut m: ShortcutMatcher<u32, char, &str> = ShortcutMatcher::new();
+        m.bind(None, [CTRL_K, CTRL_C], "global-comment");
+        m.bind(Some(1), [CTRL_C], "app-copy");
+        m.bind(Some(3), [CTRL_C], "editor-copy");
+
+        assert_eq!(
+            m.on_chord(CTRL_C, &[1, 2, 3], 0),
+            ShortcutResult::Matched("editor-copy")
+        );
+        assert_eq!(
+            m.on_chord(CTRL_C, &[1, 2], 0),
+            ShortcutResult::Matched("app-copy")
+        );
+        // Ctrl+K is not bound in scope 1, so the global chord still applies.
+        assert_eq!(m.on_chord(CTRL_K, &[1, 2], 0), ShortcutResult::Pending);
+        assert_eq!(
+            m.on_chord(CTRL_C, &[1, 2], 10),
+            ShortcutResult::Matched("global-comment")
+        );
+
+        // An inner prefix shadows an outer exact binding.
+        m.bind(Some(3), [CTRL_C, CTRL_C], "editor-copy-line");
+        m.unbind(Some(3), &[CTRL_C]);
+        assert_eq!(m.on_chord(CTRL_C, &[1, 2, 3], 20), ShortcutResult::Pending);
+        assert_eq!(
+            m.on_chord(CTRL_C, &[1, 2, 3], 30),
+            ShortcutResult::Matched("editor-copy-line")
+        );
+    }
+}
@@ -10,8 +10,10 @@
 //! that require stateful tracking across multiple events. Each module handles a
 //! specific interaction pattern:
 //!
-//! - [`hover`]: Track enter/leave transitions as the pointer moves across UI elements
+//! - [`hover`]: Track enter/leave transitions as the pointer moves across UI elements,
+//!   for one or many pointers, with optional timed rest and hover-intent tracking
 //! - [`focus`]: Manage keyboard focus state and focus transitions
+//! - [`keyboard`]: Track held keys, modifiers and repeats, and match scoped shortcut chords
 //! - [`click`]: Transform-aware click recognition with spatial/temporal tolerance
 //! - [`drag`]: Track drag operations with movement deltas and total offsets
 //!
@@ -81,6 +83,22 @@
 //! ]);
 //! ```
 //!
+//! ### Keyboard Shortcuts
+//!
+//! Use [`keyboard::ShortcutMatcher`] to resolve multi-step chords, scoped to the focus path:
+//!
+//! ```rust
+//! use understory_event_state::keyboard::{Chord, Modifiers, ShortcutMatcher, ShortcutResult};
+//!
+//! let mut shortcuts: ShortcutMatcher<u32, char, &str> = ShortcutMatcher::new();
+//! let ctrl = |key| Chord::new(Modifiers::CTRL, key);
+//! shortcuts.bind(None, [ctrl('k'), ctrl('c')], "comment");
+//!
+//! // Focus path [1, 42], e.g. from `FocusState::current_path`
+//! assert_eq!(shortcuts.on_chord(ctrl('k'), &[1, 42], 1000), ShortcutResult::Pending);
+//! assert_eq!(shortcuts.on_chord(ctrl('c'), &[1, 42], 1200), ShortcutResult::Matched("comment"));
+//! ```
+//!
 //! ### Transform-Aware Click Recognition
 //!
 //! Use [`click::ClickState`] to recognize clicks even when elements transform during interaction:
@@ -144,6 +162,7 @@
 //!
 //! - `click`: Enable transform-aware click recognition (requires `kurbo` dependency)
 //! - `drag`: Enable drag state tracking (requires `kurbo` dependency)
+//! - `hover_intent`: Enable timed hover and hover-intent tracking (requires `kurbo` dependency)
 //!
 //! This crate is `no_std` compatible (with `alloc`) for all modules.
 
@@ -158,3 +177,4 @@ pub mod click;
 pub mod drag;
 pub mod focus;
 pub mod hover;
+pub mod keyboard;
@@ -10,8 +10,10 @@
 //! ## Notes
 //!
 //! These helpers convert box-tree query results into responder hits.
-//! They do not perform ordering; when only a single candidate exists (e.g., top hit), the depth key value is irrelevant.
-//! For lists (e.g., viewport queries), consumers can apply their own ordering if needed.
+//! Each hit's depth key is [`DepthKey::Z`] of the node's [`Tree::paint_rank`], not its raw
+//! `z_index`, so nodes painted later rank higher and a `z_index` inside a
+//! [stacking context](understory_box_tree::NodeFlags::STACKING_CONTEXT) does not escape it
+//! when the router compares hits, including hits merged from several queries.
 //!
 //! ## Navigation
 //!
@@ -25,7 +27,21 @@ use alloc::vec::Vec;
 use kurbo::{Point, Rect};
 use understory_box_tree::{QueryFilter, Tree};
 
-use crate::types::{DepthKey, Localizer, ResolvedHit};
+use crate::types::{DepthKey, Localizer, ResolvedHit, TieBreakPolicy};
+
+/// Map a responder [`TieBreakPolicy`] to the box tree's equivalent policy.
+///
+/// Configure both with the same policy (for example via
+/// [`Router::set_default_tie_break`](crate::router::Router::set_default_tie_break) and
+/// [`Tree::set_tie_break`]) so that they agree on which of two otherwise equal nodes wins.
+pub fn tie_break_policy(policy: TieBreakPolicy) -> understory_box_tree::TieBreakPolicy {
+    match policy {
+        TieBreakPolicy::Newer => understory_box_tree::TieBreakPolicy::Newer,
+        TieBreakPolicy::Older => understory_box_tree::TieBreakPolicy::Older,
+        TieBreakPolicy::MinId => understory_box_tree::TieBreakPolicy::MinId,
+        TieBreakPolicy::MaxId => understory_box_tree::TieBreakPolicy::MaxId,
+    }
+}
 
 /// Build a single resolved hit for the topmost node under a point.
 ///
@@ -34,49 +50,77 @@ use crate::types::{DepthKey, Localizer, ResolvedHit};
 /// Notes
 /// - Path is populated from the box tree's hit test result so the router does
 ///   not need a parent lookup.
-/// - `DepthKey` is derived from the node's z-index; since only a single candidate
-///   is returned, ordering is irrelevant.
+/// - `DepthKey` is derived from the node's paint rank, like the other helpers.
 pub fn top_hit_for_point(
     tree: &Tree,
     pt: Point,
-    filter: QueryFilter,
+    filter: QueryFilter<'_>,
 ) -> Option<ResolvedHit<understory_box_tree::NodeId, ()>> {
     let hit = tree.hit_test_point(pt, filter)?;
-    let depth_key = tree
-        .z_index(hit.node)
-        .map(DepthKey::Z)
-        .unwrap_or(DepthKey::Z(0));
     Some(ResolvedHit {
+        depth_key: depth_key(tree, hit.node),
         node: hit.node,
         path: Some(hit.path),
-        depth_key,
         localizer: Localizer::default(),
         meta: (),
     })
 }
 
+/// Build resolved hits for every node under a point, topmost first.
+///
+/// Uses [`Tree::hit_test_point_all`], so paths are populated and the list is
+/// the box tree's paint order reversed (z-index, then reverse tree order).
+/// Depth keys follow paint order, so the first entry ranks highest and [`Router::handle_with_hits`](crate::router::Router::handle_with_hits)
+/// routes to it. At equal z-index, [`top_hit_for_point`] prefers deeper nodes,
+/// so its result can appear after the first entry.
+pub fn hits_for_point(
+    tree: &Tree,
+    pt: Point,
+    filter: QueryFilter<'_>,
+) -> Vec<ResolvedHit<understory_box_tree::NodeId, ()>> {
+    tree.hit_test_point_all(pt, filter)
+        .into_iter()
+        .rev()
+        .map(|hit| ResolvedHit {
+            depth_key: depth_key(tree, hit.node),
+            node: hit.node,
+            path: Some(hit.path),
+            localizer: Localizer::default(),
+            meta: (),
+        })
+        .collect()
+}
+
 /// Build resolved hits for nodes intersecting a world-space rectangle.
 ///
 /// Path is not populated; the router can reconstruct a singleton path (or a
 /// parent-aware path if constructed with a parent lookup). Depth keys are set
-/// to each node's z-index; the returned list preserves the box tree's original
+/// to each node's paint rank; the returned list preserves the box tree's original
 /// iteration order so downstream consumers can sort as needed.
 pub fn hits_for_rect(
     tree: &Tree,
     rect: Rect,
-    filter: QueryFilter,
+    filter: QueryFilter<'_>,
 ) -> Vec<ResolvedHit<understory_box_tree::NodeId, ()>> {
     tree.intersect_rect(rect, filter)
         .map(|id| ResolvedHit {
             node: id,
             path: None,
-            depth_key: tree.z_index(id).map(DepthKey::Z).unwrap_or(DepthKey::Z(0)),
+            depth_key: depth_key(tree, id),
             localizer: Localizer::default(),
             meta: (),
         })
         .collect()
 }
 
+/// Rank a node by its position in the tree's paint order.
+fn depth_key(tree: &Tree, id: understory_box_tree::NodeId) -> DepthKey {
+    let rank = tree
+        .paint_rank(id)
+        .map_or(0, |rank| i32::try_from(rank).unwrap_or(i32::MAX));
+    DepthKey::Z(rank)
+}
+
 /// Tree navigation utilities for UI focus/keyboard traversal.
 ///
 /// These methods provide filtered traversal with wraparound semantics,
@@ -90,7 +134,7 @@ pub mod navigation {
     pub fn next_depth_first_filtered(
         tree: &Tree,
         current: NodeId,
-        filter: QueryFilter,
+        filter: QueryFilter<'_>,
     ) -> Option<NodeId> {
         if !tree.is_alive(current) {
             return None;
@@ -128,7 +172,7 @@ pub mod navigation {
     pub fn prev_depth_first_filtered(
         tree: &Tree,
         current: NodeId,
-        filter: QueryFilter,
+        filter: QueryFilter<'_>,
     ) -> Option<NodeId> {
         if !tree.is_alive(current) {
             return None;
@@ -162,16 +206,8 @@ pub mod navigation {
     }
 
     /// Check if a node matches the given filter criteria.
-    fn node_matches_filter(tree: &Tree, id: NodeId, filter: QueryFilter) -> bool {
-        if !tree.is_alive(id) {
-            return false;
-        }
-
-        let Some(flags) = tree.flags(id) else {
-            return false;
-        };
-
-        filter.matches(flags)
+    fn node_matches_filter(tree: &Tree, id: NodeId, filter: QueryFilter<'_>) -> bool {
+        tree.matches_filter(id, filter)
     }
 
     /// Find the root node of the subtree containing the given node.
@@ -247,6 +283,7 @@ pub mod navigation {
 
             let filter = QueryFilter {
                 required_flags: NodeFlags::VISIBLE,
+                ..QueryFilter::default()
             };
 
             // From root, next visible should be b (skipping hidden a)
@@ -302,6 +339,7 @@ pub mod navigation {
 
             let filter = QueryFilter {
                 required_flags: NodeFlags::PICKABLE,
+                ..QueryFilter::default()
             };
 
             // From root, next pickable should be b (skipping non-pickable a)
@@ -337,6 +375,7 @@ pub mod navigation {
 
             let filter = QueryFilter {
                 required_flags: NodeFlags::PICKABLE,
+                ..QueryFilter::default()
             };
 
             // Should return None since no nodes are pickable
@@ -377,6 +416,7 @@ pub mod navigation {
 
             let filter = QueryFilter {
                 required_flags: NodeFlags::VISIBLE,
+                ..QueryFilter::default()
             };
 
             // From visible_child (last visible), next should wrap to root
@@ -411,6 +451,7 @@ pub mod navigation {
 
             let filter = QueryFilter {
                 required_flags: NodeFlags::VISIBLE,
+                ..QueryFilter::default()
             };
 
             // Should work with live nodes
@@ -464,6 +505,7 @@ pub mod navigation {
 
             let filter = QueryFilter {
                 required_flags: NodeFlags::VISIBLE,
+                ..QueryFilter::default()
             };
 
             // From child1_visible (last visible in subtree1), should wrap to root1 (not cross to subtree2)
@@ -474,5 +516,149 @@ pub mod navigation {
             let next = next_depth_first_filtered(&tree, root1, filter).unwrap();
             assert_eq!(next, child1_visible);
         }
+
+        #[test]
+        fn filtered_traversal_scope_and_exclusions() {
+            const DISABLED: NodeFlags = NodeFlags::custom(0);
+            let mut tree = Tree::new();
+
+            // Build tree: root -> [dialog -> [ok, cancel(disabled), help], outside]
+            let root = tree.insert(None, LocalNode::default());
+            let dialog = tree.insert(Some(root), LocalNode::default());
+            let ok = tree.insert(Some(dialog), LocalNode::default());
+            let _cancel = tree.insert(
+                Some(dialog),
+                LocalNode {
+                    flags: NodeFlags::default() | DISABLED,
+                    ..Default::default()
+                },
+            );
+            let help = tree.insert(Some(dialog), LocalNode::default());
+            let _outside = tree.insert(Some(root), LocalNode::default());
+
+            let not_dialog = |id: NodeId| id != dialog;
+            let filter = QueryFilter::new()
+                .within(dialog)
+                .none_of(DISABLED)
+                .predicate(&not_dialog);
+
+            // Disabled nodes, the dialog itself, and nodes outside it are skipped.
+            assert_eq!(next_depth_first_filtered(&tree, ok, filter), Some(help));
+            assert_eq!(next_depth_first_filtered(&tree, help, filter), Some(ok));
+            assert_eq!(prev_depth_first_filtered(&tree, ok, filter), Some(help));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::Router;
+    use crate::types::WidgetLookup;
+    use understory_box_tree::{LocalNode, NodeFlags, NodeId};
+
+    struct Lookup;
+    impl WidgetLookup<NodeId> for Lookup {
+        type WidgetId = NodeId;
+        fn widget_of(&self, node: &NodeId) -> Option<Self::WidgetId> {
+            Some(*node)
+        }
+    }
+
+    fn routed_target(hits: &[ResolvedHit<NodeId, ()>]) -> Option<NodeId> {
+        let router: Router<NodeId, Lookup> = Router::new(Lookup);
+        router
+            .handle_with_hits(hits)
+            .iter()
+            .find(|d| d.phase == crate::types::Phase::Target)
+            .map(|d| d.node)
+    }
+
+    #[test]
+    fn routed_point_hits_target_the_topmost_node() {
+        let mut tree = Tree::new();
+        let root = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                ..Default::default()
+            },
+        );
+        let child = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 50.0, 50.0),
+                ..Default::default()
+            },
+        );
+        let _ = tree.commit();
+
+        let pt = Point::new(10.0, 10.0);
+        let hits = hits_for_point(&tree, pt, QueryFilter::new());
+        assert_eq!(
+            hits.iter().map(|h| h.node).collect::<Vec<_>>(),
+            [child, root]
+        );
+        assert_eq!(routed_target(&hits), Some(child));
+        assert_eq!(
+            tree.hit_test_point(pt, QueryFilter::new()).map(|h| h.node),
+            Some(child)
+        );
+    }
+
+    #[test]
+    fn stacking_context_scopes_routed_z_index() {
+        let mut tree = Tree::new();
+        let root = tree.insert(
+            None,
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                ..Default::default()
+            },
+        );
+        let dialog = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                z_index: 1,
+                flags: NodeFlags::default() | NodeFlags::STACKING_CONTEXT,
+                ..Default::default()
+            },
+        );
+        let field = tree.insert(
+            Some(dialog),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+                z_index: 100,
+                ..Default::default()
+            },
+        );
+        let popover = tree.insert(
+            Some(root),
+            LocalNode {
+                local_bounds: Rect::new(0.0, 0.0, 50.0, 50.0),
+                z_index: 5,
+                ..Default::default()
+            },
+        );
+        let _ = tree.commit();
+
+        let pt = Point::new(10.0, 10.0);
+        let filter = QueryFilter::new();
+        assert_eq!(
+            tree.hit_test_point(pt, filter).map(|h| h.node),
+            Some(popover)
+        );
+        assert_eq!(
+            routed_target(&hits_for_point(&tree, pt, filter)),
+            Some(popover)
+        );
+
+        // Hits from separate queries still rank the popover above the dialog's contents.
+        let top = top_hit_for_point(&tree, pt, filter).unwrap();
+        let mut merged = hits_for_rect(&tree, Rect::new(60.0, 60.0, 70.0, 70.0), filter);
+        assert!(merged.iter().any(|h| h.node == field));
+        merged.insert(0, top);
+        assert_eq!(routed_target(&merged), Some(popover));
     }
 }
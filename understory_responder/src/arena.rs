@@ -0,0 +1,403 @@
+// Copyright 2025 the Understory Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Gesture arena: resolve competing recognizers for a pointer to a single winner.
+//!
+//! A list item can be draggable while sitting inside a scrollable pane; both
+//! want the same pointer stream, and [`dispatcher::run`](crate::dispatcher::run)
+//! alone can only stop or continue propagation. The arena lets every interested
+//! recognizer join per pointer, then claim or reject as the gesture evolves, and
+//! decides which one gets the pointer.
+//!
+//! ## Lifecycle
+//!
+//! 1) On pointer down, nodes on the dispatch path [`add`](GestureArena::add) their
+//!    recognizers (or use [`GestureArena::add_from_dispatch`]).
+//! 2) After the down event has been dispatched, [`close`](GestureArena::close) the arena.
+//!    No further members can join.
+//! 3) As moves arrive, recognizers [`accept`](GestureArena::accept) (claim) or
+//!    [`reject`](GestureArena::reject) (give up).
+//! 4) On pointer up, [`sweep`](GestureArena::sweep) the arena; on pointer cancel,
+//!    [`cancel`](GestureArena::cancel) it.
+//!
+//! ## Resolution rules
+//!
+//! - **Eager winner**: the first member to accept while the arena is still open
+//!   wins as soon as it closes. After closing, the first member to accept wins
+//!   immediately.
+//! - **Last one standing**: once closed, if only one member remains (because it
+//!   was alone, or every other member rejected) it wins.
+//! - **Sweep**: on pointer up an unresolved arena is decided in favor of its first
+//!   member, which is the innermost node when members are added target → root.
+//!
+//! Every resolution reports [`ArenaEvent::Cancel`] for each member that lost
+//! without rejecting itself, followed by [`ArenaEvent::Win`] for the winner.
+//! The arena does not call recognizers; apply the returned events yourself.
+//!
+//! ## Minimal example
+//!
+//! ```
+//! use understory_responder::arena::{ArenaEvent, GestureArena};
+//!
+//! #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+//! enum Recognizer { ItemDrag, PaneScroll }
+//!
+//! let mut arena: GestureArena<u32, Recognizer> = GestureArena::new();
+//! let pointer = 1;
+//! arena.add(pointer, Recognizer::ItemDrag);
+//! arena.add(pointer, Recognizer::PaneScroll);
+//! assert!(arena.close(pointer).is_empty());
+//!
+//! // The pointer moves vertically: the pane claims it and the item drag is cancelled.
+//! assert_eq!(arena.accept(pointer, Recognizer::PaneScroll), vec![
+//!     ArenaEvent::Cancel { pointer, member: Recognizer::ItemDrag },
+//!     ArenaEvent::Win { pointer, member: Recognizer::PaneScroll },
+//! ]);
+//! assert_eq!(arena.winner(pointer), Some(Recognizer::PaneScroll));
+//! arena.sweep(pointer);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::types::{Dispatch, Phase};
+
+/// A resolution produced by [`GestureArena`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ArenaEvent<P, G> {
+    /// `member` won the arena for `pointer` and now owns its events.
+    Win {
+        /// Pointer whose arena was resolved.
+        pointer: P,
+        /// The winning member.
+        member: G,
+    },
+    /// `member` lost the arena for `pointer` and should cancel its gesture.
+    Cancel {
+        /// Pointer whose arena was resolved.
+        pointer: P,
+        /// The losing member.
+        member: G,
+    },
+}
+
+#[derive(Clone, Debug)]
+struct Arena<P, G> {
+    pointer: P,
+    members: Vec<G>,
+    open: bool,
+    eager_winner: Option<G>,
+    winner: Option<G>,
+}
+
+/// Per-pointer arenas of competing gesture recognizers.
+///
+/// `P` identifies pointers and `G` identifies members, for example a
+/// `(node, recognizer kind)` pair. See the [module docs](self) for the rules.
+#[derive(Clone, Debug)]
+pub struct GestureArena<P, G> {
+    arenas: Vec<Arena<P, G>>,
+}
+
+impl<P: Copy + Eq, G: Copy + Eq> GestureArena<P, G> {
+    /// Create an arena set with no active pointers.
+    pub fn new() -> Self {
+        Self { arenas: Vec::new() }
+    }
+
+    /// Add `member` to the arena for `pointer`, opening it if needed.
+    ///
+    /// Returns `false` if the arena is already closed or `member` already joined.
+    pub fn add(&mut self, pointer: P, member: G) -> bool {
+        let arena = match self.arenas.iter().position(|a| a.pointer == pointer) {
+            Some(i) => &mut self.arenas[i],
+            None => {
+                self.arenas.push(Arena {
+                    pointer,
+                    members: Vec::new(),
+                    open: true,
+                    eager_winner: None,
+                    winner: None,
+                });
+                self.arenas.last_mut().unwrap()
+            }
+        };
+        if !arena.open || arena.members.contains(&member) {
+            return false;
+        }
+        arena.members.push(member);
+        true
+    }
+
+    /// Add members for the target and bubble entries of a dispatch sequence,
+    /// innermost first, using `recognizer` to pick each entry's member (if any).
+    ///
+    /// Returns the number of members added.
+    pub fn add_from_dispatch<K, W, M>(
+        &mut self,
+        pointer: P,
+        seq: &[Dispatch<K, W, M>],
+        mut recognizer: impl FnMut(&Dispatch<K, W, M>) -> Option<G>,
+    ) -> usize {
+        seq.iter()
+            .filter(|d| d.phase != Phase::Capture)
+            .filter_map(&mut recognizer)
+            .filter(|&member| self.add(pointer, member))
+            .count()
+    }
+
+    /// Close the arena for `pointer` to new members and try to resolve it.
+    pub fn close(&mut self, pointer: P) -> Vec<ArenaEvent<P, G>> {
+        let Some(i) = self.index(pointer) else {
+            return Vec::new();
+        };
+        let arena = &mut self.arenas[i];
+        arena.open = false;
+        if arena.winner.is_some() {
+            return Vec::new();
+        }
+        if let Some(eager) = arena.eager_winner {
+            return resolve(arena, eager);
+        }
+        self.try_last_standing(i)
+    }
+
+    /// `member` claims the gesture for `pointer`.
+    ///
+    /// While the arena is open this records an eager winner; once closed the
+    /// claim wins immediately. Claims on a resolved arena are ignored.
+    pub fn accept(&mut self, pointer: P, member: G) -> Vec<ArenaEvent<P, G>> {
+        let Some(i) = self.index(pointer) else {
+            return Vec::new();
+        };
+        let arena = &mut self.arenas[i];
+        if arena.winner.is_some() || !arena.members.contains(&member) {
+            return Vec::new();
+        }
+        if arena.open {
+            arena.eager_winner.get_or_insert(member);
+            Vec::new()
+        } else {
+            resolve(arena, member)
+        }
+    }
+
+    /// `member` gives up on the gesture for `pointer`.
+    ///
+    /// The rejecting member receives no [`ArenaEvent::Cancel`]. If it leaves a
+    /// single member in a closed arena, that member wins.
+    pub fn reject(&mut self, pointer: P, member: G) -> Vec<ArenaEvent<P, G>> {
+        let Some(i) = self.index(pointer) else {
+            return Vec::new();
+        };
+        let arena = &mut self.arenas[i];
+        if arena.winner.is_some() {
+            return Vec::new();
+        }
+        arena.members.retain(|m| *m != member);
+        if arena.eager_winner == Some(member) {
+            arena.eager_winner = None;
+        }
+        if arena.open {
+            return Vec::new();
+        }
+        self.try_last_standing(i)
+    }
+
+    /// Finish the arena for `pointer` on pointer up.
+    ///
+    /// If unresolved, the first member wins and the rest are cancelled. The
+    /// arena is removed either way.
+    pub fn sweep(&mut self, pointer: P) -> Vec<ArenaEvent<P, G>> {
+        let Some(i) = self.index(pointer) else {
+            return Vec::new();
+        };
+        let mut arena = self.arenas.swap_remove(i);
+        match (arena.winner, arena.members.first().copied()) {
+            (None, Some(first)) => resolve(&mut arena, first),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Abandon the arena for `pointer` (pointer cancel), cancelling every
+    /// member including a winner.
+    pub fn cancel(&mut self, pointer: P) -> Vec<ArenaEvent<P, G>> {
+        let Some(i) = self.index(pointer) else {
+            return Vec::new();
+        };
+        let arena = self.arenas.swap_remove(i);
+        arena
+            .members
+            .into_iter()
+            .map(|member| ArenaEvent::Cancel { pointer, member })
+            .collect()
+    }
+
+    /// Return the winner for `pointer`, if the arena has been resolved.
+    pub fn winner(&self, pointer: P) -> Option<G> {
+        self.index(pointer).and_then(|i| self.arenas[i].winner)
+    }
+
+    /// Return whether an arena exists for `pointer`.
+    pub fn contains(&self, pointer: P) -> bool {
+        self.index(pointer).is_some()
+    }
+
+    /// Return whether the arena for `pointer` still accepts members.
+    pub fn is_open(&self, pointer: P) -> bool {
+        self.index(pointer).is_some_and(|i| self.arenas[i].open)
+    }
+
+    /// Return the members still competing for `pointer` (just the winner once resolved).
+    pub fn members(&self, pointer: P) -> &[G] {
+        self.index(pointer).map_or(&[], |i| &self.arenas[i].members)
+    }
+
+    fn index(&self, pointer: P) -> Option<usize> {
+        self.arenas.iter().position(|a| a.pointer == pointer)
+    }
+
+    /// Resolve a closed arena whose members have dwindled to one, and drop
+    /// arenas that have no members left.
+    fn try_last_standing(&mut self, i: usize) -> Vec<ArenaEvent<P, G>> {
+        let arena = &mut self.arenas[i];
+        match arena.members.as_slice() {
+            [] => {
+                self.arenas.swap_remove(i);
+                Vec::new()
+            }
+            &[only] => resolve(arena, only),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl<P: Copy + Eq, G: Copy + Eq> Default for GestureArena<P, G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn resolve<P: Copy, G: Copy + Eq>(arena: &mut Arena<P, G>, winner: G) -> Vec<ArenaEvent<P, G>> {
+    let pointer = arena.pointer;
+    let mut out: Vec<_> = arena
+        .members
+        .iter()
+        .filter(|&&m| m != winner)
+        .map(|&member| ArenaEvent::Cancel { pointer, member })
+        .collect();
+    out.push(ArenaEvent::Win {
+        pointer,
+        member: winner,
+    });
+    arena.members.clear();
+    arena.members.push(winner);
+    arena.eager_winner = None;
+    arena.winner = Some(winner);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn win(member: u32) -> ArenaEvent<u8, u32> {
+        ArenaEvent::Win { pointer: 0, member }
+    }
+
+    fn cancel(member: u32) -> ArenaEvent<u8, u32> {
+        ArenaEvent::Cancel { pointer: 0, member }
+    }
+
+    #[test]
+    fn eager_winner_resolves_on_close() {
+        let mut a: GestureArena<u8, u32> = GestureArena::new();
+        a.add(0, 1);
+        a.add(0, 2);
+        a.add(0, 3);
+        assert!(a.accept(0, 2).is_empty());
+        assert!(a.accept(0, 3).is_empty());
+        assert_eq!(a.close(0), vec![cancel(1), cancel(3), win(2)]);
+        assert_eq!(a.winner(0), Some(2));
+        // Late members and claims are ignored.
+        assert!(!a.add(0, 4));
+        assert!(a.accept(0, 3).is_empty());
+        assert!(a.sweep(0).is_empty());
+        assert!(!a.contains(0));
+    }
+
+    #[test]
+    fn single_member_wins_on_close_and_claims_win_after_close() {
+        let mut a: GestureArena<u8, u32> = GestureArena::new();
+        a.add(0, 7);
+        assert_eq!(a.close(0), vec![win(7)]);
+
+        let mut a: GestureArena<u8, u32> = GestureArena::new();
+        a.add(0, 1);
+        a.add(0, 2);
+        assert!(a.close(0).is_empty());
+        assert!(!a.is_open(0));
+        assert_eq!(a.accept(0, 2), vec![cancel(1), win(2)]);
+    }
+
+    #[test]
+    fn last_one_standing_wins() {
+        let mut a: GestureArena<u8, u32> = GestureArena::new();
+        a.add(0, 1);
+        a.add(0, 2);
+        a.add(0, 3);
+        // Rejecting while open only removes the member.
+        assert!(a.reject(0, 1).is_empty());
+        assert!(a.close(0).is_empty());
+        assert_eq!(a.reject(0, 3), vec![win(2)]);
+
+        // Everyone rejects: the arena is dropped without a winner.
+        let mut a: GestureArena<u8, u32> = GestureArena::new();
+        a.add(0, 1);
+        a.reject(0, 1);
+        assert!(a.close(0).is_empty());
+        assert!(!a.contains(0));
+    }
+
+    #[test]
+    fn sweep_and_cancel() {
+        let mut a: GestureArena<u8, u32> = GestureArena::new();
+        a.add(0, 1);
+        a.add(0, 2);
+        a.add(1, 5);
+        a.add(1, 6);
+        a.close(0);
+        a.close(1);
+        assert_eq!(a.sweep(0), vec![cancel(2), win(1)]);
+        assert_eq!(
+            a.cancel(1),
+            vec![
+                ArenaEvent::Cancel {
+                    pointer: 1,
+                    member: 5
+                },
+                ArenaEvent::Cancel {
+                    pointer: 1,
+                    member: 6
+                },
+            ]
+        );
+        assert!(a.sweep(1).is_empty());
+    }
+
+    #[test]
+    fn members_from_dispatch_are_innermost_first() {
+        let seq: Vec<Dispatch<u32, (), ()>> = vec![
+            Dispatch::capture(1),
+            Dispatch::capture(2),
+            Dispatch::target(2),
+            Dispatch::bubble(2),
+            Dispatch::bubble(1),
+        ];
+        let mut a: GestureArena<u8, u32> = GestureArena::new();
+        // Node 2 (list item) drags, node 1 (pane) scrolls; each joins once.
+        let added = a.add_from_dispatch(0, &seq, |d| Some(d.node * 10));
+        assert_eq!(added, 2);
+        assert_eq!(a.members(0), &[20, 10]);
+    }
+}
@@ -61,6 +61,34 @@
 //! ]);
 //! ```
 //!
+//! ### Registered listeners
+//!
+//! For several listeners per node, keep them in a [`ListenerRegistry`] keyed by
+//! node and phase and walk the sequence with [`run_registered`]. Listeners on the
+//! same entry run in insertion order; [`Outcome::Stop`] lets the rest of them run
+//! before propagation ends, while [`Outcome::StopImmediate`] skips them.
+//!
+//! ```
+//! use understory_responder::dispatcher::{run_registered, ListenerOptions, ListenerRegistry};
+//! use understory_responder::types::{Dispatch, Outcome, Phase};
+//!
+//! type Listener = fn(&mut Vec<&'static str>) -> Outcome;
+//! let mut listeners: ListenerRegistry<u32, Listener> = ListenerRegistry::new();
+//! listeners.add(2, Phase::Target, |log| { log.push("a"); Outcome::StopImmediate }, ListenerOptions::default());
+//! listeners.add(2, Phase::Target, |log| { log.push("b"); Outcome::Continue }, ListenerOptions::default());
+//! listeners.add(1, Phase::Bubble, |log| { log.push("parent"); Outcome::Continue }, ListenerOptions::default());
+//!
+//! let seq: Vec<Dispatch<u32, (), ()>> = vec![
+//!     Dispatch::capture(1),
+//!     Dispatch::target(2),
+//!     Dispatch::bubble(1),
+//! ];
+//! let mut log = Vec::new();
+//! let stopped = run_registered(&mut listeners, &seq, &mut log, |listener, _d, log, _opts| listener(log));
+//! assert_eq!(stopped.map(|d| d.node), Some(2));
+//! assert_eq!(log, ["a"]);
+//! ```
+//!
 //! ### Tracking "consumed" / "default prevented" in your event
 //!
 //! Higher‑level semantics such as “consumed” or “default prevented” live on
@@ -104,7 +132,10 @@
 //! assert!(ev.default_prevented);         // skip default action
 //! ```
 
-use crate::types::{Dispatch, Outcome};
+use alloc::vec::Vec;
+
+use crate::router::path_from_dispatch;
+use crate::types::{Dispatch, Outcome, Phase};
 
 /// Run a handler over a dispatch sequence and honor stop outcomes.
 ///
@@ -128,10 +159,10 @@ use crate::types::{Dispatch, Outcome};
 ///
 /// ## Tips
 ///
-/// - Multiple listeners per node/phase: proxy them inside your `handler`; short‑circuit to emulate
-///   “stopImmediatePropagation”.
+/// - Multiple listeners per node/phase: register them in a [`ListenerRegistry`] and use
+///   [`run_registered`], which also honors [`Outcome::StopImmediate`].
 /// - Default prevention: add a `default_prevented: bool` flag to your event and set it in `handler`.
-///   After `run`, check the flag to decide whether to execute a default action.
+///   After `run`, let [`DefaultActions::run_after`] check the flag and execute a default action.
 ///
 /// ## Examples
 ///
@@ -203,16 +234,272 @@ pub fn run<'a, K, W, M, E>(
         match handler(d, event) {
             Outcome::Continue => {}
             // Abort propagation immediately (spec-aligned: no target/bubble if raised in capture).
-            Outcome::Stop => return Some(d),
+            Outcome::Stop | Outcome::StopImmediate => return Some(d),
         }
     }
     None
 }
 
+/// Identifies a listener added to a [`ListenerRegistry`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ListenerId(u64);
+
+/// Flags attached to a registered listener.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListenerOptions {
+    /// The listener promises not to prevent the default action.
+    ///
+    /// Default prevention lives on your event payload, so the dispatcher cannot
+    /// enforce this; it is passed to the `invoke` callback of [`run_registered`]
+    /// so the payload can ignore such requests.
+    pub passive: bool,
+    /// Remove the listener after its first invocation.
+    pub once: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Entry<K, H> {
+    id: ListenerId,
+    node: K,
+    phase: Phase,
+    options: ListenerOptions,
+    handler: H,
+}
+
+/// Listeners keyed by node and phase, kept in insertion order.
+///
+/// A listener registered for [`Phase::Capture`] runs on capture entries for its
+/// node, [`Phase::Target`] on target entries and [`Phase::Bubble`] on bubble
+/// entries. As in the DOM, the router emits only a target entry for the target
+/// node, so its capture and bubble listeners run on that entry too: capture
+/// listeners first, then target listeners, then bubble listeners. Hand-built
+/// sequences that also list the target under capture or bubble run those
+/// listeners twice. `H` is whatever your listeners are: a boxed closure, a function
+/// pointer, or an id into your own table; [`run_registered`] hands each one to
+/// your `invoke` callback.
+#[derive(Clone, Debug)]
+pub struct ListenerRegistry<K, H> {
+    entries: Vec<Entry<K, H>>,
+    next_id: u64,
+}
+
+impl<K: Copy + Eq, H> ListenerRegistry<K, H> {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Register `handler` for `node` in `phase`, after any existing listeners.
+    pub fn add(
+        &mut self,
+        node: K,
+        phase: Phase,
+        handler: H,
+        options: ListenerOptions,
+    ) -> ListenerId {
+        let id = ListenerId(self.next_id);
+        self.next_id += 1;
+        self.entries.push(Entry {
+            id,
+            node,
+            phase,
+            options,
+            handler,
+        });
+        id
+    }
+
+    /// Remove a listener, returning it if it was still registered.
+    pub fn remove(&mut self, id: ListenerId) -> Option<H> {
+        let index = self.entries.iter().position(|e| e.id == id)?;
+        Some(self.entries.remove(index).handler)
+    }
+
+    /// Remove every listener registered for `node`.
+    pub fn remove_node(&mut self, node: &K) {
+        self.entries.retain(|e| e.node != *node);
+    }
+
+    /// Iterate over the listeners for `node` in `phase`, in invocation order.
+    pub fn listeners(&self, node: &K, phase: Phase) -> impl Iterator<Item = &H> {
+        self.entries
+            .iter()
+            .filter(move |e| e.node == *node && e.phase == phase)
+            .map(|e| &e.handler)
+    }
+
+    /// Return the number of registered listeners.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return `true` if no listeners are registered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<K: Copy + Eq, H> Default for ListenerRegistry<K, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Walk a dispatch sequence, invoking every registered listener for each entry.
+///
+/// For each entry, the listeners registered for its node and phase are invoked
+/// in insertion order through `invoke`; target entries also run the node's
+/// capture listeners before and its bubble listeners after, as described on
+/// [`ListenerRegistry`]. `invoke` which receives the listener, the entry,
+/// the event payload and the listener's options. Listeners added or removed
+/// by the caller between entries are respected; `once` listeners are removed
+/// right after they run.
+///
+/// - [`Outcome::Continue`]: keep going.
+/// - [`Outcome::Stop`]: finish the remaining listeners on this entry, then stop.
+/// - [`Outcome::StopImmediate`]: stop right away.
+///
+/// Returns the entry where propagation stopped, or `None` if the sequence completed.
+pub fn run_registered<'a, K, W, M, E, H>(
+    registry: &mut ListenerRegistry<K, H>,
+    seq: &'a [Dispatch<K, W, M>],
+    event: &mut E,
+    mut invoke: impl FnMut(&mut H, &Dispatch<K, W, M>, &mut E, ListenerOptions) -> Outcome,
+) -> Option<&'a Dispatch<K, W, M>>
+where
+    K: Copy + Eq,
+{
+    let mut ids = Vec::new();
+    for d in seq {
+        // Snapshot the listeners for this entry so `once` removal doesn't skip any.
+        ids.clear();
+        let phases: &[Phase] = if d.phase == Phase::Target {
+            &[Phase::Capture, Phase::Target, Phase::Bubble]
+        } else {
+            core::slice::from_ref(&d.phase)
+        };
+        for &phase in phases {
+            ids.extend(
+                registry
+                    .entries
+                    .iter()
+                    .filter(|e| e.node == d.node && e.phase == phase)
+                    .map(|e| e.id),
+            );
+        }
+        let mut stop = false;
+        for &id in &ids {
+            let Some(index) = registry.entries.iter().position(|e| e.id == id) else {
+                continue;
+            };
+            let entry = &mut registry.entries[index];
+            let options = entry.options;
+            let outcome = invoke(&mut entry.handler, d, event, options);
+            if options.once {
+                registry.entries.remove(index);
+            }
+            match outcome {
+                Outcome::Continue => {}
+                Outcome::Stop => stop = true,
+                Outcome::StopImmediate => return Some(d),
+            }
+        }
+        if stop {
+            return Some(d);
+        }
+    }
+    None
+}
+
+/// Event payloads that can report whether their default action was prevented.
+pub trait DefaultPrevented {
+    /// Returns `true` if a handler prevented the default action.
+    fn default_prevented(&self) -> bool;
+}
+
+impl DefaultPrevented for bool {
+    fn default_prevented(&self) -> bool {
+        *self
+    }
+}
+
+/// Default actions keyed by node, run after propagation unless prevented.
+///
+/// `A` is the action type for one kind of event, e.g. a function pointer over
+/// your click payload or an enum of built-in behaviors. Keep one registry per
+/// event type. As with DOM activation behavior, only the innermost node on the
+/// path that has an action runs it, and stopping propagation does not prevent it.
+#[derive(Clone, Debug)]
+pub struct DefaultActions<K, A> {
+    actions: Vec<(K, A)>,
+}
+
+impl<K: Copy + Eq, A> DefaultActions<K, A> {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self {
+            actions: Vec::new(),
+        }
+    }
+
+    /// Register the default action for `node`, returning the one it replaces.
+    pub fn register(&mut self, node: K, action: A) -> Option<A> {
+        if let Some((_, slot)) = self.actions.iter_mut().find(|(k, _)| *k == node) {
+            return Some(core::mem::replace(slot, action));
+        }
+        self.actions.push((node, action));
+        None
+    }
+
+    /// Remove the default action for `node`.
+    pub fn remove(&mut self, node: &K) -> Option<A> {
+        let index = self.actions.iter().position(|(k, _)| k == node)?;
+        Some(self.actions.remove(index).1)
+    }
+
+    /// Return the default action for `node`, if any.
+    pub fn get(&self, node: &K) -> Option<&A> {
+        self.actions.iter().find(|(k, _)| k == node).map(|(_, a)| a)
+    }
+
+    /// Run the default action for a dispatched event unless it was prevented.
+    ///
+    /// Call after [`run`] or [`run_registered`] with the same sequence. The
+    /// innermost node on the sequence's path with a registered action is passed
+    /// to `invoke` and returned; `None` if prevented or no node has an action.
+    pub fn run_after<W, M, E: DefaultPrevented>(
+        &mut self,
+        seq: &[Dispatch<K, W, M>],
+        event: &mut E,
+        mut invoke: impl FnMut(&mut A, K, &mut E),
+    ) -> Option<K> {
+        if event.default_prevented() {
+            return None;
+        }
+        let path = path_from_dispatch(seq);
+        let (node, index) = path
+            .iter()
+            .rev()
+            .find_map(|node| Some((*node, self.actions.iter().position(|(k, _)| k == node)?)))?;
+        let action = &mut self.actions[index].1;
+        invoke(action, node, event);
+        Some(node)
+    }
+}
+
+impl<K: Copy + Eq, A> Default for DefaultActions<K, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::Phase;
+    use alloc::boxed::Box;
     use alloc::vec;
     use alloc::vec::Vec;
 
@@ -366,4 +653,217 @@ mod tests {
             vec![(Phase::Capture, 1), (Phase::Capture, 2), (Phase::Target, 2),]
         );
     }
+
+    type Listener = fn(&mut Log, u32) -> Outcome;
+    type Log = Vec<(u32, &'static str)>;
+    type Boxed = Box<dyn Fn(&mut Log, u32) -> Outcome>;
+
+    fn log(tag: &'static str, outcome: Outcome) -> impl Fn(&mut Log, u32) -> Outcome {
+        move |log, node| {
+            log.push((node, tag));
+            outcome
+        }
+    }
+
+    fn run_log(registry: &mut ListenerRegistry<Node, Boxed>) -> (Option<(Phase, u32)>, Log) {
+        let seq = mk_seq();
+        let mut seen = Vec::new();
+        let stopped = run_registered(registry, &seq, &mut seen, |l, d, e, _| l(e, d.node.0));
+        (stopped.map(|d| (d.phase, d.node.0)), seen)
+    }
+
+    #[test]
+    fn registered_listeners_run_in_order_with_stop_semantics() {
+        let mut reg: ListenerRegistry<Node, Boxed> = ListenerRegistry::new();
+        let opts = ListenerOptions::default();
+        reg.add(
+            Node(1),
+            Phase::Capture,
+            Box::new(log("cap", Outcome::Continue)),
+            opts,
+        );
+        reg.add(
+            Node(2),
+            Phase::Target,
+            Box::new(log("a", Outcome::Continue)),
+            opts,
+        );
+        reg.add(
+            Node(2),
+            Phase::Target,
+            Box::new(log("b", Outcome::Stop)),
+            opts,
+        );
+        let c = reg.add(
+            Node(2),
+            Phase::Target,
+            Box::new(log("c", Outcome::Continue)),
+            opts,
+        );
+        reg.add(
+            Node(1),
+            Phase::Bubble,
+            Box::new(log("bub", Outcome::Continue)),
+            opts,
+        );
+
+        // Stop lets "c" run on the same entry but skips bubbling.
+        let (stopped, seen) = run_log(&mut reg);
+        assert_eq!(stopped, Some((Phase::Target, 2)));
+        assert_eq!(seen, vec![(1, "cap"), (2, "a"), (2, "b"), (2, "c")]);
+
+        // StopImmediate skips the remaining listeners.
+        reg.remove(c);
+        reg.add(
+            Node(2),
+            Phase::Target,
+            Box::new(log("d", Outcome::Continue)),
+            opts,
+        );
+        let first = reg.listeners(&Node(2), Phase::Target).count();
+        assert_eq!(first, 3);
+        reg.remove_node(&Node(2));
+        reg.add(
+            Node(2),
+            Phase::Target,
+            Box::new(log("x", Outcome::StopImmediate)),
+            opts,
+        );
+        reg.add(
+            Node(2),
+            Phase::Target,
+            Box::new(log("y", Outcome::Continue)),
+            opts,
+        );
+        let (stopped, seen) = run_log(&mut reg);
+        assert_eq!(stopped, Some((Phase::Target, 2)));
+        assert_eq!(seen, vec![(1, "cap"), (2, "x")]);
+    }
+
+    #[test]
+    fn once_listeners_are_removed_and_passive_is_forwarded() {
+        let mut reg: ListenerRegistry<Node, Listener> = ListenerRegistry::new();
+        reg.add(
+            Node(2),
+            Phase::Target,
+            |log, n| {
+                log.push((n, "once"));
+                Outcome::Continue
+            },
+            ListenerOptions {
+                once: true,
+                passive: true,
+            },
+        );
+        reg.add(
+            Node(1),
+            Phase::Bubble,
+            |log, n| {
+                log.push((n, "bubble"));
+                Outcome::Continue
+            },
+            ListenerOptions::default(),
+        );
+        let seq = mk_seq();
+        let mut seen = Vec::new();
+        let mut passive = Vec::new();
+        for _ in 0..2 {
+            let stopped = run_registered(&mut reg, &seq, &mut seen, |l, d, e, o| {
+                passive.push(o.passive);
+                l(e, d.node.0)
+            });
+            assert!(stopped.is_none());
+        }
+        assert_eq!(seen, vec![(2, "once"), (1, "bubble"), (1, "bubble")]);
+        assert_eq!(passive, vec![true, false, false]);
+        assert_eq!(reg.len(), 1);
+    }
+
+    #[test]
+    fn capture_and_bubble_listeners_run_at_target() {
+        let mut reg: ListenerRegistry<Node, Boxed> = ListenerRegistry::new();
+        let opts = ListenerOptions::default();
+        reg.add(
+            Node(2),
+            Phase::Bubble,
+            Box::new(log("bub", Outcome::Continue)),
+            opts,
+        );
+        reg.add(
+            Node(2),
+            Phase::Target,
+            Box::new(log("target", Outcome::Continue)),
+            opts,
+        );
+        reg.add(
+            Node(2),
+            Phase::Capture,
+            Box::new(log("cap", Outcome::Continue)),
+            opts,
+        );
+        reg.add(
+            Node(1),
+            Phase::Bubble,
+            Box::new(log("parent", Outcome::Continue)),
+            opts,
+        );
+
+        // The router emits only a target entry for the target node.
+        let seq: Vec<Dispatch<Node, (), ()>> = vec![
+            Dispatch::capture(Node(1)),
+            Dispatch::target(Node(2)),
+            Dispatch::bubble(Node(1)),
+        ];
+        let mut seen = Vec::new();
+        let stopped = run_registered(&mut reg, &seq, &mut seen, |l, d, e, _| l(e, d.node.0));
+        assert!(stopped.is_none());
+        assert_eq!(
+            seen,
+            vec![(2, "cap"), (2, "target"), (2, "bub"), (1, "parent")]
+        );
+    }
+
+    #[test]
+    fn run_treats_stop_immediate_as_stop() {
+        let seq = mk_seq();
+        let stopped = run(&seq, &mut (), |_, _| Outcome::StopImmediate);
+        assert_eq!(stopped.map(|d| d.node.0), Some(1));
+    }
+
+    #[test]
+    fn default_actions_run_innermost_unless_prevented() {
+        #[derive(Default)]
+        struct Ev {
+            prevent: bool,
+            done: Vec<u32>,
+        }
+        impl DefaultPrevented for Ev {
+            fn default_prevented(&self) -> bool {
+                self.prevent
+            }
+        }
+
+        let seq = mk_seq();
+        let mut defaults: DefaultActions<Node, fn(&mut Ev, u32)> = DefaultActions::new();
+        defaults.register(Node(1), |ev, n| ev.done.push(n));
+        let mut ev = Ev::default();
+        let ran = defaults.run_after(&seq, &mut ev, |a, node, ev| a(ev, node.0));
+        assert_eq!(ran, Some(Node(1)));
+
+        // The target's own action wins over the ancestor's, even after Stop.
+        defaults.register(Node(2), |ev, n| ev.done.push(n * 10));
+        let stopped = run(&seq, &mut ev, |_, _| Outcome::Stop);
+        assert!(stopped.is_some());
+        defaults.run_after(&seq, &mut ev, |a, node, ev| a(ev, node.0));
+        assert_eq!(ev.done, vec![1, 20]);
+
+        ev.prevent = true;
+        assert_eq!(
+            defaults.run_after(&seq, &mut ev, |a, node, ev| a(ev, node.0)),
+            None
+        );
+        assert!(defaults.remove(&Node(2)).is_some());
+        assert!(defaults.get(&Node(2)).is_none());
+        assert!(defaults.run_after(&seq, &mut false, |_, _, _| {}).is_some());
+    }
 }
@@ -51,8 +51,14 @@
 //!
 //! - Extract root→target paths using [`path_from_dispatch`](crate::router::path_from_dispatch)
 //! - Feed paths to hover, focus, click, and drag state managers as needed
+//! - Use [`Router::hover_transition`](router::Router::hover_transition) to synthesize enter/leave (non-bubbling) and over/out (bubbling) sequences when the hover target changes
 //! - See `understory_event_state` documentation for details on each state manager
 //!
+//! ## Encapsulation
+//!
+//! Mark component hosts with [`Router::set_encapsulation`](router::Router::set_encapsulation) to retarget events across encapsulation boundaries.
+//! Each [`Dispatch`](crate::types::Dispatch) carries the target as seen from its node, so entries outside a component report the host node instead of its internals.
+//!
 //! ## Focus Routing
 //!
 //! Focus routing is separate from pointer routing.
@@ -63,6 +69,7 @@
 //! ## Dispatcher
 //!
 //! Execute handlers over the responder sequence and honor stop/cancelation with [`dispatcher::run`].
+//! To run several listeners per node and phase, register them in a `ListenerRegistry` and use [`dispatcher::run_registered`].
 //!
 //! ```no_run
 //! use understory_responder::dispatcher;
@@ -84,8 +91,14 @@
 //! assert!(default_prevented);
 //! ```
 //!
+//! Register typed default actions per node with [`DefaultActions`](dispatcher::DefaultActions); they run after propagation unless the event's default was prevented.
 //! See the `dispatcher` module docs for additional patterns and helpers.
 //!
+//! ## Gesture Arena
+//!
+//! When several recognizers on the dispatch path want the same pointer (say a draggable list item inside a scrollable pane), register them in a [`GestureArena`](crate::arena::GestureArena) for that pointer.
+//! Recognizers claim or reject as the gesture evolves; the arena picks a single winner using "eager winner" and "last one standing" rules and reports cancellation for the losers.
+//!
 //! ## Adapters
 //!
 //! The [`adapters`] module provides integration with other Understory crates:
@@ -100,6 +113,7 @@
 extern crate alloc;
 
 pub mod adapters;
+pub mod arena;
 pub mod dispatcher;
 pub mod router;
 pub mod types;
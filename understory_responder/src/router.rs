@@ -30,7 +30,8 @@
 use alloc::vec::Vec;
 
 use crate::types::{
-    Dispatch, Localizer, NoParent, ParentLookup, Phase, ResolvedHit, TieBreakPolicy, WidgetLookup,
+    Dispatch, HoverTransition, Localizer, NoParent, ParentLookup, Phase, ResolvedHit,
+    TieBreakPolicy, WidgetLookup,
 };
 
 /// Deterministic responder chain router.
@@ -56,6 +57,7 @@ pub struct Router<K, L: WidgetLookup<K>, P: ParentLookup<K> = NoParent> {
     pub(crate) parent: P,
     pub(crate) default_tie_break: TieBreakPolicy,
     pub(crate) scope: Option<fn(&K) -> bool>,
+    pub(crate) encapsulation: Option<fn(&K) -> bool>,
     pub(crate) focus: Option<K>,
     // Minimal capture for skeleton; production would be per-pointer id.
     pub(crate) capture: Option<K>,
@@ -78,6 +80,7 @@ impl<K: Copy + Eq, L: WidgetLookup<K>, P: ParentLookup<K> + Default> Router<K, L
             parent: P::default(),
             default_tie_break: TieBreakPolicy::Newer,
             scope: None,
+            encapsulation: None,
             focus: None,
             capture: None,
             _phantom: core::marker::PhantomData,
@@ -93,6 +96,7 @@ impl<K: Copy + Eq, L: WidgetLookup<K>, P: ParentLookup<K>> Router<K, L, P> {
             parent,
             default_tie_break: TieBreakPolicy::Newer,
             scope: None,
+            encapsulation: None,
             focus: None,
             capture: None,
             _phantom: core::marker::PhantomData,
@@ -109,6 +113,16 @@ impl<K: Copy + Eq, L: WidgetLookup<K>, P: ParentLookup<K>> Router<K, L, P> {
         self.scope = scope;
     }
 
+    /// Set an optional encapsulation predicate marking component hosts.
+    ///
+    /// Nodes below a host are internal to it. Each emitted [`Dispatch::target`]
+    /// is retargeted so entries outside a component see the host instead of
+    /// its internal nodes, like shadow DOM retargeting. The full path is still
+    /// dispatched; listeners decide what to do with internal entries.
+    pub fn set_encapsulation(&mut self, is_host: Option<fn(&K) -> bool>) {
+        self.encapsulation = is_host;
+    }
+
     /// Set the focused node (reserved for higher-level policies; currently not used in routing).
     pub fn set_focus(&mut self, node: Option<K>) {
         self.focus = node;
@@ -224,23 +238,104 @@ impl<K: Copy + Eq, L: WidgetLookup<K>, P: ParentLookup<K>> Router<K, L, P> {
         self.emit_path(path, localizer, meta)
     }
 
+    /// Synthesize boundary-event sequences for a hover target change from `prev` to `next`.
+    ///
+    /// Paths are reconstructed with [`ParentLookup`]. See
+    /// [`Router::hover_transition_paths`] for the rules.
+    pub fn hover_transition<M: Clone>(
+        &self,
+        prev: Option<K>,
+        next: Option<K>,
+    ) -> HoverTransition<K, L::WidgetId, M> {
+        let prev_path = prev.map_or_else(Vec::new, |t| Self::reconstruct_path(t, &self.parent));
+        let next_path = next.map_or_else(Vec::new, |t| Self::reconstruct_path(t, &self.parent));
+        self.hover_transition_paths(&prev_path, &next_path)
+    }
+
+    /// Synthesize boundary-event sequences for a hover change between two root→target paths,
+    /// e.g. successive results of [`path_from_dispatch`].
+    ///
+    /// - `out`/`over` bubble: they are full capture → target → bubble sequences for the
+    ///   previous and new targets.
+    /// - `leave`/`enter` don't bubble: each node that is left or entered (below the
+    ///   common ancestor) gets its own capture → target sequence.
+    /// - While a node is captured (see [`Router::capture`]), the pointer counts as
+    ///   hovering the captured node, whatever `next_path` says.
+    ///
+    /// Nothing is emitted when the target does not change. Entries carry a default
+    /// [`Localizer`] and no metadata.
+    pub fn hover_transition_paths<M: Clone>(
+        &self,
+        prev_path: &[K],
+        next_path: &[K],
+    ) -> HoverTransition<K, L::WidgetId, M> {
+        let captured;
+        let next_path = match self.capture {
+            Some(cap) if next_path.last() != Some(&cap) => {
+                captured = Self::reconstruct_path(cap, &self.parent);
+                &captured[..]
+            }
+            _ => next_path,
+        };
+
+        let mut transition = HoverTransition {
+            out: Vec::new(),
+            leave: Vec::new(),
+            over: Vec::new(),
+            enter: Vec::new(),
+        };
+        if prev_path.last() == next_path.last() {
+            return transition;
+        }
+
+        let lca = prev_path
+            .iter()
+            .zip(next_path)
+            .take_while(|(a, b)| a == b)
+            .count();
+        transition.out = self.emit_path(prev_path.to_vec(), Localizer::default(), None);
+        transition.leave = (lca..prev_path.len())
+            .rev()
+            .map(|i| self.emit_non_bubbling(&prev_path[..=i]))
+            .collect();
+        transition.over = self.emit_path(next_path.to_vec(), Localizer::default(), None);
+        transition.enter = (lca..next_path.len())
+            .map(|i| self.emit_non_bubbling(&next_path[..=i]))
+            .collect();
+        transition
+    }
+
+    /// Build the entry for `path[index]`, reporting the target as seen from that node.
     fn make_dispatch<M: Clone>(
         &self,
         phase: Phase,
-        node: K,
+        path: &[K],
+        index: usize,
         localizer: Localizer,
         meta: Option<M>,
     ) -> Dispatch<K, L::WidgetId, M> {
+        let node = path[index];
         let widget = self.lookup.widget_of(&node);
         Dispatch {
             phase,
             node,
+            target: Some(self.retarget(path, index)),
             widget,
             localizer,
             meta,
         }
     }
 
+    /// The target of `path` as seen from `path[index]`: the first encapsulation
+    /// host at or below that node which still has the real target inside it,
+    /// or the real target itself.
+    fn retarget(&self, path: &[K], index: usize) -> K {
+        let last = path.len() - 1;
+        self.encapsulation
+            .and_then(|is_host| (index..last).find(|&i| is_host(&path[i])))
+            .map_or(path[last], |i| path[i])
+    }
+
     fn reconstruct_path(target: K, parent_lookup: &impl ParentLookup<K>) -> Vec<K> {
         let mut out = Vec::new();
         let mut cur = target;
@@ -264,26 +359,48 @@ impl<K: Copy + Eq, L: WidgetLookup<K>, P: ParentLookup<K>> Router<K, L, P> {
     ) -> Vec<Dispatch<K, L::WidgetId, M>> {
         let mut out = Vec::new();
         // Split into ancestors and target. If path is empty, nothing to emit.
-        let (target, ancestors) = match path.split_last() {
-            Some((t, ancestors)) => (t, ancestors),
-            None => return out,
+        let Some((_, ancestors)) = path.split_last() else {
+            return out;
         };
 
         // Capture: root→(excluding target)
-        for &n in ancestors {
-            out.push(self.make_dispatch(Phase::Capture, n, localizer.clone(), meta.clone()));
+        for i in 0..ancestors.len() {
+            out.push(self.make_dispatch(Phase::Capture, &path, i, localizer.clone(), meta.clone()));
         }
 
         // Target: only the target element
-        out.push(self.make_dispatch(Phase::Target, *target, localizer.clone(), meta.clone()));
+        out.push(self.make_dispatch(
+            Phase::Target,
+            &path,
+            ancestors.len(),
+            localizer.clone(),
+            meta.clone(),
+        ));
 
         // Bubble: parent→root (excluding target)
-        for &n in ancestors.iter().rev() {
-            out.push(self.make_dispatch(Phase::Bubble, n, localizer.clone(), meta.clone()));
+        for i in (0..ancestors.len()).rev() {
+            out.push(self.make_dispatch(Phase::Bubble, &path, i, localizer.clone(), meta.clone()));
         }
         out
     }
 
+    /// Capture → target sequence for the last node of `path`, without bubbling.
+    fn emit_non_bubbling<M: Clone>(&self, path: &[K]) -> Vec<Dispatch<K, L::WidgetId, M>> {
+        let Some(last) = path.len().checked_sub(1) else {
+            return Vec::new();
+        };
+        (0..=last)
+            .map(|i| {
+                let phase = if i == last {
+                    Phase::Target
+                } else {
+                    Phase::Capture
+                };
+                self.make_dispatch(phase, path, i, Localizer::default(), None)
+            })
+            .collect()
+    }
+
     fn tiebreak(&self, a: &K, b: &K) -> core::cmp::Ordering {
         use core::cmp::Ordering::*;
         match self.default_tie_break {
@@ -953,6 +1070,7 @@ mod tests {
             Dispatch {
                 phase: Phase::Capture,
                 node: 1_u32,
+                target: None,
                 widget: Some(10),
                 localizer: Localizer::default(),
                 meta: Some(()),
@@ -960,6 +1078,7 @@ mod tests {
             Dispatch {
                 phase: Phase::Capture,
                 node: 2_u32,
+                target: None,
                 widget: Some(20),
                 localizer: Localizer::default(),
                 meta: Some(()),
@@ -967,6 +1086,7 @@ mod tests {
             Dispatch {
                 phase: Phase::Target,
                 node: 3_u32,
+                target: None,
                 widget: Some(30),
                 localizer: Localizer::default(),
                 meta: Some(()),
@@ -974,6 +1094,7 @@ mod tests {
             Dispatch {
                 phase: Phase::Bubble,
                 node: 3_u32,
+                target: None,
                 widget: Some(30),
                 localizer: Localizer::default(),
                 meta: Some(()),
@@ -981,6 +1102,7 @@ mod tests {
             Dispatch {
                 phase: Phase::Bubble,
                 node: 2_u32,
+                target: None,
                 widget: Some(20),
                 localizer: Localizer::default(),
                 meta: Some(()),
@@ -988,6 +1110,7 @@ mod tests {
             Dispatch {
                 phase: Phase::Bubble,
                 node: 1_u32,
+                target: None,
                 widget: Some(10),
                 localizer: Localizer::default(),
                 meta: Some(()),
@@ -998,4 +1121,124 @@ mod tests {
         // Should include all `Capture` phases plus the `Target` phase
         assert_eq!(path, vec![1, 2, 3]);
     }
+
+    fn phases(seq: &[Dispatch<Node, u32, ()>]) -> Vec<(Phase, u32)> {
+        seq.iter().map(|d| (d.phase, d.node.0)).collect()
+    }
+
+    struct Tree;
+    impl ParentLookup<Node> for Tree {
+        fn parent_of(&self, node: &Node) -> Option<Node> {
+            match node.0 {
+                2 | 4 => Some(Node(1)),
+                3 => Some(Node(2)),
+                _ => None,
+            }
+        }
+    }
+
+    // Tree: 1 → {2 → 3, 4}. Moving from 3 to 4 leaves 3 and 2, enters 4.
+    #[test]
+    fn hover_transition_synthesizes_boundary_events() {
+        let router: Router<Node, Lookup, Tree> = Router::with_parent(Lookup, Tree);
+        let t = router.hover_transition::<()>(Some(Node(3)), Some(Node(4)));
+
+        assert_eq!(
+            phases(&t.out),
+            vec![
+                (Phase::Capture, 1),
+                (Phase::Capture, 2),
+                (Phase::Target, 3),
+                (Phase::Bubble, 2),
+                (Phase::Bubble, 1),
+            ]
+        );
+        let leave: Vec<_> = t.leave.iter().map(|s| phases(s)).collect();
+        assert_eq!(
+            leave,
+            vec![
+                vec![(Phase::Capture, 1), (Phase::Capture, 2), (Phase::Target, 3)],
+                vec![(Phase::Capture, 1), (Phase::Target, 2)],
+            ]
+        );
+        assert_eq!(
+            phases(&t.over),
+            vec![(Phase::Capture, 1), (Phase::Target, 4), (Phase::Bubble, 1)]
+        );
+        let enter: Vec<_> = t.enter.iter().map(|s| phases(s)).collect();
+        assert_eq!(enter, vec![vec![(Phase::Capture, 1), (Phase::Target, 4)]]);
+        assert!(t.over.iter().all(|d| d.widget == Some(d.node.0)));
+
+        // Entering from nothing enters every node; same target is a no-op.
+        let t = router.hover_transition::<()>(None, Some(Node(2)));
+        assert!(t.out.is_empty() && t.leave.is_empty());
+        assert_eq!(t.enter.len(), 2);
+        assert!(
+            router
+                .hover_transition::<()>(Some(Node(2)), Some(Node(2)))
+                .is_empty()
+        );
+
+        // Moving into a child only enters the child.
+        let t =
+            router.hover_transition_paths::<()>(&[Node(1), Node(2)], &[Node(1), Node(2), Node(3)]);
+        assert!(t.leave.is_empty());
+        assert_eq!(t.enter.len(), 1);
+        assert_eq!(phases(&t.out).len(), 3);
+    }
+
+    #[test]
+    fn hover_transition_honors_capture() {
+        let mut router: Router<Node, Lookup, Tree> = Router::with_parent(Lookup, Tree);
+        router.capture(Some(Node(3)));
+        // The pointer moved over 4, but 3 holds capture: nothing changes.
+        let t = router.hover_transition::<()>(Some(Node(3)), Some(Node(4)));
+        assert!(t.is_empty());
+        // Hovering elsewhere when capture starts moves hover to the captured node.
+        let t = router.hover_transition::<()>(Some(Node(4)), Some(Node(4)));
+        assert_eq!(
+            t.enter
+                .iter()
+                .map(|s| s.last().unwrap().node.0)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+    }
+
+    // Tree: 1 → 2 (component host) → 3 → 4 (internal). Entries outside the
+    // component see the host as the target.
+    #[test]
+    fn encapsulation_retargets_outside_entries() {
+        let mut router: Router<Node, Lookup, NoParent> = Router::new(Lookup);
+        router.set_encapsulation(Some(|n: &Node| n.0 == 2));
+        let hits = vec![ResolvedHit {
+            node: Node(4),
+            path: Some(vec![Node(1), Node(2), Node(3), Node(4)]),
+            depth_key: DepthKey::Z(0),
+            localizer: Localizer::default(),
+            meta: (),
+        }];
+        let out = router.handle_with_hits::<()>(&hits);
+        let seen: Vec<(Phase, u32, u32)> = out
+            .iter()
+            .map(|d| (d.phase, d.node.0, d.target.unwrap().0))
+            .collect();
+        assert_eq!(
+            seen,
+            vec![
+                (Phase::Capture, 1, 2),
+                (Phase::Capture, 2, 2),
+                (Phase::Capture, 3, 4),
+                (Phase::Target, 4, 4),
+                (Phase::Bubble, 3, 4),
+                (Phase::Bubble, 2, 2),
+                (Phase::Bubble, 1, 2),
+            ]
+        );
+
+        // Without a boundary every entry sees the real target.
+        router.set_encapsulation(None);
+        let out = router.handle_with_hits::<()>(&hits);
+        assert!(out.iter().all(|d| d.target == Some(Node(4))));
+    }
 }
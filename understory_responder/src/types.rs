@@ -35,7 +35,15 @@ pub enum Outcome {
     /// Continue within the current phase.
     Continue,
     /// Stop propagation within the current phase.
+    ///
+    /// With [`run_registered`](crate::dispatcher::run_registered), the remaining
+    /// listeners on the same dispatch entry still run (like DOM `stopPropagation`).
     Stop,
+    /// Stop propagation and skip the remaining listeners on the same dispatch
+    /// entry (like DOM `stopImmediatePropagation`).
+    ///
+    /// [`run`](crate::dispatcher::run) treats this like [`Outcome::Stop`].
+    StopImmediate,
 }
 
 /// Policy for breaking ties after equal primary depth.
@@ -166,6 +174,12 @@ pub struct Dispatch<K, W, M = ()> {
     pub phase: Phase,
     /// Node associated with this dispatch step.
     pub node: K,
+    /// Event target as seen from [`Dispatch::node`].
+    ///
+    /// The router fills this in, retargeting across encapsulation boundaries
+    /// (see [`Router::set_encapsulation`](crate::router::Router::set_encapsulation)).
+    /// `None` for hand-built entries unless set with [`Dispatch::with_target`].
+    pub target: Option<K>,
     /// Optional widget id corresponding to the node.
     pub widget: Option<W>,
     /// Transformation context for local event coordinates.
@@ -197,6 +211,7 @@ impl<K, W, M> Dispatch<K, W, M> {
         Self {
             phase: Phase::Capture,
             node,
+            target: None,
             widget: None,
             localizer: Localizer::default(),
             meta: None,
@@ -208,6 +223,7 @@ impl<K, W, M> Dispatch<K, W, M> {
         Self {
             phase: Phase::Target,
             node,
+            target: None,
             widget: None,
             localizer: Localizer::default(),
             meta: None,
@@ -219,12 +235,19 @@ impl<K, W, M> Dispatch<K, W, M> {
         Self {
             phase: Phase::Bubble,
             node,
+            target: None,
             widget: None,
             localizer: Localizer::default(),
             meta: None,
         }
     }
 
+    /// Attach the (possibly retargeted) event target to this dispatch entry.
+    pub fn with_target(mut self, target: K) -> Self {
+        self.target = Some(target);
+        self
+    }
+
     /// Attach a widget id to this dispatch entry.
     pub fn with_widget(mut self, w: W) -> Self {
         self.widget = Some(w);
@@ -244,6 +267,33 @@ impl<K, W, M> Dispatch<K, W, M> {
     }
 }
 
+/// Boundary-event dispatch sequences for a change of hover target.
+///
+/// Produced by [`Router::hover_transition`](crate::router::Router::hover_transition).
+/// Dispatch the sequences in field order, mirroring DOM pointer boundary events:
+/// `pointerout`, `pointerleave`, `pointerover`, then `pointerenter`.
+#[derive(Clone, Debug)]
+pub struct HoverTransition<K, W, M = ()> {
+    /// `out` at the previous target: capture → target → bubble along the old path.
+    pub out: Vec<Dispatch<K, W, M>>,
+    /// One non-bubbling sequence (capture → target) per node left, inner→outer.
+    pub leave: Vec<Vec<Dispatch<K, W, M>>>,
+    /// `over` at the new target: capture → target → bubble along the new path.
+    pub over: Vec<Dispatch<K, W, M>>,
+    /// One non-bubbling sequence (capture → target) per node entered, outer→inner.
+    pub enter: Vec<Vec<Dispatch<K, W, M>>>,
+}
+
+impl<K, W, M> HoverTransition<K, W, M> {
+    /// Returns `true` if there is nothing to dispatch (the target did not change).
+    pub fn is_empty(&self) -> bool {
+        self.out.is_empty()
+            && self.leave.is_empty()
+            && self.over.is_empty()
+            && self.enter.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
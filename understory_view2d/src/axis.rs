@@ -0,0 +1,284 @@
+// Copyright 2025 the Understory Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Axis transforms: monotonic mappings between world and axis coordinates.
+
+use core::f64::consts::{E, LN_10};
+use core::ops::Range;
+
+#[cfg(not(feature = "std"))]
+use kurbo::common::FloatFuncs as _;
+
+/// A monotonic mapping between world coordinates and linear axis coordinates.
+///
+/// Viewports apply their pan and zoom in *axis* space: a world coordinate is
+/// first mapped with [`AxisTransform::world_to_axis`], then scaled and translated
+/// into view space. With [`LinearAxis`] (the default) axis and world
+/// coordinates are identical; other transforms give logarithmic, symmetric
+/// logarithmic or broken axes while keeping zooming, fitting and clamping
+/// well defined.
+///
+/// Implementations must be strictly increasing and continuous, and
+/// [`AxisTransform::axis_to_world`] must invert [`AxisTransform::world_to_axis`].
+pub trait AxisTransform {
+    /// Maps a world coordinate to an axis coordinate.
+    fn world_to_axis(&self, world: f64) -> f64;
+
+    /// Maps an axis coordinate back to a world coordinate.
+    fn axis_to_world(&self, axis: f64) -> f64;
+
+    /// Returns the world distance per axis unit at `axis`, i.e. the derivative
+    /// of [`AxisTransform::axis_to_world`].
+    ///
+    /// The default uses a central difference.
+    fn world_per_axis(&self, axis: f64) -> f64 {
+        let h = 1e-6 * axis.abs().max(1.0);
+        (self.axis_to_world(axis + h) - self.axis_to_world(axis - h)) / (2.0 * h)
+    }
+}
+
+/// The identity transform: axis coordinates are world coordinates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LinearAxis;
+
+impl AxisTransform for LinearAxis {
+    #[inline]
+    fn world_to_axis(&self, world: f64) -> f64 {
+        world
+    }
+
+    #[inline]
+    fn axis_to_world(&self, axis: f64) -> f64 {
+        axis
+    }
+
+    #[inline]
+    fn world_per_axis(&self, _axis: f64) -> f64 {
+        1.0
+    }
+}
+
+/// A base‑10 logarithmic axis: one axis unit per decade.
+///
+/// World coordinates must be positive; non‑positive values are treated as
+/// the smallest positive `f64`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LogAxis;
+
+impl AxisTransform for LogAxis {
+    fn world_to_axis(&self, world: f64) -> f64 {
+        world.max(f64::MIN_POSITIVE).ln() / LN_10
+    }
+
+    fn axis_to_world(&self, axis: f64) -> f64 {
+        10.0_f64.powf(axis)
+    }
+
+    fn world_per_axis(&self, axis: f64) -> f64 {
+        self.axis_to_world(axis) * LN_10
+    }
+}
+
+/// A symmetric logarithmic axis that is linear near zero.
+///
+/// Maps `x` to `sign(x) · ln(1 + |x| / linear_threshold)`, so it handles
+/// negative values and zero, and behaves like a log axis for
+/// `|x| ≫ linear_threshold`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SymlogAxis {
+    linear_threshold: f64,
+}
+
+impl SymlogAxis {
+    /// Creates a symlog axis; `linear_threshold` must be positive.
+    #[must_use]
+    pub fn new(linear_threshold: f64) -> Self {
+        debug_assert!(
+            linear_threshold > 0.0,
+            "symlog linear threshold must be positive"
+        );
+        Self {
+            linear_threshold: linear_threshold.abs().max(f64::MIN_POSITIVE),
+        }
+    }
+
+    /// Returns the size of the region around zero that is (approximately) linear.
+    #[must_use]
+    pub fn linear_threshold(&self) -> f64 {
+        self.linear_threshold
+    }
+}
+
+impl Default for SymlogAxis {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl AxisTransform for SymlogAxis {
+    fn world_to_axis(&self, world: f64) -> f64 {
+        (1.0 + world.abs() / self.linear_threshold).ln() * world.signum()
+    }
+
+    fn axis_to_world(&self, axis: f64) -> f64 {
+        (E.powf(axis.abs()) - 1.0) * self.linear_threshold * axis.signum()
+    }
+
+    fn world_per_axis(&self, axis: f64) -> f64 {
+        E.powf(axis.abs()) * self.linear_threshold
+    }
+}
+
+/// A broken axis that shows `N` world segments side by side.
+///
+/// The gaps between segments are collapsed to `gap` axis units each; world
+/// values inside a gap are spread linearly across it. Values before the
+/// first or after the last segment are extrapolated linearly. A single
+/// segment behaves like [`LinearAxis`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PiecewiseAxis<const N: usize> {
+    segments: [Range<f64>; N],
+    /// Axis coordinate of each segment's start.
+    offsets: [f64; N],
+    gap: f64,
+}
+
+impl<const N: usize> PiecewiseAxis<N> {
+    /// Creates a broken axis from increasing, non‑overlapping `segments`,
+    /// separated by `gap` axis units.
+    #[must_use]
+    pub fn new(segments: [Range<f64>; N], gap: f64) -> Self {
+        debug_assert!(
+            segments.iter().all(|s| s.end >= s.start)
+                && segments.windows(2).all(|w| w[1].start >= w[0].end),
+            "piecewise axis segments must be increasing and non-overlapping"
+        );
+        let gap = gap.max(0.0);
+        let mut offsets = [0.0; N];
+        for i in 0..N {
+            offsets[i] = if i == 0 {
+                segments[0].start
+            } else {
+                offsets[i - 1] + (segments[i - 1].end - segments[i - 1].start) + gap
+            };
+        }
+        Self {
+            segments,
+            offsets,
+            gap,
+        }
+    }
+
+    /// Returns the world segments.
+    #[must_use]
+    pub fn segments(&self) -> &[Range<f64>; N] {
+        &self.segments
+    }
+
+    /// Returns the gap between segments, in axis units.
+    #[must_use]
+    pub fn gap(&self) -> f64 {
+        self.gap
+    }
+
+    /// Index of the last segment starting at or before `value`, using `key` for the start.
+    fn segment_index(&self, value: f64, key: impl Fn(usize) -> f64) -> usize {
+        (1..N).take_while(|&i| key(i) <= value).last().unwrap_or(0)
+    }
+}
+
+impl<const N: usize> AxisTransform for PiecewiseAxis<N> {
+    fn world_to_axis(&self, world: f64) -> f64 {
+        if N == 0 {
+            return world;
+        }
+        let i = self.segment_index(world, |i| self.segments[i].start);
+        let seg = &self.segments[i];
+        if world <= seg.end || i + 1 == N {
+            return self.offsets[i] + (world - seg.start);
+        }
+        let next = self.segments[i + 1].start;
+        let t = (world - seg.end) / (next - seg.end);
+        self.offsets[i] + (seg.end - seg.start) + t * self.gap
+    }
+
+    fn axis_to_world(&self, axis: f64) -> f64 {
+        if N == 0 {
+            return axis;
+        }
+        let i = self.segment_index(axis, |i| self.offsets[i]);
+        let seg = &self.segments[i];
+        let seg_end = self.offsets[i] + (seg.end - seg.start);
+        if axis <= seg_end || i + 1 == N {
+            return seg.start + (axis - self.offsets[i]);
+        }
+        let next = self.segments[i + 1].start;
+        let t = (axis - seg_end) / self.gap;
+        seg.end + t * (next - seg.end)
+    }
+
+    fn world_per_axis(&self, axis: f64) -> f64 {
+        if N == 0 {
+            return 1.0;
+        }
+        let i = self.segment_index(axis, |i| self.offsets[i]);
+        let seg = &self.segments[i];
+        if axis <= self.offsets[i] + (seg.end - seg.start) || i + 1 == N {
+            1.0
+        } else {
+            (self.segments[i + 1].start - seg.end) / self.gap
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_roundtrip(t: &impl AxisTransform, values: &[f64]) {
+        for &v in values {
+            let back = t.axis_to_world(t.world_to_axis(v));
+            assert!((back - v).abs() <= 1e-9 * v.abs().max(1.0), "{v} -> {back}");
+        }
+    }
+
+    #[test]
+    fn transforms_roundtrip() {
+        assert_roundtrip(&LinearAxis, &[-5.0, 0.0, 3.5]);
+        assert_roundtrip(&LogAxis, &[1e-3, 1.0, 20.0, 1e6]);
+        assert_roundtrip(&SymlogAxis::new(10.0), &[-1e4, -3.0, 0.0, 3.0, 1e4]);
+        assert_roundtrip(
+            &PiecewiseAxis::new([0.0..10.0, 100.0..110.0, 500.0..510.0], 2.0),
+            &[-5.0, 0.0, 5.0, 10.0, 50.0, 105.0, 300.0, 505.0, 600.0],
+        );
+        assert!((LogAxis.world_to_axis(1000.0) - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn piecewise_collapses_gaps() {
+        let axis = PiecewiseAxis::new([0.0..10.0, 100.0..110.0], 2.0);
+        assert_eq!(axis.world_to_axis(10.0), 10.0);
+        assert_eq!(axis.world_to_axis(55.0), 11.0);
+        assert_eq!(axis.world_to_axis(100.0), 12.0);
+        assert_eq!(axis.world_to_axis(110.0), 22.0);
+        assert_eq!(axis.world_per_axis(5.0), 1.0);
+        assert_eq!(axis.world_per_axis(11.0), 45.0);
+    }
+
+    #[test]
+    fn default_derivative_matches_analytic() {
+        struct Tripled;
+        impl AxisTransform for Tripled {
+            fn world_to_axis(&self, world: f64) -> f64 {
+                world / 3.0
+            }
+            fn axis_to_world(&self, axis: f64) -> f64 {
+                axis * 3.0
+            }
+        }
+        assert!((Tripled.world_per_axis(7.0) - 3.0).abs() < 1e-6);
+        let log = LogAxis;
+        let numeric = (log.axis_to_world(2.0 + 1e-6) - log.axis_to_world(2.0 - 1e-6)) / 2e-6;
+        assert!((log.world_per_axis(2.0) - numeric).abs() / numeric < 1e-6);
+    }
+}
@@ -0,0 +1,749 @@
+// Copyright 2025 the Understory Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Headless, time‑stepped scroll physics: velocity tracking, fling, bounce, and eased scrolling.
+
+use core::ops::Range;
+
+#[cfg(not(feature = "std"))]
+use kurbo::common::FloatFuncs as _;
+
+use crate::util::exp;
+
+/// Number of samples retained by [`VelocityTracker`].
+const TRACKER_CAPACITY: usize = 20;
+
+/// Samples older than this (relative to the newest sample) are ignored, in milliseconds.
+const TRACKER_HORIZON_MS: u64 = 100;
+
+/// If no sample arrived for this long before release, the pointer is considered at rest.
+const TRACKER_STALE_MS: u64 = 40;
+
+/// Estimates a 1D velocity from timestamped position samples.
+///
+/// Samples are kept in a small fixed-size ring buffer (no allocation). The
+/// velocity is the slope of a least-squares line through the samples from the
+/// last 100 ms, which smooths out jitter in individual pointer events.
+///
+/// Timestamps are caller-provided milliseconds; positions are in any 1D unit
+/// and velocities are reported in units per second.
+#[derive(Clone, Debug)]
+pub struct VelocityTracker {
+    samples: [(u64, f64); TRACKER_CAPACITY],
+    /// Index of the next slot to write.
+    next: usize,
+    len: usize,
+}
+
+impl Default for VelocityTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VelocityTracker {
+    /// Creates an empty tracker.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            samples: [(0, 0.0); TRACKER_CAPACITY],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Discards all samples.
+    pub fn reset(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+
+    /// Records a position sample at `time_ms`.
+    ///
+    /// Samples are expected in non-decreasing time order; a sample older than
+    /// the newest one resets the tracker first.
+    pub fn add_sample(&mut self, time_ms: u64, position: f64) {
+        if self.newest().is_some_and(|(t, _)| time_ms < t) {
+            self.reset();
+        }
+        self.samples[self.next] = (time_ms, position);
+        self.next = (self.next + 1) % TRACKER_CAPACITY;
+        self.len = (self.len + 1).min(TRACKER_CAPACITY);
+    }
+
+    /// Returns the newest sample, if any.
+    #[must_use]
+    pub fn newest(&self) -> Option<(u64, f64)> {
+        (self.len > 0).then(|| self.samples[(self.next + TRACKER_CAPACITY - 1) % TRACKER_CAPACITY])
+    }
+
+    /// Estimates the velocity, in units per second, as of `now_ms`.
+    ///
+    /// Returns `0.0` when fewer than two recent samples are available or when
+    /// the newest sample is too old (the pointer stopped before `now_ms`).
+    #[must_use]
+    pub fn velocity(&self, now_ms: u64) -> f64 {
+        let Some((newest_t, _)) = self.newest() else {
+            return 0.0;
+        };
+        if now_ms.saturating_sub(newest_t) > TRACKER_STALE_MS {
+            return 0.0;
+        }
+
+        // Least-squares slope over samples within the horizon, with times
+        // relative to the newest sample to keep the sums well conditioned.
+        let (mut n, mut st, mut sx, mut stt, mut stx) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for i in 0..self.len {
+            let (t, x) = self.samples[(self.next + TRACKER_CAPACITY - 1 - i) % TRACKER_CAPACITY];
+            let age = newest_t - t;
+            if age > TRACKER_HORIZON_MS {
+                break;
+            }
+            let t = -(age as f64) / 1000.0;
+            n += 1.0;
+            st += t;
+            sx += x;
+            stt += t * t;
+            stx += t * x;
+        }
+        let denom = n * stt - st * st;
+        if n < 2.0 || denom.abs() < f64::EPSILON {
+            return 0.0;
+        }
+        (n * stx - st * sx) / denom
+    }
+}
+
+/// Tuning parameters for [`KineticScroll`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KineticConfig {
+    /// Exponential velocity decay rate during a fling, per second.
+    ///
+    /// Velocity is multiplied by `e^(-friction * t)`; a fling starting at
+    /// velocity `v` travels `v / friction` units in total.
+    pub friction: f64,
+    /// Speed (units per second) below which a fling comes to rest.
+    pub min_velocity: f64,
+    /// Natural frequency (radians per second) of the critically damped spring
+    /// that returns an overscrolled position to the nearest bound.
+    pub spring_frequency: f64,
+    /// Fraction of a drag delta applied while the position is outside the bounds.
+    pub overscroll_resistance: f64,
+    /// Whether flings may carry past the bounds and spring back.
+    ///
+    /// When `false`, flings stop at the bounds and drags are clamped to them.
+    pub bounce: bool,
+}
+
+impl Default for KineticConfig {
+    fn default() -> Self {
+        Self {
+            friction: 2.0,
+            min_velocity: 5.0,
+            spring_frequency: 20.0,
+            overscroll_resistance: 0.5,
+            bounce: true,
+        }
+    }
+}
+
+/// What a [`KineticScroll`] is currently doing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum KineticPhase {
+    /// At rest.
+    #[default]
+    Idle,
+    /// Following a drag; the position only changes through [`KineticScroll::drag_by`].
+    Dragging,
+    /// Decelerating after a fling.
+    Flinging,
+    /// Springing back into bounds after overscrolling.
+    Returning,
+    /// Easing towards a target set by [`KineticScroll::animate_to`].
+    Animating,
+}
+
+/// State of an eased animation started by [`KineticScroll::animate_to`].
+#[derive(Clone, Copy, Debug)]
+struct EaseAnimation {
+    from: f64,
+    to: f64,
+    start_ms: u64,
+    duration_ms: u64,
+}
+
+/// Time-stepped scroll physics over a single 1D position.
+///
+/// `KineticScroll` owns a scroll position and advances it when the host calls
+/// [`KineticScroll::step`] with the current timestamp, typically once per
+/// frame while [`KineticScroll::is_animating`] returns `true`. It supports:
+/// - drags with rubber-band resistance outside the bounds,
+/// - flings whose velocity is estimated by a [`VelocityTracker`] and decays
+///   exponentially,
+/// - a critically damped spring back to the bounds after overscrolling,
+/// - eased scrolling to a target (for example an animated "scroll to index").
+///
+/// The position is a plain `f64` in whatever space the host scrolls in, so the
+/// same physics can drive different targets:
+/// - a virtual list: use its scroll offset as the position, bounds
+///   `0.0..content_extent - viewport_extent`, and pass `-pointer_delta` to
+///   [`KineticScroll::drag_by`];
+/// - a [`Viewport1D`](crate::Viewport1D): use [`Viewport1D::pan`](crate::Viewport1D::pan)
+///   as the position, [`Viewport1D::pan_range`](crate::Viewport1D::pan_range)
+///   as the bounds, and pass the pointer delta directly;
+/// - a [`Viewport2D`](crate::Viewport2D): use one scroller per axis over the
+///   components of [`Viewport2D::pan`](crate::Viewport2D::pan), bounded by
+///   [`Viewport2D::pan_bounds`](crate::Viewport2D::pan_bounds).
+///
+/// Targets that cannot represent positions outside their bounds can apply
+/// [`KineticScroll::clamped_position`] and render [`KineticScroll::overscroll`]
+/// as an extra translation.
+///
+/// All timestamps are caller-provided milliseconds, so the physics is fully
+/// deterministic and can be driven by a fake clock in tests.
+///
+/// ```rust
+/// use understory_view2d::{KineticPhase, KineticScroll};
+///
+/// let mut scroll = KineticScroll::new(0.0);
+/// scroll.set_bounds(Some(0.0..1000.0));
+///
+/// // Drag 10 units every 10 ms, then release.
+/// scroll.begin_drag(0);
+/// for i in 1..=5 {
+///     scroll.drag_by(i * 10, 10.0);
+/// }
+/// scroll.end_drag(50);
+/// assert_eq!(scroll.phase(), KineticPhase::Flinging);
+///
+/// // Advance the fake clock until the fling settles.
+/// let mut now = 50;
+/// while scroll.is_animating() {
+///     now += 16;
+///     scroll.step(now);
+/// }
+/// assert!(scroll.position() > 50.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct KineticScroll {
+    config: KineticConfig,
+    position: f64,
+    velocity: f64,
+    bounds: Option<Range<f64>>,
+    phase: KineticPhase,
+    last_time_ms: u64,
+    tracker: VelocityTracker,
+    /// Unresisted drag position, used for velocity estimation.
+    drag_position: f64,
+    animation: Option<EaseAnimation>,
+}
+
+impl KineticScroll {
+    /// Creates an idle scroller at `position` with the default configuration.
+    #[must_use]
+    pub fn new(position: f64) -> Self {
+        Self::with_config(position, KineticConfig::default())
+    }
+
+    /// Creates an idle scroller at `position` with the given configuration.
+    #[must_use]
+    pub fn with_config(position: f64, config: KineticConfig) -> Self {
+        Self {
+            config,
+            position,
+            velocity: 0.0,
+            bounds: None,
+            phase: KineticPhase::Idle,
+            last_time_ms: 0,
+            tracker: VelocityTracker::new(),
+            drag_position: position,
+            animation: None,
+        }
+    }
+
+    /// Returns the configuration.
+    #[must_use]
+    pub fn config(&self) -> &KineticConfig {
+        &self.config
+    }
+
+    /// Replaces the configuration. Motion in progress continues with the new parameters.
+    pub fn set_config(&mut self, config: KineticConfig) {
+        self.config = config;
+    }
+
+    /// Returns the current position, which may lie outside the bounds while overscrolled.
+    #[must_use]
+    pub fn position(&self) -> f64 {
+        self.position
+    }
+
+    /// Returns the current position clamped into the bounds.
+    #[must_use]
+    pub fn clamped_position(&self) -> f64 {
+        self.clamp(self.position)
+    }
+
+    /// Returns how far the position lies outside the bounds (negative before the start).
+    #[must_use]
+    pub fn overscroll(&self) -> f64 {
+        self.position - self.clamped_position()
+    }
+
+    /// Returns the current velocity in units per second.
+    #[must_use]
+    pub fn velocity(&self) -> f64 {
+        self.velocity
+    }
+
+    /// Returns the current phase.
+    #[must_use]
+    pub fn phase(&self) -> KineticPhase {
+        self.phase
+    }
+
+    /// Returns `true` while the position changes over time without input,
+    /// that is, while the host should keep calling [`KineticScroll::step`].
+    #[must_use]
+    pub fn is_animating(&self) -> bool {
+        matches!(
+            self.phase,
+            KineticPhase::Flinging | KineticPhase::Returning | KineticPhase::Animating
+        )
+    }
+
+    /// Returns the bounds, if any.
+    #[must_use]
+    pub fn bounds(&self) -> Option<Range<f64>> {
+        self.bounds.clone()
+    }
+
+    /// Sets the range the position should settle within.
+    ///
+    /// The range is normalized so that `start <= end`. Changing the bounds
+    /// does not move the position; an idle scroller that ends up outside the
+    /// new bounds stays there until the next drag, fling, or [`KineticScroll::settle`].
+    pub fn set_bounds(&mut self, bounds: Option<Range<f64>>) {
+        self.bounds = bounds.map(|b| if b.start <= b.end { b } else { b.end..b.start });
+    }
+
+    /// Jumps to `position`, stopping any motion.
+    pub fn set_position(&mut self, position: f64) {
+        self.position = position;
+        self.stop();
+    }
+
+    /// Stops any motion in place.
+    pub fn stop(&mut self) {
+        self.velocity = 0.0;
+        self.animation = None;
+        self.phase = KineticPhase::Idle;
+    }
+
+    /// Springs back into bounds if currently overscrolled; otherwise stops.
+    pub fn settle(&mut self, time_ms: u64) {
+        self.animation = None;
+        self.last_time_ms = time_ms;
+        if self.overscroll() != 0.0 && self.config.bounce {
+            self.phase = KineticPhase::Returning;
+        } else {
+            self.position = self.clamped_position();
+            self.stop();
+        }
+    }
+
+    /// Starts a drag at `time_ms`, interrupting any motion in progress.
+    pub fn begin_drag(&mut self, time_ms: u64) {
+        self.velocity = 0.0;
+        self.animation = None;
+        self.phase = KineticPhase::Dragging;
+        self.last_time_ms = time_ms;
+        self.drag_position = self.position;
+        self.tracker.reset();
+        self.tracker.add_sample(time_ms, self.drag_position);
+    }
+
+    /// Moves the position by `delta` as part of a drag and returns the new position.
+    ///
+    /// Starts a drag if none is in progress. Movement that pushes further
+    /// outside the bounds is scaled by [`KineticConfig::overscroll_resistance`],
+    /// or clamped when [`KineticConfig::bounce`] is disabled.
+    pub fn drag_by(&mut self, time_ms: u64, delta: f64) -> f64 {
+        if self.phase != KineticPhase::Dragging {
+            self.begin_drag(time_ms);
+        }
+        self.last_time_ms = time_ms;
+        self.drag_position += delta;
+        self.tracker.add_sample(time_ms, self.drag_position);
+
+        let target = self.position + delta;
+        let over_before = self.overscroll();
+        let over_after = target - self.clamp(target);
+        self.position = if over_after == 0.0 {
+            target
+        } else if !self.config.bounce {
+            self.clamp(target)
+        } else {
+            // Only the part of the delta that increases overscroll is resisted.
+            let free = if over_before != 0.0 && over_after.signum() == over_before.signum() {
+                0.0
+            } else {
+                delta - over_after
+            };
+            self.position + free + (delta - free) * self.config.overscroll_resistance
+        };
+        self.position
+    }
+
+    /// Ends a drag at `time_ms`, flinging with the tracked release velocity.
+    ///
+    /// If the position is overscrolled, it springs back instead; if the
+    /// release velocity is below [`KineticConfig::min_velocity`], the scroller
+    /// becomes idle.
+    pub fn end_drag(&mut self, time_ms: u64) {
+        if self.phase != KineticPhase::Dragging {
+            return;
+        }
+        let velocity = self.tracker.velocity(time_ms);
+        self.tracker.reset();
+        self.fling(time_ms, velocity);
+    }
+
+    /// Starts a fling at `time_ms` with the given initial velocity (units per second).
+    pub fn fling(&mut self, time_ms: u64, velocity: f64) {
+        self.animation = None;
+        self.last_time_ms = time_ms;
+        self.velocity = velocity;
+        if self.overscroll() != 0.0 {
+            self.settle(time_ms);
+            self.velocity = if self.config.bounce { velocity } else { 0.0 };
+        } else if velocity.abs() < self.config.min_velocity {
+            self.stop();
+        } else {
+            self.phase = KineticPhase::Flinging;
+        }
+    }
+
+    /// Eases from the current position to `target` over `duration_ms`, starting at `time_ms`.
+    ///
+    /// The target is clamped into the bounds. A zero duration jumps immediately.
+    pub fn animate_to(&mut self, time_ms: u64, target: f64, duration_ms: u64) {
+        let to = self.clamp(target);
+        self.velocity = 0.0;
+        self.last_time_ms = time_ms;
+        if duration_ms == 0 || to == self.position {
+            self.position = to;
+            self.stop();
+            return;
+        }
+        self.animation = Some(EaseAnimation {
+            from: self.position,
+            to,
+            start_ms: time_ms,
+            duration_ms,
+        });
+        self.phase = KineticPhase::Animating;
+    }
+
+    /// Returns the position the current motion will come to rest at, if predictable.
+    ///
+    /// This is the animation target while animating, the nearest bound while
+    /// springing back, and the projected fling end (clamped to the bounds)
+    /// while flinging.
+    #[must_use]
+    pub fn resting_position(&self) -> f64 {
+        match self.phase {
+            KineticPhase::Animating => self.animation.map_or(self.position, |a| a.to),
+            KineticPhase::Returning => self.clamped_position(),
+            KineticPhase::Flinging => self.clamp(self.position + self.velocity / self.friction()),
+            KineticPhase::Idle | KineticPhase::Dragging => self.position,
+        }
+    }
+
+    /// Advances the simulation to `time_ms` and returns the new position.
+    ///
+    /// Steps with a timestamp earlier than the previous one are ignored.
+    pub fn step(&mut self, time_ms: u64) -> f64 {
+        let elapsed = time_ms.saturating_sub(self.last_time_ms);
+        self.last_time_ms = self.last_time_ms.max(time_ms);
+        match self.phase {
+            KineticPhase::Idle | KineticPhase::Dragging => {}
+            KineticPhase::Animating => self.step_animation(time_ms),
+            KineticPhase::Flinging => self.step_fling(elapsed as f64 / 1000.0),
+            KineticPhase::Returning => self.step_spring(elapsed as f64 / 1000.0),
+        }
+        self.position
+    }
+
+    fn friction(&self) -> f64 {
+        self.config.friction.max(f64::MIN_POSITIVE)
+    }
+
+    fn clamp(&self, position: f64) -> f64 {
+        match &self.bounds {
+            Some(b) => position.clamp(b.start, b.end),
+            None => position,
+        }
+    }
+
+    fn step_animation(&mut self, time_ms: u64) {
+        let Some(anim) = self.animation else {
+            self.stop();
+            return;
+        };
+        let t = time_ms.saturating_sub(anim.start_ms) as f64 / anim.duration_ms as f64;
+        if t >= 1.0 {
+            self.position = anim.to;
+            self.stop();
+            return;
+        }
+        // Ease-out cubic.
+        let u = 1.0 - t;
+        let eased = 1.0 - u * u * u;
+        self.position = anim.from + (anim.to - anim.from) * eased;
+    }
+
+    fn step_fling(&mut self, dt: f64) {
+        let k = self.friction();
+        let v0 = self.velocity;
+        let x0 = self.position;
+
+        // Time at which the fling would cross a bound, if it does within `dt`:
+        // solve x0 + v0 / k * (1 - e^(-k t)) = bound for t. A fling starting
+        // on the bound it moves towards (`s == 1`) hits it immediately.
+        let bound = self
+            .bounds
+            .as_ref()
+            .map(|b| if v0 > 0.0 { b.end } else { b.start });
+        let hit = bound.and_then(|bound| {
+            let s = 1.0 - (bound - x0) * k / v0;
+            (s > 0.0 && s <= 1.0).then(|| (bound, -s.ln() / k))
+        });
+
+        match hit {
+            Some((bound, t_hit)) if t_hit <= dt => {
+                let decay = exp(-k * t_hit);
+                self.position = bound;
+                self.velocity = v0 * decay;
+                if self.config.bounce {
+                    self.phase = KineticPhase::Returning;
+                    self.step_spring(dt - t_hit);
+                } else {
+                    self.stop();
+                }
+            }
+            _ => {
+                let decay = exp(-k * dt);
+                self.position = x0 + v0 / k * (1.0 - decay);
+                self.velocity = v0 * decay;
+                if self.velocity.abs() < self.config.min_velocity {
+                    self.stop();
+                }
+            }
+        }
+    }
+
+    fn step_spring(&mut self, dt: f64) {
+        // Critically damped spring towards the nearest bound:
+        // d(t) = (d0 + (v0 + w d0) t) e^(-w t)
+        // v(t) = (v0 - w (v0 + w d0) t) e^(-w t)
+        let target = self.clamped_position();
+        let w = self.config.spring_frequency.max(f64::MIN_POSITIVE);
+        let d0 = self.position - target;
+        let v0 = self.velocity;
+        let decay = exp(-w * dt);
+        let c = v0 + w * d0;
+        let d = (d0 + c * dt) * decay;
+        let v = (v0 - w * c * dt) * decay;
+
+        // Once the spring crosses the bound it would only oscillate within
+        // rounding error, so rest there; likewise once it is close enough.
+        let crossed = d0 != 0.0 && d.signum() != d0.signum() && d != 0.0;
+        if crossed || (d.abs() < 0.5 && v.abs() < self.config.min_velocity) {
+            self.position = target;
+            self.stop();
+        } else {
+            self.position = target + d;
+            self.velocity = v;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KineticConfig, KineticPhase, KineticScroll, VelocityTracker};
+
+    /// Steps `scroll` every 16 ms from `start` until it stops animating.
+    fn run_to_rest(scroll: &mut KineticScroll, start: u64) -> u64 {
+        let mut now = start;
+        while scroll.is_animating() {
+            now += 16;
+            scroll.step(now);
+            assert!(now - start < 60_000, "scroll never came to rest");
+        }
+        now
+    }
+
+    #[test]
+    fn velocity_tracker_estimates_constant_velocity() {
+        let mut tracker = VelocityTracker::new();
+        for i in 0..10 {
+            tracker.add_sample(i * 8, i as f64 * 4.0);
+        }
+        // 4 units per 8 ms = 500 units per second.
+        assert!((tracker.velocity(72) - 500.0).abs() < 1e-6);
+        // Samples older than the horizon are ignored.
+        tracker.add_sample(200, 36.0);
+        assert_eq!(tracker.velocity(200), 0.0);
+        // A pointer that stopped before release has no velocity.
+        let mut tracker = VelocityTracker::new();
+        tracker.add_sample(0, 0.0);
+        tracker.add_sample(10, 10.0);
+        assert_eq!(tracker.velocity(100), 0.0);
+    }
+
+    #[test]
+    fn fling_decays_to_projected_rest_position() {
+        let mut scroll = KineticScroll::new(0.0);
+        scroll.fling(0, 1000.0);
+        assert_eq!(scroll.phase(), KineticPhase::Flinging);
+        let projected = scroll.resting_position();
+        assert!((projected - 500.0).abs() < 1e-9);
+
+        let mut last = 0.0;
+        let mut now = 0;
+        while scroll.is_animating() {
+            now += 16;
+            let pos = scroll.step(now);
+            assert!(pos >= last, "fling must be monotonic");
+            last = pos;
+        }
+        // Stops once velocity drops below `min_velocity`, just short of the projection.
+        let remaining = KineticConfig::default().min_velocity / KineticConfig::default().friction;
+        assert!(projected - last <= remaining + 1e-6);
+        assert!(last > 490.0);
+    }
+
+    #[test]
+    fn stepping_is_independent_of_frame_rate() {
+        let mut a = KineticScroll::new(0.0);
+        let mut b = KineticScroll::new(0.0);
+        a.fling(0, 800.0);
+        b.fling(0, 800.0);
+        for t in 1..=100 {
+            a.step(t * 5);
+        }
+        b.step(250);
+        b.step(500);
+        assert!((a.position() - b.position()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fling_past_bounds_bounces_back() {
+        let mut scroll = KineticScroll::new(90.0);
+        scroll.set_bounds(Some(0.0..100.0));
+        scroll.fling(0, 2000.0);
+
+        let mut max = 0.0_f64;
+        let mut now = 0;
+        while scroll.is_animating() {
+            now += 16;
+            max = max.max(scroll.step(now));
+        }
+        assert!(max > 100.0, "fling should overshoot before springing back");
+        assert_eq!(scroll.position(), 100.0);
+        assert_eq!(scroll.phase(), KineticPhase::Idle);
+
+        // Without bounce, the fling stops at the bound.
+        let config = KineticConfig {
+            bounce: false,
+            ..KineticConfig::default()
+        };
+        let mut scroll = KineticScroll::with_config(90.0, config);
+        scroll.set_bounds(Some(0.0..100.0));
+        scroll.fling(0, 2000.0);
+        scroll.step(16);
+        assert_eq!(scroll.position(), 100.0);
+        assert!(!scroll.is_animating());
+    }
+
+    #[test]
+    fn fling_starting_at_bound_hits_it_immediately() {
+        let mut scroll = KineticScroll::new(100.0);
+        scroll.set_bounds(Some(0.0..100.0));
+        scroll.fling(0, 2000.0);
+
+        let mut max = 0.0_f64;
+        let mut now = 0;
+        while scroll.is_animating() {
+            now += 16;
+            max = max.max(scroll.step(now));
+        }
+        assert!(max > 100.0, "bounce should still overshoot");
+        assert!(max < 150.0, "the fling must not run unbounded, got {max}");
+        assert_eq!(scroll.position(), 100.0);
+        assert_eq!(scroll.phase(), KineticPhase::Idle);
+
+        // Without bounce, the fling stops on the spot, at either bound.
+        let config = KineticConfig {
+            bounce: false,
+            ..KineticConfig::default()
+        };
+        for (start, velocity) in [(100.0, 2000.0), (0.0, -2000.0)] {
+            let mut scroll = KineticScroll::with_config(start, config);
+            scroll.set_bounds(Some(0.0..100.0));
+            scroll.fling(0, velocity);
+            scroll.step(16);
+            assert_eq!(scroll.position(), start);
+            assert!(!scroll.is_animating());
+        }
+    }
+
+    #[test]
+    fn drag_resists_overscroll_and_springs_back() {
+        let mut scroll = KineticScroll::new(10.0);
+        scroll.set_bounds(Some(0.0..100.0));
+        scroll.begin_drag(0);
+        // 10 units inside bounds, then 20 units resisted at 0.5.
+        assert_eq!(scroll.drag_by(10, -30.0), -10.0);
+        assert_eq!(scroll.overscroll(), -10.0);
+        assert_eq!(scroll.clamped_position(), 0.0);
+
+        // Hold still, then release: no fling, just the spring back.
+        scroll.end_drag(200);
+        assert_eq!(scroll.phase(), KineticPhase::Returning);
+        run_to_rest(&mut scroll, 200);
+        assert_eq!(scroll.position(), 0.0);
+    }
+
+    #[test]
+    fn drag_past_end_bound_resists_only_the_overscroll() {
+        let mut scroll = KineticScroll::new(90.0);
+        scroll.set_bounds(Some(0.0..100.0));
+        scroll.begin_drag(0);
+        // 10 units inside bounds, then 20 units resisted at 0.5.
+        assert_eq!(scroll.drag_by(10, 30.0), 110.0);
+        assert_eq!(scroll.overscroll(), 10.0);
+    }
+
+    #[test]
+    fn animate_to_eases_and_lands_on_clamped_target() {
+        let mut scroll = KineticScroll::new(0.0);
+        scroll.set_bounds(Some(0.0..100.0));
+        scroll.animate_to(1000, 250.0, 200);
+        assert_eq!(scroll.resting_position(), 100.0);
+
+        let halfway = scroll.step(1100);
+        // Ease-out: more than half the distance covered at half the time.
+        assert!(halfway > 50.0 && halfway < 100.0);
+        scroll.step(1200);
+        assert_eq!(scroll.position(), 100.0);
+        assert_eq!(scroll.phase(), KineticPhase::Idle);
+
+        // Starting a drag interrupts an animation.
+        scroll.animate_to(2000, 0.0, 200);
+        scroll.begin_drag(2050);
+        assert_eq!(scroll.phase(), KineticPhase::Dragging);
+        assert!(!scroll.is_animating());
+    }
+}
@@ -12,6 +12,13 @@
 //! - Coordinate conversion between world and view/device (pixel) space.
 //! - View fitting and centering/alignment helpers.
 //! - Simple zoom / pan constraints.
+//! - Axis ticks and labels for linear, logarithmic and calendar scales
+//!   ([`TickGenerator`]).
+//! - Scrollbar geometry and thumb/track interaction for viewports and
+//!   scroll regions such as virtual lists ([`Scrollbar`]).
+//! - Animated transitions between viewport states ([`ViewTransition`]).
+//! - Linked viewports that share visible ranges ([`ViewportLink`]) and
+//!   overview minimaps ([`Minimap`]).
 //!
 //! It does **not** own any scene graph or rendering backend. Callers are
 //! expected to:
@@ -63,13 +70,25 @@
 //!
 //! ## Design notes
 //!
-//! - Cameras are axis‑aligned with a **uniform** zoom factor.
-//! - Panning operates in view space; zooming is expressed as a scalar.
-//! - Rotation is intentionally left out of the initial design and can be
-//!   added later as a backwards‑compatible extension.
-//! - Controllers that interpret `ui-events` and more complex behaviors such
-//!   as inertia are expected to live in higher‑level crates built on top of
-//!   this one.
+//! - Cameras use a **uniform** zoom factor by default; [`Viewport2D`] can
+//!   opt into independent X/Y zoom via [`ZoomMode::Anisotropic`].
+//! - Panning operates in view space; zooming is expressed as a scalar (or a
+//!   per‑axis pair).
+//! - Axes are linear by default. A pluggable [`AxisTransform`] (such as
+//!   [`LogAxis`], [`SymlogAxis`] or the broken‑axis [`PiecewiseAxis`]) can be
+//!   supplied via [`Viewport1D::with_transform`] or per axis via
+//!   [`Viewport2D::with_axes`]; pan, zoom, fitting and clamping then operate
+//!   in axis coordinates.
+//! - [`Viewport2D`] supports rotation about a view anchor. Rotated views
+//!   report the bounding box of the visible region from
+//!   [`Viewport2D::visible_world_rect`] and its exact corners from
+//!   [`Viewport2D::visible_world_quad`].
+//! - Controllers that interpret `ui-events` are expected to live in
+//!   higher‑level crates built on top of this one. Inertia is provided as a
+//!   headless building block: [`KineticScroll`] is a time‑stepped scroll
+//!   physics model (velocity tracking, fling, edge bounce, eased scrolling)
+//!   driven by caller timestamps, which can feed [`Viewport1D::set_pan`],
+//!   [`Viewport2D::set_pan`], or any other 1D scroll offset.
 //!
 //! ## Culling example
 //!
@@ -102,10 +121,23 @@
 
 #![no_std]
 
+mod axis;
+mod kinetic;
+mod link;
 mod modes;
+mod scrollbar;
+mod ticks;
+mod transition;
+mod util;
 mod viewport1d;
 mod viewport2d;
 
-pub use modes::{ClampMode, FitMode};
+pub use axis::{AxisTransform, LinearAxis, LogAxis, PiecewiseAxis, SymlogAxis};
+pub use kinetic::{KineticConfig, KineticPhase, KineticScroll, VelocityTracker};
+pub use link::{LinkAxis, LinkMapping, LinkedViewport, Minimap, ViewportLink};
+pub use modes::{ClampMode, FitMode, ZoomMode};
+pub use scrollbar::{Scrollbar, ScrollbarPart};
+pub use ticks::{AxisTicks, Tick, TickGenerator, TickLabel, TickLevel, TickScale, Ticks};
+pub use transition::{TransitionPath, ViewTransition};
 pub use viewport1d::{Viewport1D, Viewport1DDebugInfo};
 pub use viewport2d::{Viewport2D, Viewport2DDebugInfo};
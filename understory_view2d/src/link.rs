@@ -0,0 +1,377 @@
+// Copyright 2025 the Understory Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Linked viewports and minimaps.
+
+use core::ops::Range;
+
+use kurbo::{Point, Rect, Vec2};
+
+use crate::{AxisTransform, Viewport1D, Viewport2D};
+
+/// An axis of a linked viewport.
+///
+/// [`Viewport1D`] has a single axis and treats both variants the same.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkAxis {
+    /// The world X axis.
+    X,
+    /// The world Y axis.
+    Y,
+}
+
+/// A viewport whose visible world range can be read and written per axis.
+///
+/// Implemented for [`Viewport1D`] and [`Viewport2D`] so both can join a
+/// [`ViewportLink`].
+pub trait LinkedViewport {
+    /// Returns the visible world range along `axis`.
+    fn linked_range(&self, axis: LinkAxis) -> Range<f64>;
+
+    /// Zooms and pans so that `range` is visible along `axis`.
+    fn set_linked_range(&mut self, axis: LinkAxis, range: Range<f64>);
+}
+
+impl<T: AxisTransform> LinkedViewport for Viewport1D<T> {
+    fn linked_range(&self, _axis: LinkAxis) -> Range<f64> {
+        self.visible_world_range()
+    }
+
+    fn set_linked_range(&mut self, _axis: LinkAxis, range: Range<f64>) {
+        self.set_visible_world_range(range);
+    }
+}
+
+impl<X: AxisTransform, Y: AxisTransform> LinkedViewport for Viewport2D<X, Y> {
+    fn linked_range(&self, axis: LinkAxis) -> Range<f64> {
+        match axis {
+            LinkAxis::X => self.visible_world_range_x(),
+            LinkAxis::Y => self.visible_world_range_y(),
+        }
+    }
+
+    fn set_linked_range(&mut self, axis: LinkAxis, range: Range<f64>) {
+        match axis {
+            LinkAxis::X => self.set_visible_world_range_x(range),
+            LinkAxis::Y => self.set_visible_world_range_y(range),
+        }
+    }
+}
+
+/// How a member's axes attach to the two channels of a [`ViewportLink`].
+///
+/// Each channel is either driven by one of the member's axes or not linked
+/// for that member. Mapping a member's Y axis onto the link's X channel lets,
+/// for example, a vertical ruler follow a horizontal timeline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinkMapping {
+    /// The member axis attached to the link's X channel, if any.
+    pub x: Option<LinkAxis>,
+    /// The member axis attached to the link's Y channel, if any.
+    pub y: Option<LinkAxis>,
+}
+
+impl LinkMapping {
+    /// Links only the X axis to the X channel.
+    pub const X: Self = Self::new(Some(LinkAxis::X), None);
+    /// Links only the Y axis to the Y channel.
+    pub const Y: Self = Self::new(None, Some(LinkAxis::Y));
+    /// Links both axes to their matching channels.
+    pub const XY: Self = Self::new(Some(LinkAxis::X), Some(LinkAxis::Y));
+
+    /// Creates a mapping from the member axes for the X and Y channels.
+    #[must_use]
+    pub const fn new(x: Option<LinkAxis>, y: Option<LinkAxis>) -> Self {
+        Self { x, y }
+    }
+}
+
+/// A link group that keeps the visible world ranges of several viewports in sync.
+///
+/// The link stores the shared range of up to two channels (X and Y) and does
+/// not own its members. Whenever one member changes (for example, after a
+/// pan or zoom gesture), publish it with [`ViewportLink::update_from`] and
+/// then [`ViewportLink::apply_to`] every other member. Applying never
+/// republishes, so there is no feedback loop between members.
+///
+/// Since ranges (rather than raw zoom factors) are shared, members with the
+/// same view extent end up with identical zoom and pan along linked axes,
+/// while members of different sizes still show the same world span.
+///
+/// ```rust
+/// use kurbo::Rect;
+/// use understory_view2d::{LinkMapping, Viewport1D, Viewport2D, ViewportLink};
+///
+/// let mut track = Viewport1D::new(0.0..800.0);
+/// let mut canvas = Viewport2D::new(Rect::new(0.0, 0.0, 800.0, 600.0));
+/// let mut link = ViewportLink::new();
+///
+/// // The user zooms the track; the canvas follows along X.
+/// track.zoom_about_view_point(0.0, 2.0);
+/// link.update_from(&track, LinkMapping::X);
+/// link.apply_to(&mut canvas, LinkMapping::X);
+///
+/// assert_eq!(canvas.visible_world_range_x(), track.visible_world_range());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ViewportLink {
+    x: Option<Range<f64>>,
+    y: Option<Range<f64>>,
+}
+
+impl ViewportLink {
+    /// Creates a link with no shared ranges yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the shared range of the X channel, if one has been published.
+    #[must_use]
+    pub fn x_range(&self) -> Option<Range<f64>> {
+        self.x.clone()
+    }
+
+    /// Returns the shared range of the Y channel, if one has been published.
+    #[must_use]
+    pub fn y_range(&self) -> Option<Range<f64>> {
+        self.y.clone()
+    }
+
+    /// Sets the shared range of the X channel directly.
+    pub fn set_x_range(&mut self, range: Option<Range<f64>>) {
+        self.x = range;
+    }
+
+    /// Sets the shared range of the Y channel directly.
+    pub fn set_y_range(&mut self, range: Option<Range<f64>>) {
+        self.y = range;
+    }
+
+    /// Publishes the visible ranges of `source` to the channels in `mapping`.
+    ///
+    /// Channels not attached by `mapping` keep their current range.
+    pub fn update_from<V: LinkedViewport + ?Sized>(&mut self, source: &V, mapping: LinkMapping) {
+        if let Some(axis) = mapping.x {
+            self.x = Some(source.linked_range(axis));
+        }
+        if let Some(axis) = mapping.y {
+            self.y = Some(source.linked_range(axis));
+        }
+    }
+
+    /// Applies the shared ranges to `target` along the axes in `mapping`.
+    ///
+    /// Returns `true` if any visible range of `target` changed. Channels that
+    /// have not been published yet are skipped.
+    pub fn apply_to<V: LinkedViewport + ?Sized>(
+        &self,
+        target: &mut V,
+        mapping: LinkMapping,
+    ) -> bool {
+        let mut changed = false;
+        for (axis, range) in [(mapping.x, &self.x), (mapping.y, &self.y)] {
+            if let (Some(axis), Some(range)) = (axis, range) {
+                let before = target.linked_range(axis);
+                if before != *range {
+                    target.set_linked_range(axis, range.clone());
+                    changed |= target.linked_range(axis) != before;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Publishes `source` and applies the result to each of `targets`.
+    ///
+    /// Each target carries its own mapping. Returns `true` if any target changed.
+    pub fn sync(
+        &mut self,
+        source: &dyn LinkedViewport,
+        source_mapping: LinkMapping,
+        targets: &mut [(&mut dyn LinkedViewport, LinkMapping)],
+    ) -> bool {
+        self.update_from(source, source_mapping);
+        let mut changed = false;
+        for (target, mapping) in targets.iter_mut() {
+            changed |= self.apply_to(*target, *mapping);
+        }
+        changed
+    }
+}
+
+/// An overview viewport that shows another viewport's visible region.
+///
+/// The minimap owns its own [`Viewport2D`] (usually fitted to the world
+/// bounds) and reads the region of a main viewport on demand. The main
+/// viewport can be driven back by dragging the indicator or clicking into the
+/// minimap.
+///
+/// Minimaps are linear-only: both the overview and the main viewport use the
+/// default [`LinearAxis`](crate::LinearAxis) on both axes, because the
+/// indicator and drags map world distances straight to view distances. For
+/// log or piecewise axes, link the ranges with a [`ViewportLink`] instead.
+#[derive(Clone, Debug)]
+pub struct Minimap {
+    view: Viewport2D,
+}
+
+impl Minimap {
+    /// Creates a minimap occupying `view_rect` that shows all of `world_bounds`.
+    #[must_use]
+    pub fn new(view_rect: Rect, world_bounds: Rect) -> Self {
+        let mut view = Viewport2D::new(view_rect);
+        view.set_world_bounds(Some(world_bounds));
+        view.fit_world();
+        Self { view }
+    }
+
+    /// Creates a minimap from an already configured overview viewport.
+    #[must_use]
+    pub fn from_viewport(view: Viewport2D) -> Self {
+        Self { view }
+    }
+
+    /// Returns the overview viewport.
+    #[must_use]
+    pub fn viewport(&self) -> &Viewport2D {
+        &self.view
+    }
+
+    /// Returns the overview viewport mutably, for example to resize or refit it.
+    pub fn viewport_mut(&mut self) -> &mut Viewport2D {
+        &mut self.view
+    }
+
+    /// Returns the corners of `main`'s visible region in minimap view coordinates.
+    ///
+    /// Unlike [`Minimap::indicator_rect`], this is exact for rotated views.
+    #[must_use]
+    pub fn indicator_quad(&self, main: &Viewport2D) -> [Point; 4] {
+        main.visible_world_quad()
+            .map(|p| self.view.world_to_view_point(p))
+    }
+
+    /// Returns the bounding box of `main`'s visible region in minimap view coordinates.
+    #[must_use]
+    pub fn indicator_rect(&self, main: &Viewport2D) -> Rect {
+        let [a, b, c, d] = self.indicator_quad(main);
+        Rect::from_points(a, b).union_pt(c).union_pt(d)
+    }
+
+    /// Returns `true` if `pt` (in minimap view coordinates) lies on the indicator.
+    #[must_use]
+    pub fn hit_indicator(&self, main: &Viewport2D, pt: Point) -> bool {
+        let world = self.view.view_to_world_point(pt);
+        let local = main.world_to_view_point(world);
+        main.view_rect().contains(local)
+    }
+
+    /// Moves `main` by a drag of `delta` on the minimap, in minimap view units.
+    ///
+    /// The indicator follows the pointer: `main`'s visible region moves by
+    /// the world distance `delta` covers on the minimap. Like the rest of
+    /// [`Minimap`], this assumes linear axes, where a world distance maps to
+    /// the same view distance anywhere in the viewport.
+    pub fn drag_indicator(&self, main: &mut Viewport2D, delta: Vec2) {
+        let to_world = self.view.view_to_world();
+        let world_delta = to_world * delta.to_point() - to_world * Point::ZERO;
+        let view_delta =
+            main.world_to_view() * world_delta.to_point() - main.world_to_view() * Point::ZERO;
+        main.pan_by_view(-view_delta);
+    }
+
+    /// Centers `main` on the world point under `pt` (in minimap view coordinates).
+    pub fn center_main_on(&self, main: &mut Viewport2D, pt: Point) {
+        main.center_on(self.view.view_to_world_point(pt));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_range_eq(a: Range<f64>, b: Range<f64>) {
+        assert!(
+            (a.start - b.start).abs() < 1e-9 && (a.end - b.end).abs() < 1e-9,
+            "{a:?} != {b:?}"
+        );
+    }
+
+    #[test]
+    fn tracks_and_canvas_share_x() {
+        let mut track_a = Viewport1D::new(0.0..800.0);
+        let mut track_b = Viewport1D::new(0.0..400.0);
+        let mut canvas = Viewport2D::new(Rect::new(0.0, 0.0, 800.0, 600.0));
+        let canvas_y = canvas.visible_world_range_y();
+        let mut link = ViewportLink::new();
+
+        track_a.zoom_about_view_point(200.0, 4.0);
+        track_a.pan_by_view(-50.0);
+        let changed = link.sync(
+            &track_a,
+            LinkMapping::X,
+            &mut [
+                (&mut track_b, LinkMapping::X),
+                (&mut canvas, LinkMapping::X),
+            ],
+        );
+        assert!(changed);
+
+        let expected = track_a.visible_world_range();
+        assert_range_eq(track_b.visible_world_range(), expected.clone());
+        assert_range_eq(canvas.visible_world_range_x(), expected);
+        // Same view width means same zoom and pan.
+        assert!((canvas.zoom() - track_a.zoom()).abs() < 1e-9);
+        // The canvas keeps its vertical center.
+        let y = canvas.visible_world_range_y();
+        let mid = (y.start + y.end) * 0.5;
+        assert!((mid - (canvas_y.start + canvas_y.end) * 0.5).abs() < 1e-9);
+
+        // Applying again is a no-op.
+        assert!(!link.apply_to(&mut canvas, LinkMapping::X));
+    }
+
+    #[test]
+    fn axis_mapping_links_y_to_x() {
+        let mut timeline = Viewport1D::new(0.0..600.0);
+        timeline.set_visible_world_range(10.0..70.0);
+        let mut canvas = Viewport2D::new(Rect::new(0.0, 0.0, 800.0, 600.0));
+        canvas.set_zoom_mode(crate::ZoomMode::Anisotropic);
+        let before_x = canvas.visible_world_range_x();
+
+        let mut link = ViewportLink::new();
+        link.update_from(&timeline, LinkMapping::X);
+        link.apply_to(&mut canvas, LinkMapping::new(Some(LinkAxis::Y), None));
+
+        assert_range_eq(canvas.visible_world_range_y(), 10.0..70.0);
+        assert_range_eq(canvas.visible_world_range_x(), before_x);
+        assert!(link.y_range().is_none());
+    }
+
+    #[test]
+    fn minimap_indicator_and_drive_back() {
+        let world = Rect::new(0.0, 0.0, 1000.0, 1000.0);
+        let minimap = Minimap::new(Rect::new(0.0, 0.0, 100.0, 100.0), world);
+        let mut main = Viewport2D::new(Rect::new(0.0, 0.0, 200.0, 200.0));
+        main.center_on(Point::new(500.0, 500.0));
+
+        let indicator = minimap.indicator_rect(&main);
+        assert!((indicator.width() - 20.0).abs() < 1e-9);
+        assert!((indicator.center().x - 50.0).abs() < 1e-9);
+        assert!(minimap.hit_indicator(&main, Point::new(50.0, 50.0)));
+        assert!(!minimap.hit_indicator(&main, Point::new(5.0, 5.0)));
+
+        // Dragging 10 minimap pixels moves the main view by 100 world units.
+        minimap.drag_indicator(&mut main, Vec2::new(10.0, 0.0));
+        let center = main.view_to_world_point(main.view_rect().center());
+        assert!((center.x - 600.0).abs() < 1e-9);
+        assert!((center.y - 500.0).abs() < 1e-9);
+
+        minimap.center_main_on(&mut main, Point::new(20.0, 30.0));
+        let center = main.view_to_world_point(main.view_rect().center());
+        assert!((center - Point::new(200.0, 300.0)).hypot() < 1e-9);
+        let indicator = minimap.indicator_rect(&main);
+        assert!((indicator.center() - Point::new(20.0, 30.0)).hypot() < 1e-9);
+    }
+}
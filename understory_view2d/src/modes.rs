@@ -37,3 +37,16 @@ pub enum FitMode {
     /// minimum with the start of the view span.
     AlignMin,
 }
+
+/// How a [`crate::Viewport2D`] zooms its two world axes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ZoomMode {
+    /// Both axes share a single zoom factor, preserving aspect ratio.
+    #[default]
+    Uniform,
+    /// The world X and Y axes have independent zoom factors.
+    ///
+    /// This suits charts where, for example, time and value axes are scaled
+    /// separately. Zoom limits apply to each axis.
+    Anisotropic,
+}
@@ -0,0 +1,418 @@
+// Copyright 2025 the Understory Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Headless scrollbar geometry.
+
+use core::ops::Range;
+
+use kurbo::{Point, Vec2};
+
+use crate::{AxisTransform, Viewport1D, Viewport2D};
+
+/// Default minimum thumb length in pixels.
+const DEFAULT_MIN_THUMB_LENGTH: f64 = 16.0;
+
+/// The part of a scrollbar track under a pointer; see [`Scrollbar::hit_test`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollbarPart {
+    /// The track before the thumb (clicking pages backwards).
+    TrackBefore,
+    /// The thumb.
+    Thumb,
+    /// The track after the thumb (clicking pages forwards).
+    TrackAfter,
+}
+
+/// Scrollbar geometry for one axis of a scrollable view.
+///
+/// A scrollbar relates a *content* range to the *visible* range of a view
+/// (both in content units, such as world or axis coordinates, or list
+/// offsets) and a track length in pixels. It reports where the thumb is and
+/// maps thumb drags and track clicks back to a *scroll position*: the start
+/// of the visible range in content units.
+///
+/// When the view is scrolled past the content (for example, while
+/// overscrolling or with loose clamping), the content range is extended to
+/// include the visible range, so the thumb shrinks instead of leaving the
+/// track.
+///
+/// For a [`Viewport1D`] or [`Viewport2D`], construct the scrollbar with
+/// [`Scrollbar::from_viewport1d`] and friends, and apply positions with
+/// [`Scrollbar::scroll_viewport1d`] and friends. For a virtual list, use
+/// [`Scrollbar::from_scroll_offset`] with the list's content extent,
+/// viewport extent and scroll offset, and pass positions to its
+/// `set_scroll_offset`.
+///
+/// ```rust
+/// use understory_view2d::{Scrollbar, ScrollbarPart};
+///
+/// // 1000 px of content in a 250 px viewport, scrolled to the top; 200 px track.
+/// let bar = Scrollbar::from_scroll_offset(1000.0, 250.0, 0.0, 200.0);
+/// assert_eq!(bar.thumb_range(), 0.0..50.0);
+///
+/// // Dragging the thumb by 75 px scrolls by 375 content units.
+/// assert_eq!(bar.position_for_thumb_start(75.0), 375.0);
+///
+/// // Clicking the track after the thumb pages down.
+/// let part = bar.hit_test(120.0);
+/// assert_eq!(part, ScrollbarPart::TrackAfter);
+/// assert_eq!(bar.page_position(part), 250.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scrollbar {
+    content: Range<f64>,
+    visible: Range<f64>,
+    track_length: f64,
+    min_thumb_length: f64,
+}
+
+impl Scrollbar {
+    /// Creates a scrollbar for `visible` within `content` on a track of `track_length` pixels.
+    ///
+    /// Reversed ranges are normalized, and `content` is extended to include `visible`.
+    #[must_use]
+    pub fn new(content: Range<f64>, visible: Range<f64>, track_length: f64) -> Self {
+        let visible = ordered(visible);
+        let content = ordered(content);
+        let content = content.start.min(visible.start)..content.end.max(visible.end);
+        Self {
+            content,
+            visible,
+            track_length: track_length.max(0.0),
+            min_thumb_length: DEFAULT_MIN_THUMB_LENGTH,
+        }
+    }
+
+    /// Creates a scrollbar from the extents and scroll offset of a scroll region starting at zero.
+    ///
+    /// This matches the scroll state of a virtual list.
+    #[must_use]
+    pub fn from_scroll_offset(
+        content_extent: f64,
+        viewport_extent: f64,
+        scroll_offset: f64,
+        track_length: f64,
+    ) -> Self {
+        Self::new(
+            0.0..content_extent,
+            scroll_offset..scroll_offset + viewport_extent,
+            track_length,
+        )
+    }
+
+    /// Creates a scrollbar for a [`Viewport1D`].
+    ///
+    /// The content is the viewport's world bounds (or just the visible range
+    /// if none are set). Ranges are measured in axis coordinates, so the
+    /// thumb moves evenly on non‑linear axes.
+    #[must_use]
+    pub fn from_viewport1d<T: AxisTransform>(view: &Viewport1D<T>, track_length: f64) -> Self {
+        let t = view.transform();
+        let visible = axis_range(t, view.visible_world_range());
+        let content = view
+            .world_bounds()
+            .map_or(visible.clone(), |b| axis_range(t, b));
+        Self::new(content, visible, track_length)
+    }
+
+    /// Creates a scrollbar for the X axis of a [`Viewport2D`].
+    ///
+    /// As for [`Scrollbar::from_viewport1d`], using the X extent of the world
+    /// bounds and of [`Viewport2D::visible_world_rect`].
+    #[must_use]
+    pub fn from_viewport2d_x<X: AxisTransform, Y: AxisTransform>(
+        view: &Viewport2D<X, Y>,
+        track_length: f64,
+    ) -> Self {
+        let t = view.x_axis();
+        let visible = axis_range(t, view.visible_world_range_x());
+        let content = view
+            .world_bounds()
+            .map_or(visible.clone(), |b| axis_range(t, b.x0..b.x1));
+        Self::new(content, visible, track_length)
+    }
+
+    /// Creates a scrollbar for the Y axis of a [`Viewport2D`].
+    ///
+    /// As for [`Scrollbar::from_viewport2d_x`], along the Y axis.
+    #[must_use]
+    pub fn from_viewport2d_y<X: AxisTransform, Y: AxisTransform>(
+        view: &Viewport2D<X, Y>,
+        track_length: f64,
+    ) -> Self {
+        let t = view.y_axis();
+        let visible = axis_range(t, view.visible_world_range_y());
+        let content = view
+            .world_bounds()
+            .map_or(visible.clone(), |b| axis_range(t, b.y0..b.y1));
+        Self::new(content, visible, track_length)
+    }
+
+    /// Sets the minimum thumb length in pixels (16 by default).
+    ///
+    /// The thumb never exceeds the track, even if this is larger.
+    #[must_use]
+    pub fn with_min_thumb_length(mut self, min_thumb_length: f64) -> Self {
+        self.min_thumb_length = min_thumb_length.max(0.0);
+        self
+    }
+
+    /// Returns the content range, including the visible range.
+    #[must_use]
+    pub fn content(&self) -> Range<f64> {
+        self.content.clone()
+    }
+
+    /// Returns the visible range.
+    #[must_use]
+    pub fn visible(&self) -> Range<f64> {
+        self.visible.clone()
+    }
+
+    /// Returns the track length in pixels.
+    #[must_use]
+    pub fn track_length(&self) -> f64 {
+        self.track_length
+    }
+
+    /// Returns the minimum thumb length in pixels.
+    #[must_use]
+    pub fn min_thumb_length(&self) -> f64 {
+        self.min_thumb_length
+    }
+
+    /// Returns `true` if the content is longer than the visible range.
+    #[must_use]
+    pub fn is_scrollable(&self) -> bool {
+        self.scroll_extent() > 0.0
+    }
+
+    /// Returns the current scroll position (the start of the visible range).
+    #[must_use]
+    pub fn position(&self) -> f64 {
+        self.visible.start
+    }
+
+    /// Returns the range of valid scroll positions.
+    #[must_use]
+    pub fn position_range(&self) -> Range<f64> {
+        self.content.start..self.content.start + self.scroll_extent()
+    }
+
+    /// Returns the thumb length in pixels.
+    #[must_use]
+    pub fn thumb_length(&self) -> f64 {
+        let content_len = self.content.end - self.content.start;
+        if content_len <= 0.0 {
+            return self.track_length;
+        }
+        let visible_len = self.visible.end - self.visible.start;
+        (self.track_length * visible_len / content_len)
+            .max(self.min_thumb_length)
+            .min(self.track_length)
+    }
+
+    /// Returns the thumb start in pixels from the start of the track.
+    #[must_use]
+    pub fn thumb_start(&self) -> f64 {
+        let scroll_extent = self.scroll_extent();
+        if scroll_extent <= 0.0 {
+            return 0.0;
+        }
+        let t = (self.visible.start - self.content.start) / scroll_extent;
+        t.clamp(0.0, 1.0) * self.thumb_travel()
+    }
+
+    /// Returns the thumb extent in pixels from the start of the track.
+    #[must_use]
+    pub fn thumb_range(&self) -> Range<f64> {
+        let start = self.thumb_start();
+        start..start + self.thumb_length()
+    }
+
+    /// Returns the scroll position that places the thumb at `thumb_start` pixels.
+    ///
+    /// To drag the thumb, remember [`Scrollbar::thumb_start`] when the drag
+    /// begins and call this with that value plus the pointer's travel.
+    #[must_use]
+    pub fn position_for_thumb_start(&self, thumb_start: f64) -> f64 {
+        let travel = self.thumb_travel();
+        if travel <= 0.0 {
+            return self
+                .visible
+                .start
+                .clamp(self.content.start, self.position_range().end);
+        }
+        let t = (thumb_start / travel).clamp(0.0, 1.0);
+        self.content.start + t * self.scroll_extent()
+    }
+
+    /// Returns which part of the track is at `track_pos` pixels.
+    #[must_use]
+    pub fn hit_test(&self, track_pos: f64) -> ScrollbarPart {
+        let thumb = self.thumb_range();
+        if track_pos < thumb.start {
+            ScrollbarPart::TrackBefore
+        } else if track_pos > thumb.end {
+            ScrollbarPart::TrackAfter
+        } else {
+            ScrollbarPart::Thumb
+        }
+    }
+
+    /// Returns the scroll position after clicking `part` of the track.
+    ///
+    /// Track clicks move by one visible range (page up/down), clamped to
+    /// [`Scrollbar::position_range`]; the thumb itself does not scroll.
+    #[must_use]
+    pub fn page_position(&self, part: ScrollbarPart) -> f64 {
+        let page = self.visible.end - self.visible.start;
+        let target = match part {
+            ScrollbarPart::TrackBefore => self.visible.start - page,
+            ScrollbarPart::Thumb => return self.visible.start,
+            ScrollbarPart::TrackAfter => self.visible.start + page,
+        };
+        let range = self.position_range();
+        target.clamp(range.start, range.end)
+    }
+
+    /// Pans a [`Viewport1D`] so that its visible range starts at `position`.
+    ///
+    /// `position` is in the axis coordinates used by [`Scrollbar::from_viewport1d`], for
+    /// example the result of [`Scrollbar::position_for_thumb_start`].
+    pub fn scroll_viewport1d<T: AxisTransform>(view: &mut Viewport1D<T>, position: f64) {
+        let current = view
+            .transform()
+            .world_to_axis(view.visible_world_range().start);
+        view.pan_by_view((current - position) * view.zoom());
+    }
+
+    /// Pans a [`Viewport2D`] horizontally so that its visible X range starts at `position`.
+    pub fn scroll_viewport2d_x<X: AxisTransform, Y: AxisTransform>(
+        view: &mut Viewport2D<X, Y>,
+        position: f64,
+    ) {
+        let current = view
+            .x_axis()
+            .world_to_axis(view.visible_world_range_x().start);
+        pan_by_axis_delta(view, Vec2::new(position - current, 0.0));
+    }
+
+    /// Pans a [`Viewport2D`] vertically so that its visible Y range starts at `position`.
+    pub fn scroll_viewport2d_y<X: AxisTransform, Y: AxisTransform>(
+        view: &mut Viewport2D<X, Y>,
+        position: f64,
+    ) {
+        let current = view
+            .y_axis()
+            .world_to_axis(view.visible_world_range_y().start);
+        pan_by_axis_delta(view, Vec2::new(0.0, position - current));
+    }
+
+    fn scroll_extent(&self) -> f64 {
+        let content_len = self.content.end - self.content.start;
+        let visible_len = self.visible.end - self.visible.start;
+        (content_len - visible_len).max(0.0)
+    }
+
+    fn thumb_travel(&self) -> f64 {
+        (self.track_length - self.thumb_length()).max(0.0)
+    }
+}
+
+fn ordered(range: Range<f64>) -> Range<f64> {
+    if range.start <= range.end {
+        range
+    } else {
+        range.end..range.start
+    }
+}
+
+fn axis_range(t: &impl AxisTransform, range: Range<f64>) -> Range<f64> {
+    t.world_to_axis(range.start)..t.world_to_axis(range.end)
+}
+
+/// Moves the visible region of `view` by `delta` in axis coordinates.
+fn pan_by_axis_delta<X: AxisTransform, Y: AxisTransform>(view: &mut Viewport2D<X, Y>, delta: Vec2) {
+    let to_view = view.world_to_view();
+    let view_delta = to_view * delta.to_point() - to_view * Point::ZERO;
+    view.pan_by_view(-view_delta);
+}
+
+#[cfg(test)]
+mod tests {
+    use kurbo::Rect;
+
+    use super::*;
+    use crate::ClampMode;
+
+    #[test]
+    fn thumb_geometry_and_min_length() {
+        let bar = Scrollbar::from_scroll_offset(1000.0, 250.0, 375.0, 200.0);
+        assert!(bar.is_scrollable());
+        assert_eq!(bar.thumb_length(), 50.0);
+        assert_eq!(bar.thumb_start(), 75.0);
+        assert_eq!(bar.position_range(), 0.0..750.0);
+
+        // Huge content: the thumb is held at the minimum and still reaches the end.
+        let bar = Scrollbar::from_scroll_offset(1.0e6, 100.0, 1.0e6 - 100.0, 200.0)
+            .with_min_thumb_length(20.0);
+        assert_eq!(bar.thumb_length(), 20.0);
+        assert_eq!(bar.thumb_range().end, 200.0);
+        assert_eq!(bar.position_for_thumb_start(180.0), 1.0e6 - 100.0);
+        assert_eq!(bar.position_for_thumb_start(500.0), 1.0e6 - 100.0);
+
+        // Content shorter than the view fills the track.
+        let bar = Scrollbar::from_scroll_offset(50.0, 100.0, 0.0, 200.0);
+        assert!(!bar.is_scrollable());
+        assert_eq!(bar.thumb_range(), 0.0..200.0);
+        assert_eq!(bar.page_position(ScrollbarPart::TrackAfter), 0.0);
+    }
+
+    #[test]
+    fn overscroll_shrinks_thumb_instead_of_leaving_track() {
+        // Visible range extends 100 units past the end of the content.
+        let bar = Scrollbar::new(0.0..1000.0, 850.0..1100.0, 200.0);
+        assert_eq!(bar.content(), 0.0..1100.0);
+        let thumb = bar.thumb_range();
+        assert!(thumb.end <= 200.0 + 1e-9);
+        assert!((thumb.end - 200.0).abs() < 1e-9);
+        assert!(bar.thumb_length() < 50.0);
+    }
+
+    #[test]
+    fn paging_clamps_to_content() {
+        let bar = Scrollbar::from_scroll_offset(1000.0, 250.0, 100.0, 200.0);
+        assert_eq!(bar.hit_test(5.0), ScrollbarPart::TrackBefore);
+        assert_eq!(bar.hit_test(30.0), ScrollbarPart::Thumb);
+        assert_eq!(bar.page_position(ScrollbarPart::TrackBefore), 0.0);
+        assert_eq!(bar.page_position(ScrollbarPart::TrackAfter), 350.0);
+        assert_eq!(bar.page_position(ScrollbarPart::Thumb), 100.0);
+    }
+
+    #[test]
+    fn viewport_roundtrip() {
+        let mut view = Viewport1D::new(0.0..100.0);
+        view.set_world_bounds(Some(0.0..1000.0));
+        view.set_clamp_mode(ClampMode::None);
+        let bar = Scrollbar::from_viewport1d(&view, 100.0);
+        assert_eq!(bar.thumb_range(), 0.0..16.0);
+        let position = bar.position_for_thumb_start(42.0);
+        Scrollbar::scroll_viewport1d(&mut view, position);
+        let bar = Scrollbar::from_viewport1d(&view, 100.0);
+        assert!((bar.thumb_start() - 42.0).abs() < 1e-9);
+
+        let mut view = Viewport2D::new(Rect::new(0.0, 0.0, 200.0, 100.0));
+        view.set_world_bounds(Some(Rect::new(0.0, 0.0, 800.0, 1000.0)));
+        let y = Scrollbar::from_viewport2d_y(&view, 100.0);
+        assert_eq!(y.thumb_range(), 0.0..16.0, "held at the minimum length");
+        let target = y.page_position(ScrollbarPart::TrackAfter);
+        Scrollbar::scroll_viewport2d_y(&mut view, target);
+        assert!((view.visible_world_range_y().start - 100.0).abs() < 1e-9);
+        let x = Scrollbar::from_viewport2d_x(&view, 200.0);
+        assert_eq!(x.thumb_range(), 0.0..50.0);
+        Scrollbar::scroll_viewport2d_x(&mut view, 600.0);
+        assert!((view.visible_world_range_x().start - 600.0).abs() < 1e-9);
+        assert!((view.visible_world_range_y().start - 100.0).abs() < 1e-9);
+    }
+}
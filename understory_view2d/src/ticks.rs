@@ -0,0 +1,1016 @@
+// Copyright 2025 the Understory Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Axis tick generation and labelling.
+
+use core::f64::consts::LN_10;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+#[cfg(not(feature = "std"))]
+use kurbo::common::FloatFuncs as _;
+
+use crate::{AxisTransform, Viewport1D, Viewport2D};
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 3_600;
+const DAY: i64 = 86_400;
+
+/// Largest timestamp magnitude (in seconds) handled by [`TickScale::Time`].
+///
+/// About 31 million years either side of the epoch.
+const MAX_TIME_SECONDS: f64 = 1.0e15;
+
+/// How tick values are distributed along an axis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TickScale {
+    /// Evenly spaced ticks on a 1‑2‑5 ladder.
+    #[default]
+    Linear,
+    /// Ticks at powers of ten, with minor ticks at 2…9 (or 2 and 5) times
+    /// each power when there is room.
+    ///
+    /// Values are data values, not their logarithms; the non‑positive part of
+    /// a range is ignored.
+    Log10,
+    /// Calendar ticks for values in seconds since the Unix epoch (UTC).
+    ///
+    /// Steps range from fractions of a second through minutes, hours, days
+    /// and months to (multiples of) years. Month and year ticks fall on
+    /// calendar boundaries.
+    Time,
+}
+
+/// Whether a [`Tick`] is a major or a minor tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TickLevel {
+    /// A major tick, usually drawn longer and labelled.
+    Major,
+    /// A minor tick between major ticks.
+    Minor,
+}
+
+/// A single tick produced by [`AxisTicks::iter`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tick {
+    /// Position of the tick in axis (world) units.
+    pub value: f64,
+    /// Major or minor.
+    pub level: TickLevel,
+}
+
+impl Tick {
+    /// Returns `true` for major ticks.
+    #[must_use]
+    pub fn is_major(&self) -> bool {
+        self.level == TickLevel::Major
+    }
+}
+
+/// Chooses tick positions for an axis from its visible range and pixel length.
+///
+/// Major ticks are spaced at least [`major_spacing`](Self::major_spacing)
+/// pixels apart; minor ticks are only generated when they are at least
+/// [`min_minor_spacing`](Self::min_minor_spacing) pixels apart.
+///
+/// ```rust
+/// use understory_view2d::{TickGenerator, TickScale, Viewport1D};
+///
+/// let mut view = Viewport1D::new(0.0..800.0);
+/// view.set_world_bounds(Some(0.0..100.0));
+/// view.fit_world();
+///
+/// let ticks = TickGenerator::new(TickScale::Linear).for_viewport1d(&view);
+/// for tick in ticks.iter().filter(|t| t.is_major()) {
+///     let x = view.world_to_view_x(tick.value);
+///     let label = ticks.label(tick.value);
+///     // Draw a tick at `x` labelled with `label` (which implements `Display`).
+///     # let _ = (x, label);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TickGenerator {
+    /// How values are distributed along the axis.
+    pub scale: TickScale,
+    /// Minimum distance between major ticks, in pixels.
+    pub major_spacing: f64,
+    /// Minimum distance between minor ticks, in pixels.
+    pub min_minor_spacing: f64,
+}
+
+impl Default for TickGenerator {
+    fn default() -> Self {
+        Self::new(TickScale::Linear)
+    }
+}
+
+impl TickGenerator {
+    /// Creates a generator for `scale` with default spacings (80px major, 8px minor).
+    #[must_use]
+    pub fn new(scale: TickScale) -> Self {
+        Self {
+            scale,
+            major_spacing: 80.0,
+            min_minor_spacing: 8.0,
+        }
+    }
+
+    /// Computes ticks for `range` (in axis units) displayed over `length_px` pixels.
+    ///
+    /// A reversed range is treated like its forward counterpart. Empty or
+    /// non‑finite inputs produce no ticks.
+    #[must_use]
+    pub fn generate(&self, range: Range<f64>, length_px: f64) -> AxisTicks {
+        let (start, end) = if range.start <= range.end {
+            (range.start, range.end)
+        } else {
+            (range.end, range.start)
+        };
+        let length_px = length_px.abs();
+        let empty = AxisTicks {
+            scale: self.scale,
+            start,
+            end,
+            plan: Plan::Empty,
+            format: LabelFormat::Fixed(0),
+        };
+        let major_px = self.major_spacing.max(1.0);
+        let minor_px = self.min_minor_spacing.max(1.0);
+        if !(start.is_finite() && end.is_finite() && end > start && length_px > 0.0) {
+            return empty;
+        }
+        match self.scale {
+            TickScale::Linear => {
+                let (plan, major) = linear_plan(end - start, length_px, major_px, minor_px);
+                AxisTicks {
+                    plan,
+                    format: linear_format(major),
+                    ..empty
+                }
+            }
+            TickScale::Log10 => {
+                let start = start.max(f64::MIN_POSITIVE);
+                if end <= start {
+                    return empty;
+                }
+                AxisTicks {
+                    start,
+                    plan: log_plan(start, end, length_px, major_px, minor_px),
+                    format: LabelFormat::Log,
+                    ..empty
+                }
+            }
+            TickScale::Time => {
+                let start = start.clamp(-MAX_TIME_SECONDS, MAX_TIME_SECONDS);
+                let end = end.clamp(-MAX_TIME_SECONDS, MAX_TIME_SECONDS);
+                if end <= start {
+                    return empty;
+                }
+                let (plan, format) = time_plan(end - start, length_px, major_px, minor_px);
+                AxisTicks {
+                    start,
+                    end,
+                    plan,
+                    format,
+                    ..empty
+                }
+            }
+        }
+    }
+
+    /// Computes ticks for the visible range of a [`Viewport1D`].
+    ///
+    /// Pair a [`LogAxis`](crate::LogAxis) viewport with [`TickScale::Log10`].
+    #[must_use]
+    pub fn for_viewport1d<T: AxisTransform>(&self, view: &Viewport1D<T>) -> AxisTicks {
+        let span = view.view_span();
+        self.generate(view.visible_world_range(), span.end - span.start)
+    }
+
+    /// Computes ticks for the visible X range of an unrotated [`Viewport2D`].
+    #[must_use]
+    pub fn for_viewport2d_x<X: AxisTransform, Y: AxisTransform>(
+        &self,
+        view: &Viewport2D<X, Y>,
+    ) -> AxisTicks {
+        self.generate(view.visible_world_range_x(), view.view_rect().width())
+    }
+
+    /// Computes ticks for the visible Y range of an unrotated [`Viewport2D`].
+    #[must_use]
+    pub fn for_viewport2d_y<X: AxisTransform, Y: AxisTransform>(
+        &self,
+        view: &Viewport2D<X, Y>,
+    ) -> AxisTicks {
+        self.generate(view.visible_world_range_y(), view.view_rect().height())
+    }
+}
+
+/// The ticks of one axis, as computed by [`TickGenerator`].
+///
+/// This is a small `Copy` value; iterating it with [`AxisTicks::iter`] and
+/// formatting labels with [`AxisTicks::label`] never allocates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AxisTicks {
+    scale: TickScale,
+    start: f64,
+    end: f64,
+    plan: Plan,
+    format: LabelFormat,
+}
+
+impl AxisTicks {
+    /// Returns the scale the ticks were generated for.
+    #[must_use]
+    pub fn scale(&self) -> TickScale {
+        self.scale
+    }
+
+    /// Returns the range covered by the ticks.
+    #[must_use]
+    pub fn range(&self) -> Range<f64> {
+        self.start..self.end
+    }
+
+    /// Returns an iterator over all ticks in the range, in increasing order.
+    #[must_use]
+    pub fn iter(&self) -> Ticks {
+        let state = match self.plan {
+            Plan::Empty => TicksState::Done,
+            Plan::Linear { step, divisions } => TicksState::Linear {
+                next: to_i64((self.start / step - 1e-9).ceil()),
+                last: to_i64((self.end / step + 1e-9).floor()),
+                step,
+                divisions,
+            },
+            Plan::Log {
+                exp_step,
+                major_every,
+                mults,
+            } => TicksState::Log {
+                exp: decade_floor(self.start).div_euclid(exp_step) * exp_step,
+                mult: 0,
+                exp_step,
+                major_every,
+                mults,
+                start: self.start * (1.0 - 1e-12),
+                end: self.end * (1.0 + 1e-12),
+            },
+            Plan::Calendar { major, minor } => TicksState::Calendar {
+                next: minor.align_up(to_i64(self.start.ceil())),
+                last: to_i64(self.end.floor()),
+                major,
+                minor,
+            },
+        };
+        Ticks { state }
+    }
+
+    /// Returns a label for `value`, formatted with a precision suited to the tick spacing.
+    ///
+    /// The label implements [`Display`](fmt::Display), so it can be written
+    /// into any buffer without intermediate allocation.
+    #[must_use]
+    pub fn label(&self, value: f64) -> TickLabel {
+        TickLabel {
+            value,
+            format: self.format,
+        }
+    }
+}
+
+impl IntoIterator for &AxisTicks {
+    type Item = Tick;
+    type IntoIter = Ticks;
+
+    fn into_iter(self) -> Ticks {
+        self.iter()
+    }
+}
+
+/// Iterator over the ticks of an [`AxisTicks`].
+#[derive(Clone, Debug)]
+pub struct Ticks {
+    state: TicksState,
+}
+
+#[derive(Clone, Debug)]
+enum TicksState {
+    Done,
+    Linear {
+        next: i64,
+        last: i64,
+        step: f64,
+        divisions: i64,
+    },
+    Log {
+        exp: i32,
+        mult: usize,
+        exp_step: i32,
+        major_every: i32,
+        mults: &'static [f64],
+        start: f64,
+        end: f64,
+    },
+    Calendar {
+        next: i64,
+        last: i64,
+        major: TimeStep,
+        minor: TimeStep,
+    },
+}
+
+impl Iterator for Ticks {
+    type Item = Tick;
+
+    fn next(&mut self) -> Option<Tick> {
+        match &mut self.state {
+            TicksState::Done => None,
+            TicksState::Linear {
+                next,
+                last,
+                step,
+                divisions,
+            } => {
+                if *next > *last {
+                    self.state = TicksState::Done;
+                    return None;
+                }
+                let i = *next;
+                *next += 1;
+                let value = i as f64 * *step;
+                Some(Tick {
+                    value,
+                    level: level(i.rem_euclid(*divisions) == 0),
+                })
+            }
+            TicksState::Log {
+                exp,
+                mult,
+                exp_step,
+                major_every,
+                mults,
+                start,
+                end,
+            } => loop {
+                if *exp > f64::MAX_10_EXP {
+                    self.state = TicksState::Done;
+                    return None;
+                }
+                let m = *mult;
+                let e = *exp;
+                let value = mults[m] * pow10(e);
+                *mult += 1;
+                if *mult == mults.len() {
+                    *mult = 0;
+                    *exp += *exp_step;
+                }
+                if value > *end {
+                    self.state = TicksState::Done;
+                    return None;
+                }
+                if value >= *start {
+                    return Some(Tick {
+                        value,
+                        level: level(m == 0 && e.rem_euclid(*major_every) == 0),
+                    });
+                }
+            },
+            TicksState::Calendar {
+                next,
+                last,
+                major,
+                minor,
+            } => {
+                if *next > *last {
+                    self.state = TicksState::Done;
+                    return None;
+                }
+                let t = *next;
+                *next = minor.advance(t);
+                let value = t as f64;
+                Some(Tick {
+                    value,
+                    level: level(major.align_up(t) == t),
+                })
+            }
+        }
+    }
+}
+
+impl FusedIterator for Ticks {}
+
+/// A formatted tick label; see [`AxisTicks::label`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TickLabel {
+    value: f64,
+    format: LabelFormat,
+}
+
+impl fmt::Display for TickLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let v = self.value;
+        match self.format {
+            LabelFormat::Fixed(decimals) => write!(f, "{v:.decimals$}"),
+            LabelFormat::Scientific(step_exp) => {
+                if v == 0.0 {
+                    return f.write_str("0");
+                }
+                let decimals = usize::try_from(decade_floor(v.abs()) - step_exp).unwrap_or(0);
+                write!(f, "{v:.decimals$e}")
+            }
+            LabelFormat::Log => {
+                if v <= 0.0 {
+                    return write!(f, "{v}");
+                }
+                let e = decade_floor(v);
+                if (-4..=5).contains(&e) {
+                    let decimals = usize::try_from(-e).unwrap_or(0);
+                    write!(f, "{v:.decimals$}")
+                } else {
+                    let m = to_i64((v / pow10(e)).round());
+                    if m == 1 {
+                        write!(f, "1e{e}")
+                    } else {
+                        write!(f, "{m}e{e}")
+                    }
+                }
+            }
+            LabelFormat::Time(precision) => write_time(f, v, precision),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Plan {
+    Empty,
+    /// Ticks at integer multiples of `step`; every `divisions`th one is major.
+    Linear {
+        step: f64,
+        divisions: i64,
+    },
+    /// Ticks at `mults[i] * 10^exp` for every `exp_step`th exponent; majors
+    /// at multiplier 1 for every `major_every`th exponent.
+    Log {
+        exp_step: i32,
+        major_every: i32,
+        mults: &'static [f64],
+    },
+    /// Ticks on calendar boundaries of `minor`; those also on `major` boundaries are major.
+    Calendar {
+        major: TimeStep,
+        minor: TimeStep,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum LabelFormat {
+    /// Fixed point with the given number of decimals.
+    Fixed(usize),
+    /// Scientific notation, with mantissa digits down to the given decade.
+    Scientific(i32),
+    /// Powers of ten.
+    Log,
+    /// Calendar date/time at the given precision.
+    Time(TimePrecision),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TimePrecision {
+    Year,
+    Month,
+    Day,
+    Minute,
+    Second,
+    /// Seconds with this many decimals.
+    Fraction(usize),
+}
+
+fn level(major: bool) -> TickLevel {
+    if major {
+        TickLevel::Major
+    } else {
+        TickLevel::Minor
+    }
+}
+
+/// Picks a 1‑2‑5 major step and its minor subdivision; returns the plan and the major step.
+fn linear_plan(len: f64, length_px: f64, major_px: f64, minor_px: f64) -> (Plan, f64) {
+    let px_per_unit = length_px / len;
+    let desired = major_px / px_per_unit;
+    let unit = pow10(decade_floor(desired));
+    let ladder: [(f64, &[i64]); 4] = [(1.0, &[5, 2]), (2.0, &[4, 2]), (5.0, &[5]), (10.0, &[5, 2])];
+    let (mantissa, subdivisions) = ladder
+        .into_iter()
+        .find(|(m, _)| m * unit >= desired)
+        .unwrap_or(ladder[3]);
+    let major = mantissa * unit;
+    let major_step_px = major * px_per_unit;
+    let divisions = subdivisions
+        .iter()
+        .copied()
+        .find(|&d| major_step_px / f64::from(to_i32_saturating(d)) >= minor_px)
+        .unwrap_or(1);
+    let step = major / f64::from(to_i32_saturating(divisions));
+    (Plan::Linear { step, divisions }, major)
+}
+
+fn linear_format(major: f64) -> LabelFormat {
+    let exp = decade_floor(major);
+    if !(-6..7).contains(&exp) {
+        LabelFormat::Scientific(exp)
+    } else {
+        LabelFormat::Fixed(usize::try_from(-exp).unwrap_or(0))
+    }
+}
+
+const LOG_ALL: &[f64] = &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+const LOG_125: &[f64] = &[1.0, 2.0, 5.0];
+const LOG_1: &[f64] = &[1.0];
+
+fn log_plan(start: f64, end: f64, length_px: f64, major_px: f64, minor_px: f64) -> Plan {
+    let decades = (end / start).ln() / LN_10;
+    let px_per_decade = length_px / decades;
+    let mut major_every = 1;
+    let mut mantissa = 0;
+    while f64::from(major_every) * px_per_decade < major_px && major_every < 1_000 {
+        major_every = [2, 5, 10][mantissa] * major_every / [1, 2, 5][mantissa];
+        mantissa = (mantissa + 1) % 3;
+    }
+    if major_every > 1 {
+        let exp_step = if px_per_decade >= minor_px {
+            1
+        } else {
+            major_every
+        };
+        return Plan::Log {
+            exp_step,
+            major_every,
+            mults: LOG_1,
+        };
+    }
+    // Narrowest gaps: 9 → 10 for all multipliers, 1 → 2 (and 5 → 10) for 1, 2, 5.
+    let mults = if px_per_decade * (1.0 - 9.0_f64.ln() / LN_10) >= minor_px {
+        LOG_ALL
+    } else if px_per_decade * (2.0_f64.ln() / LN_10) >= minor_px {
+        LOG_125
+    } else {
+        LOG_1
+    };
+    Plan::Log {
+        exp_step: 1,
+        major_every: 1,
+        mults,
+    }
+}
+
+fn time_plan(len: f64, length_px: f64, major_px: f64, minor_px: f64) -> (Plan, LabelFormat) {
+    let px_per_second = length_px / len;
+    let desired = major_px / px_per_second;
+    if desired < 1.0 {
+        let (plan, major) = linear_plan(len, length_px, major_px, minor_px);
+        let decimals = usize::try_from(-decade_floor(major)).unwrap_or(0);
+        let precision = if decimals == 0 {
+            TimePrecision::Second
+        } else {
+            TimePrecision::Fraction(decimals)
+        };
+        return (plan, LabelFormat::Time(precision));
+    }
+    let (major, minor) = TIME_STEPS
+        .iter()
+        .copied()
+        .find(|(major, _)| major.approx_seconds() >= desired)
+        .unwrap_or_else(|| year_steps(desired));
+    let minor = minor
+        .filter(|m| m.approx_seconds() * px_per_second >= minor_px)
+        .unwrap_or(major);
+    let precision = match major.unit {
+        TimeUnit::Second => TimePrecision::Second,
+        TimeUnit::Minute | TimeUnit::Hour => TimePrecision::Minute,
+        TimeUnit::Day => TimePrecision::Day,
+        TimeUnit::Month => TimePrecision::Month,
+        TimeUnit::Year => TimePrecision::Year,
+    };
+    (
+        Plan::Calendar { major, minor },
+        LabelFormat::Time(precision),
+    )
+}
+
+/// Multi-year steps beyond the table: 10, 20, 50, 100, … years.
+fn year_steps(desired_seconds: f64) -> (TimeStep, Option<TimeStep>) {
+    let years = desired_seconds / TimeStep::new(TimeUnit::Year, 1).approx_seconds();
+    let mut count = 10;
+    let mut mantissa = 0;
+    while (count as f64) < years && count < 1_000_000_000 {
+        count = [2, 5, 10][mantissa] * count / [1, 2, 5][mantissa];
+        mantissa = (mantissa + 1) % 3;
+    }
+    // 2·10ⁿ years split in quarters, 10ⁿ and 5·10ⁿ years in fifths.
+    let divisions = if mantissa == 1 { 4 } else { 5 };
+    (
+        TimeStep::new(TimeUnit::Year, count),
+        Some(TimeStep::new(TimeUnit::Year, count / divisions)),
+    )
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+/// A calendar step: `count` units, aligned within the enclosing unit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct TimeStep {
+    unit: TimeUnit,
+    count: i64,
+}
+
+/// Major steps and their preferred minor steps, in increasing order.
+///
+/// Second, minute, hour and month counts divide the enclosing unit, so
+/// aligning to the step within the epoch and within the enclosing unit agree.
+const TIME_STEPS: &[(TimeStep, Option<TimeStep>)] = {
+    use TimeUnit::{Day, Hour, Minute, Month, Second, Year};
+    const fn s(unit: TimeUnit, count: i64) -> TimeStep {
+        TimeStep::new(unit, count)
+    }
+    &[
+        (s(Second, 1), None),
+        (s(Second, 2), Some(s(Second, 1))),
+        (s(Second, 5), Some(s(Second, 1))),
+        (s(Second, 10), Some(s(Second, 2))),
+        (s(Second, 15), Some(s(Second, 5))),
+        (s(Second, 30), Some(s(Second, 5))),
+        (s(Minute, 1), Some(s(Second, 10))),
+        (s(Minute, 2), Some(s(Second, 30))),
+        (s(Minute, 5), Some(s(Minute, 1))),
+        (s(Minute, 10), Some(s(Minute, 2))),
+        (s(Minute, 15), Some(s(Minute, 5))),
+        (s(Minute, 30), Some(s(Minute, 5))),
+        (s(Hour, 1), Some(s(Minute, 10))),
+        (s(Hour, 2), Some(s(Minute, 30))),
+        (s(Hour, 3), Some(s(Hour, 1))),
+        (s(Hour, 6), Some(s(Hour, 1))),
+        (s(Hour, 12), Some(s(Hour, 2))),
+        (s(Day, 1), Some(s(Hour, 6))),
+        (s(Day, 2), Some(s(Hour, 12))),
+        (s(Day, 7), Some(s(Day, 1))),
+        (s(Month, 1), Some(s(Day, 1))),
+        (s(Month, 3), Some(s(Month, 1))),
+        (s(Month, 6), Some(s(Month, 1))),
+        (s(Year, 1), Some(s(Month, 1))),
+        (s(Year, 2), Some(s(Year, 1))),
+        (s(Year, 5), Some(s(Year, 1))),
+    ]
+};
+
+impl TimeStep {
+    const fn new(unit: TimeUnit, count: i64) -> Self {
+        Self { unit, count }
+    }
+
+    fn approx_seconds(self) -> f64 {
+        let unit = match self.unit {
+            TimeUnit::Second => 1.0,
+            TimeUnit::Minute => 60.0,
+            TimeUnit::Hour => 3_600.0,
+            TimeUnit::Day => 86_400.0,
+            // Mean Gregorian month and year.
+            TimeUnit::Month => 2_629_746.0,
+            TimeUnit::Year => 31_556_952.0,
+        };
+        let count = self.count as f64;
+        unit * count
+    }
+
+    /// Returns the first boundary of this step at or after `t`.
+    fn align_up(self, t: i64) -> i64 {
+        let k = self.count;
+        let fixed = match self.unit {
+            TimeUnit::Second => Some(1),
+            TimeUnit::Minute => Some(MINUTE),
+            TimeUnit::Hour => Some(HOUR),
+            _ => None,
+        };
+        if let Some(unit) = fixed {
+            let s = unit * k;
+            let r = t.rem_euclid(s);
+            return if r == 0 { t } else { t - r + s };
+        }
+        let exact_day = t.rem_euclid(DAY) == 0;
+        let (y, m, d) = civil_from_days(t.div_euclid(DAY));
+        match self.unit {
+            TimeUnit::Day => {
+                let (y, m, d) = if exact_day {
+                    (y, m, d)
+                } else {
+                    next_day(y, m, d)
+                };
+                let r = (d - 1) % k;
+                if r == 0 {
+                    return days_from_civil(y, m, d) * DAY;
+                }
+                let d = d - r + k;
+                if d > days_in_month(y, m) {
+                    month_start(y * 12 + m)
+                } else {
+                    days_from_civil(y, m, d) * DAY
+                }
+            }
+            TimeUnit::Month => {
+                let mut index = y * 12 + m - 1;
+                if !(exact_day && d == 1) {
+                    index += 1;
+                }
+                month_start(round_up(index, k))
+            }
+            _ => {
+                let y = if exact_day && m == 1 && d == 1 {
+                    y
+                } else {
+                    y + 1
+                };
+                days_from_civil(round_up(y, k), 1, 1) * DAY
+            }
+        }
+    }
+
+    /// Returns the boundary after `t`, which must itself be a boundary of this step.
+    fn advance(self, t: i64) -> i64 {
+        let k = self.count;
+        let (y, m, d) = civil_from_days(t.div_euclid(DAY));
+        match self.unit {
+            TimeUnit::Second => t + k,
+            TimeUnit::Minute => t + k * MINUTE,
+            TimeUnit::Hour => t + k * HOUR,
+            TimeUnit::Day => {
+                if d + k > days_in_month(y, m) {
+                    month_start(y * 12 + m)
+                } else {
+                    days_from_civil(y, m, d + k) * DAY
+                }
+            }
+            TimeUnit::Month => month_start(y * 12 + m - 1 + k),
+            TimeUnit::Year => days_from_civil(y + k, 1, 1) * DAY,
+        }
+    }
+}
+
+fn round_up(value: i64, k: i64) -> i64 {
+    let r = value.rem_euclid(k);
+    if r == 0 { value } else { value - r + k }
+}
+
+/// Timestamp of the first day of the month with zero-based index `y * 12 + (m - 1)`.
+fn month_start(index: i64) -> i64 {
+    days_from_civil(index.div_euclid(12), index.rem_euclid(12) + 1, 1) * DAY
+}
+
+fn next_day(y: i64, m: i64, d: i64) -> (i64, i64, i64) {
+    civil_from_days(days_from_civil(y, m, d) + 1)
+}
+
+fn days_in_month(y: i64, m: i64) -> i64 {
+    let (ny, nm) = if m == 12 { (y + 1, 1) } else { (y, m + 1) };
+    days_from_civil(ny, nm, 1) - days_from_civil(y, m, 1)
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm).
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Proleptic Gregorian date for days since 1970-01-01; inverse of [`days_from_civil`].
+fn civil_from_days(z: i64) -> (i64, i64, i64) {
+    let z = z + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400;
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+fn write_time(f: &mut fmt::Formatter<'_>, value: f64, precision: TimePrecision) -> fmt::Result {
+    let decimals = match precision {
+        TimePrecision::Fraction(decimals) => decimals,
+        _ => 0,
+    };
+    let scale = 10_i64.pow(u32::try_from(decimals).unwrap_or(0));
+    let ticks = to_i64((value * scale as f64).round());
+    let secs = ticks.div_euclid(scale);
+    let frac = ticks.rem_euclid(scale);
+    let (y, m, d) = civil_from_days(secs.div_euclid(DAY));
+    let sod = secs.rem_euclid(DAY);
+    let (hh, mm, ss) = (sod / HOUR, sod % HOUR / MINUTE, sod % MINUTE);
+    let midnight = sod == 0 && frac == 0;
+    match precision {
+        TimePrecision::Year => write!(f, "{y}"),
+        TimePrecision::Month if m == 1 => write!(f, "{y}"),
+        TimePrecision::Month => write!(f, "{y}-{m:02}"),
+        TimePrecision::Day => write!(f, "{y}-{m:02}-{d:02}"),
+        _ if midnight => write!(f, "{y}-{m:02}-{d:02}"),
+        TimePrecision::Minute => write!(f, "{hh:02}:{mm:02}"),
+        TimePrecision::Second => write!(f, "{hh:02}:{mm:02}:{ss:02}"),
+        TimePrecision::Fraction(_) => {
+            write!(f, "{hh:02}:{mm:02}:{ss:02}.{frac:0decimals$}")
+        }
+    }
+}
+
+fn pow10(exp: i32) -> f64 {
+    10.0_f64.powi(exp)
+}
+
+/// Returns `floor(log10(x))` for positive finite `x`, exact at powers of ten.
+fn decade_floor(x: f64) -> i32 {
+    let mut exp = to_i32((x.ln() / LN_10).floor());
+    if pow10(exp) > x {
+        exp -= 1;
+    } else if pow10(exp + 1) <= x {
+        exp += 1;
+    }
+    exp
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    reason = "Float to int casts saturate; callers only pass values in range."
+)]
+fn to_i64(x: f64) -> i64 {
+    x as i64
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    reason = "Float to int casts saturate; callers only pass values in range."
+)]
+fn to_i32(x: f64) -> i32 {
+    x as i32
+}
+
+fn to_i32_saturating(x: i64) -> i32 {
+    i32::try_from(x).unwrap_or(i32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    use super::*;
+
+    fn collect(ticks: &AxisTicks) -> (Vec<f64>, Vec<f64>) {
+        let (major, minor): (Vec<Tick>, Vec<Tick>) = ticks.iter().partition(Tick::is_major);
+        (
+            major.iter().map(|t| t.value).collect(),
+            minor.iter().map(|t| t.value).collect(),
+        )
+    }
+
+    /// 2024-03-15 00:00:00 UTC.
+    const MAR_15_2024: f64 = 1_710_460_800.0;
+
+    #[test]
+    fn linear_major_and_minor() {
+        let ticks = TickGenerator::new(TickScale::Linear).generate(0.0..100.0, 500.0);
+        let (major, minor) = collect(&ticks);
+        assert_eq!(major, [0.0, 20.0, 40.0, 60.0, 80.0, 100.0]);
+        assert_eq!(minor.len(), 15, "four minor ticks per major step");
+        assert_eq!(ticks.label(40.0).to_string(), "40");
+
+        let ticks = TickGenerator::new(TickScale::Linear).generate(0.0..1.0, 800.0);
+        let labels: Vec<_> = ticks
+            .iter()
+            .filter(Tick::is_major)
+            .map(|t| ticks.label(t.value).to_string())
+            .collect();
+        assert_eq!(labels[3], "0.3");
+        assert_eq!(labels.len(), 11);
+
+        let ticks = TickGenerator::new(TickScale::Linear).generate(0.0..1.0e9, 800.0);
+        assert_eq!(ticks.label(3.0e8).to_string(), "3e8");
+        assert_eq!(ticks.label(0.0).to_string(), "0");
+    }
+
+    #[test]
+    fn log_decades() {
+        let ticks = TickGenerator::new(TickScale::Log10).generate(1.0..1.0e6, 600.0);
+        let (major, minor) = collect(&ticks);
+        assert_eq!(major, [1.0, 10.0, 100.0, 1.0e3, 1.0e4, 1.0e5, 1.0e6]);
+        assert_eq!(minor.len(), 12, "2 and 5 in each of six decades");
+        assert_eq!(ticks.label(100.0).to_string(), "100");
+        assert_eq!(ticks.label(1.0e6).to_string(), "1e6");
+        assert_eq!(ticks.label(5.0e7).to_string(), "5e7");
+        assert_eq!(ticks.label(0.01).to_string(), "0.01");
+
+        // Many decades: majors every few decades.
+        let ticks = TickGenerator::new(TickScale::Log10).generate(1.0e-20..1.0e20, 400.0);
+        let (major, _) = collect(&ticks);
+        assert!(major.len() <= 6, "{major:?}");
+    }
+
+    #[test]
+    fn time_hours_in_a_day() {
+        let ticks = TickGenerator::new(TickScale::Time)
+            .generate(MAR_15_2024..MAR_15_2024 + 86_400.0, 960.0);
+        let (major, minor) = collect(&ticks);
+        assert_eq!(major.len(), 13);
+        assert_eq!(major[1] - major[0], 7_200.0);
+        assert_eq!(minor.len(), 36, "half-hour minor ticks");
+        assert_eq!(ticks.label(major[0]).to_string(), "2024-03-15");
+        assert_eq!(ticks.label(major[1]).to_string(), "02:00");
+        assert_eq!(ticks.label(major[12]).to_string(), "2024-03-16");
+    }
+
+    #[test]
+    fn time_months_fall_on_calendar_boundaries() {
+        let jan_1_2024 = 1_704_067_200.0;
+        let jan_1_2025 = 1_735_689_600.0;
+        let ticks = TickGenerator::new(TickScale::Time).generate(jan_1_2024..jan_1_2025, 1200.0);
+        let labels: Vec<_> = ticks
+            .iter()
+            .map(|t| ticks.label(t.value).to_string())
+            .collect();
+        assert_eq!(labels.len(), 13);
+        assert_eq!(labels[0], "2024");
+        assert_eq!(labels[1], "2024-02");
+        assert_eq!(labels[2], "2024-03");
+        assert_eq!(labels[12], "2025");
+        assert!(ticks.iter().all(|t| t.is_major()));
+    }
+
+    #[test]
+    fn time_weeks_restart_each_month() {
+        let step = TimeStep::new(TimeUnit::Day, 7);
+        let jan_29 = days_from_civil(2024, 1, 29) * DAY;
+        assert_eq!(step.align_up(jan_29 - 1), jan_29);
+        assert_eq!(step.advance(jan_29), days_from_civil(2024, 2, 1) * DAY);
+        assert_eq!(
+            step.align_up(days_from_civil(2024, 2, 2) * DAY),
+            days_from_civil(2024, 2, 8) * DAY
+        );
+        for z in [-800_000, -1, 0, 1, 19_797, 2_000_000] {
+            let (y, m, d) = civil_from_days(z);
+            assert_eq!(days_from_civil(y, m, d), z);
+        }
+    }
+
+    #[test]
+    fn time_sub_second_and_years() {
+        let ticks =
+            TickGenerator::new(TickScale::Time).generate(MAR_15_2024..MAR_15_2024 + 1.0, 800.0);
+        let (major, _) = collect(&ticks);
+        assert_eq!(major.len(), 11);
+        assert_eq!(ticks.label(major[3]).to_string(), "00:00:00.3");
+
+        let ticks = TickGenerator::new(TickScale::Time).generate(0.0..2.5e10, 800.0);
+        let (major, minor) = collect(&ticks);
+        assert_eq!(ticks.label(major[0]).to_string(), "2000");
+        assert_eq!(ticks.label(major[1]).to_string(), "2100");
+        assert_eq!(ticks.label(minor[0]).to_string(), "1980");
+    }
+
+    #[test]
+    fn viewport_helpers() {
+        let mut view = Viewport1D::new(0.0..800.0);
+        view.set_world_bounds(Some(0.0..120.0));
+        view.fit_world();
+        let ticks = TickGenerator::default().for_viewport1d(&view);
+        let (major, _) = collect(&ticks);
+        assert_eq!(major.first(), Some(&0.0));
+        assert_eq!(major.last(), Some(&120.0));
+
+        let view = Viewport2D::new(kurbo::Rect::new(0.0, 0.0, 800.0, 600.0));
+        let x = TickGenerator::default().for_viewport2d_x(&view);
+        let y = TickGenerator::default().for_viewport2d_y(&view);
+        assert_eq!(x.range(), 0.0..800.0);
+        assert_eq!(y.range(), 0.0..600.0);
+        assert!(
+            TickGenerator::default()
+                .generate(5.0..5.0, 100.0)
+                .iter()
+                .next()
+                .is_none()
+        );
+    }
+}
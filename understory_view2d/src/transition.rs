@@ -0,0 +1,416 @@
+// Copyright 2025 the Understory Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Animated transitions between two [`Viewport2D`] states.
+
+use core::f64::consts::{PI, TAU};
+
+#[cfg(not(feature = "std"))]
+use kurbo::common::FloatFuncs as _;
+use kurbo::{Point, Vec2};
+
+use crate::Viewport2D;
+use crate::util::exp;
+
+/// Zoom ratio (in either direction) from which [`ViewTransition::new`] picks
+/// [`TransitionPath::SmoothZoom`].
+const SMOOTH_ZOOM_RATIO: f64 = 4.0;
+
+/// Curvature parameter `ρ` of the smooth zoom path; `√2` is the value
+/// recommended by van Wijk and Nuij.
+const SMOOTH_ZOOM_RHO: f64 = core::f64::consts::SQRT_2;
+
+/// How a [`ViewTransition`] moves between its endpoints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransitionPath {
+    /// Interpolate the view center linearly and the zoom geometrically.
+    ///
+    /// Suited to small pans and zoom changes.
+    Linear,
+    /// Follow the van Wijk–Nuij "smooth and efficient zooming and panning"
+    /// path, which zooms out while travelling and back in on arrival.
+    ///
+    /// Suited to large zoom changes and long jumps, where linear motion would
+    /// either blur past the content or crawl at high zoom.
+    SmoothZoom,
+}
+
+/// An animated transition between two [`Viewport2D`] states.
+///
+/// A transition captures the world point at the view center, the zoom, and
+/// the rotation of a start and an end viewport, then [`ViewTransition::apply`]
+/// writes the interpolated state for a caller-provided timestamp back into a
+/// viewport. Each step goes through [`Viewport2D::set_rotation`],
+/// [`Viewport2D::set_zoom_xy`], and [`Viewport2D::center_on`], so zoom limits
+/// and the viewport's [`ClampMode`](crate::ClampMode) are respected at every
+/// frame, not just at the endpoints.
+///
+/// The easiest way to build a transition is [`ViewTransition::animate`], which
+/// runs any of the instant viewport operations (such as
+/// [`Viewport2D::fit_rect`] or [`Viewport2D::center_on`]) on a copy of the
+/// viewport to find the target:
+///
+/// ```rust
+/// use kurbo::Rect;
+/// use understory_view2d::{ViewTransition, Viewport2D};
+///
+/// let mut view = Viewport2D::new(Rect::new(0.0, 0.0, 800.0, 600.0));
+/// let target = Rect::new(1000.0, 1000.0, 1080.0, 1060.0);
+///
+/// let transition = ViewTransition::animate(&view, 0, 500, |v| v.fit_rect(target));
+///
+/// // Drive it from the host's frame clock.
+/// let mut now = 0;
+/// while transition.apply(&mut view, now) {
+///     now += 16;
+/// }
+/// assert!((view.visible_world_rect().center() - target.center()).hypot() < 1e-6);
+/// ```
+///
+/// Timestamps are caller-provided milliseconds, so transitions are fully
+/// deterministic and work headless.
+///
+/// Transitions are linear-only: they take viewports with the default
+/// [`LinearAxis`](crate::LinearAxis) on both axes, and interpolate centers and
+/// zoom in world space, which would not move evenly on log or piecewise axes.
+#[derive(Clone, Copy, Debug)]
+pub struct ViewTransition {
+    from_center: Point,
+    from_zoom: f64,
+    to_center: Point,
+    to_zoom: f64,
+    /// Ratio of Y zoom to X zoom at either end, for anisotropic viewports.
+    from_aspect: f64,
+    to_aspect: f64,
+    from_rotation: f64,
+    to_rotation: f64,
+    start_ms: u64,
+    duration_ms: u64,
+    path: TransitionPath,
+    /// View width in view units, used to express the smooth zoom path in world units.
+    view_width: f64,
+}
+
+impl ViewTransition {
+    /// Creates a transition from the state of `from` to the state of `to`.
+    ///
+    /// The path is [`TransitionPath::SmoothZoom`] when the zoom changes by a
+    /// factor of four or more, and [`TransitionPath::Linear`] otherwise; use
+    /// [`ViewTransition::with_path`] to override it.
+    #[must_use]
+    pub fn new(from: &Viewport2D, to: &Viewport2D, start_ms: u64, duration_ms: u64) -> Self {
+        let (from_center, from_zoom) = (view_center(from), from.zoom());
+        let (to_center, to_zoom) = (view_center(to), to.zoom());
+        let ratio = (to_zoom / from_zoom).max(from_zoom / to_zoom);
+        let path = if ratio >= SMOOTH_ZOOM_RATIO {
+            TransitionPath::SmoothZoom
+        } else {
+            TransitionPath::Linear
+        };
+        Self {
+            from_center,
+            from_zoom,
+            to_center,
+            to_zoom,
+            from_aspect: aspect(from),
+            to_aspect: aspect(to),
+            from_rotation: from.rotation(),
+            to_rotation: to.rotation(),
+            start_ms,
+            duration_ms,
+            path,
+            view_width: from.view_rect().width().max(f64::MIN_POSITIVE),
+        }
+    }
+
+    /// Creates a transition from `from` to the state produced by running `op` on a copy of it.
+    #[must_use]
+    pub fn animate(
+        from: &Viewport2D,
+        start_ms: u64,
+        duration_ms: u64,
+        op: impl FnOnce(&mut Viewport2D),
+    ) -> Self {
+        let mut to = from.clone();
+        op(&mut to);
+        Self::new(from, &to, start_ms, duration_ms)
+    }
+
+    /// Returns this transition with the given path.
+    #[must_use]
+    pub fn with_path(mut self, path: TransitionPath) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Returns the path this transition follows.
+    #[must_use]
+    pub fn path(&self) -> TransitionPath {
+        self.path
+    }
+
+    /// Returns the world point the view is centered on at the end of the transition.
+    #[must_use]
+    pub fn target_center(&self) -> Point {
+        self.to_center
+    }
+
+    /// Returns the zoom at the end of the transition.
+    #[must_use]
+    pub fn target_zoom(&self) -> f64 {
+        self.to_zoom
+    }
+
+    /// Returns the linear progress in `[0, 1]` at `time_ms`.
+    #[must_use]
+    pub fn progress(&self, time_ms: u64) -> f64 {
+        if self.duration_ms == 0 {
+            return 1.0;
+        }
+        let elapsed = time_ms.saturating_sub(self.start_ms) as f64;
+        (elapsed / self.duration_ms as f64).min(1.0)
+    }
+
+    /// Returns `true` once `time_ms` is at or past the end of the transition.
+    #[must_use]
+    pub fn is_finished(&self, time_ms: u64) -> bool {
+        self.progress(time_ms) >= 1.0
+    }
+
+    /// Returns the interpolated view center (in world space) and zoom at `time_ms`.
+    ///
+    /// Progress is eased in and out. The returned zoom has not yet been
+    /// clamped to any viewport's limits.
+    #[must_use]
+    pub fn sample(&self, time_ms: u64) -> (Point, f64) {
+        let t = self.progress(time_ms);
+        if t >= 1.0 {
+            return (self.to_center, self.to_zoom);
+        }
+        // Smoothstep ease-in-out.
+        let t = t * t * (3.0 - 2.0 * t);
+        match self.path {
+            TransitionPath::Linear => self.sample_linear(t),
+            TransitionPath::SmoothZoom => self.sample_smooth(t),
+        }
+    }
+
+    /// Returns the interpolated rotation (in radians) and Y/X zoom ratio at `time_ms`.
+    ///
+    /// Rotation takes the shorter way around; the zoom ratio is interpolated
+    /// geometrically. Both use the same easing as [`ViewTransition::sample`].
+    #[must_use]
+    pub fn sample_rotation_and_aspect(&self, time_ms: u64) -> (f64, f64) {
+        let t = self.progress(time_ms);
+        if t >= 1.0 {
+            return (self.to_rotation, self.to_aspect);
+        }
+        let t = t * t * (3.0 - 2.0 * t);
+        let turn = (self.to_rotation - self.from_rotation + PI).rem_euclid(TAU) - PI;
+        let rotation = self.from_rotation + turn * t;
+        let aspect = self.from_aspect * (self.to_aspect / self.from_aspect).powf(t);
+        (rotation, aspect)
+    }
+
+    /// Writes the interpolated state at `time_ms` into `viewport`.
+    ///
+    /// Returns `true` while the transition is still running, so hosts can
+    /// keep requesting frames until it returns `false`.
+    pub fn apply(&self, viewport: &mut Viewport2D, time_ms: u64) -> bool {
+        let (center, zoom) = self.sample(time_ms);
+        let (rotation, aspect) = self.sample_rotation_and_aspect(time_ms);
+        viewport.set_rotation(rotation);
+        viewport.set_zoom_xy(Vec2::new(zoom, zoom * aspect));
+        viewport.center_on(center);
+        !self.is_finished(time_ms)
+    }
+
+    fn sample_linear(&self, t: f64) -> (Point, f64) {
+        let center = self.from_center.lerp(self.to_center, t);
+        let zoom = self.from_zoom * (self.to_zoom / self.from_zoom).powf(t);
+        (center, zoom)
+    }
+
+    fn sample_smooth(&self, t: f64) -> (Point, f64) {
+        // Van Wijk & Nuij, "Smooth and efficient zooming and panning" (2003).
+        // `w` is the visible width in world units and `u` the distance travelled.
+        let rho = SMOOTH_ZOOM_RHO;
+        let rho2 = rho * rho;
+        let w0 = self.view_width / self.from_zoom;
+        let w1 = self.view_width / self.to_zoom;
+        let delta = self.to_center - self.from_center;
+        let u1 = delta.hypot();
+
+        if u1 < 1e-9 * w0.max(w1) {
+            // Pure zoom: w changes exponentially along the path.
+            let s = (w1 / w0).ln() * t;
+            let zoom = self.view_width / (w0 * exp(s));
+            return (self.from_center, zoom);
+        }
+
+        let b = |w: f64, sign: f64| {
+            (w1 * w1 - w0 * w0 + sign * rho2 * rho2 * u1 * u1) / (2.0 * w * rho2 * u1)
+        };
+        let r = |b: f64| (-b + (b * b + 1.0).sqrt()).ln();
+        let r0 = r(b(w0, 1.0));
+        let r1 = r(b(w1, -1.0));
+        let total = (r1 - r0) / rho;
+
+        let s = total * t;
+        let u = w0 / rho2 * (cosh(r0) * tanh(rho * s + r0) - sinh(r0));
+        let w = w0 * cosh(r0) / cosh(rho * s + r0);
+        let center = self.from_center + delta * (u / u1);
+        (center, self.view_width / w)
+    }
+}
+
+fn view_center(viewport: &Viewport2D) -> Point {
+    viewport.view_to_world_point(viewport.view_rect().center())
+}
+
+fn aspect(viewport: &Viewport2D) -> f64 {
+    let zoom = viewport.zoom_xy();
+    zoom.y / zoom.x
+}
+
+fn cosh(x: f64) -> f64 {
+    (exp(x) + exp(-x)) * 0.5
+}
+
+fn sinh(x: f64) -> f64 {
+    (exp(x) - exp(-x)) * 0.5
+}
+
+fn tanh(x: f64) -> f64 {
+    let e2 = exp(2.0 * x);
+    if e2.is_infinite() {
+        1.0
+    } else {
+        (e2 - 1.0) / (e2 + 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kurbo::{Point, Rect};
+
+    use super::{TransitionPath, ViewTransition, view_center};
+    use crate::{ClampMode, Viewport2D};
+
+    fn viewport() -> Viewport2D {
+        Viewport2D::new(Rect::new(0.0, 0.0, 400.0, 300.0))
+    }
+
+    #[test]
+    fn linear_transition_interpolates_and_lands_on_target() {
+        let mut vp = viewport();
+        let transition = ViewTransition::animate(&vp, 100, 200, |v| {
+            v.set_zoom(2.0);
+            v.center_on(Point::new(50.0, 50.0));
+        });
+        assert_eq!(transition.path(), TransitionPath::Linear);
+
+        // Before the start, the viewport stays at the start state.
+        let (center, zoom) = transition.sample(0);
+        assert_eq!(center, Point::new(200.0, 150.0));
+        assert_eq!(zoom, 1.0);
+
+        // Halfway: smoothstep(0.5) = 0.5, so the center is halfway and the zoom is the geometric mean.
+        let (center, zoom) = transition.sample(200);
+        assert!((center - Point::new(125.0, 100.0)).hypot() < 1e-9);
+        assert!((zoom - 2.0_f64.sqrt()).abs() < 1e-9);
+
+        assert!(transition.apply(&mut vp, 200));
+        assert!(!transition.apply(&mut vp, 300));
+        assert!((view_center(&vp) - Point::new(50.0, 50.0)).hypot() < 1e-9);
+        assert_eq!(vp.zoom(), 2.0);
+    }
+
+    #[test]
+    fn large_zoom_change_uses_smooth_zoom_path() {
+        let vp = viewport();
+        let transition = ViewTransition::animate(&vp, 0, 1000, |v| {
+            v.set_zoom(64.0);
+            v.center_on(Point::new(5000.0, 0.0));
+        });
+        assert_eq!(transition.path(), TransitionPath::SmoothZoom);
+
+        // Endpoints match the captured states.
+        let (c0, z0) = transition.sample(0);
+        assert!((c0 - Point::new(200.0, 150.0)).hypot() < 1e-6);
+        assert!((z0 - 1.0).abs() < 1e-9);
+        let (c1, z1) = transition.sample(1000);
+        assert_eq!((c1, z1), (Point::new(5000.0, 0.0), 64.0));
+
+        // The path zooms out below both endpoints while travelling and moves monotonically.
+        let mut min_zoom = f64::INFINITY;
+        let mut last_x = f64::NEG_INFINITY;
+        for t in (0..=1000).step_by(50) {
+            let (c, z) = transition.sample(t);
+            min_zoom = min_zoom.min(z);
+            assert!(c.x >= last_x - 1e-9, "center must move towards the target");
+            last_x = c.x;
+        }
+        assert!(min_zoom < 1.0);
+    }
+
+    #[test]
+    fn pure_zoom_smooth_path_stays_centered() {
+        let vp = viewport();
+        let anchor = vp.view_rect().center();
+        let transition =
+            ViewTransition::animate(&vp, 0, 100, |v| v.zoom_about_view_point(anchor, 16.0))
+                .with_path(TransitionPath::SmoothZoom);
+        let (center, zoom) = transition.sample(50);
+        assert!((center - Point::new(200.0, 150.0)).hypot() < 1e-9);
+        assert!((zoom - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn every_step_respects_zoom_limits_and_clamping() {
+        let mut vp = viewport();
+        vp.set_world_bounds(Some(Rect::new(0.0, 0.0, 400.0, 300.0)));
+        vp.set_clamp_mode(ClampMode::KeepSomeVisible);
+
+        // Target lies far outside the world bounds; intermediate centers are
+        // pulled back so some of the world stays visible.
+        let transition = ViewTransition::animate(&vp, 0, 100, |v| {
+            v.set_clamp_mode(ClampMode::None);
+            v.center_on(Point::new(10_000.0, 10_000.0));
+        });
+        vp.set_zoom_limits(0.5, 1.5);
+        for t in (0..=100).step_by(10) {
+            transition.apply(&mut vp, t);
+            assert!(vp.zoom() >= 0.5 && vp.zoom() <= 1.5);
+            let visible = vp.visible_world_rect();
+            assert!(visible.min_x() <= 400.0 && visible.min_y() <= 300.0);
+        }
+    }
+
+    #[test]
+    fn rotation_takes_the_short_way_around() {
+        let mut vp = viewport();
+        vp.set_rotation(3.0);
+        let anchor = vp.view_rect().center();
+        let transition =
+            ViewTransition::animate(&vp, 0, 100, |v| v.rotate_about_view_point(anchor, 0.5));
+        // 3.0 + 0.5 wraps past π; the midpoint is still between the two angles.
+        let (rotation, aspect) = transition.sample_rotation_and_aspect(50);
+        assert!((rotation - 3.25).abs() < 1e-9);
+        assert_eq!(aspect, 1.0);
+
+        let center_before = view_center(&vp);
+        transition.apply(&mut vp, 100);
+        assert!((vp.rotation() - (3.5 - core::f64::consts::TAU)).abs() < 1e-9);
+        assert!((view_center(&vp) - center_before).hypot() < 1e-9);
+    }
+
+    #[test]
+    fn zero_duration_jumps_to_target() {
+        let mut vp = viewport();
+        let transition = ViewTransition::animate(&vp, 10, 0, |v| v.set_zoom(3.0));
+        assert!(transition.is_finished(0));
+        assert!(!transition.apply(&mut vp, 0));
+        assert_eq!(vp.zoom(), 3.0);
+    }
+}
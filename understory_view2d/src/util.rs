@@ -0,0 +1,12 @@
+// Copyright 2025 the Understory Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use core::f64::consts::E;
+
+#[cfg(not(feature = "std"))]
+use kurbo::common::FloatFuncs as _;
+
+/// `e^x`, usable in `no_std` builds through Kurbo's float functions.
+pub(crate) fn exp(x: f64) -> f64 {
+    E.powf(x)
+}
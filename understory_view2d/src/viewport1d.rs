@@ -5,6 +5,7 @@ use core::ops::Range;
 
 use kurbo::Point;
 
+use crate::axis::{AxisTransform, LinearAxis};
 use crate::modes::{ClampMode, FitMode};
 
 /// 1D viewport over a world‑space axis.
@@ -13,9 +14,15 @@ use crate::modes::{ClampMode, FitMode};
 /// transform along a single world axis. It is conventionally used for the
 /// horizontal (X) axis in time/timeline views or scroll regions where only the
 /// X dimension is zoomed.
+///
+/// World coordinates pass through an [`AxisTransform`] before pan and zoom
+/// are applied, so the axis can be logarithmic or otherwise non‑linear. The
+/// default, [`LinearAxis`], maps world coordinates unchanged; zoom and pan
+/// are then in world units, and for other transforms in axis units.
 #[derive(Clone, Debug)]
-pub struct Viewport1D {
+pub struct Viewport1D<T = LinearAxis> {
     view_span: Range<f64>,
+    transform: T,
     world_bounds: Option<Range<f64>>,
     zoom: f64,
     pan: f64,
@@ -26,7 +33,7 @@ pub struct Viewport1D {
 }
 
 impl Viewport1D {
-    /// Creates a new 1D viewport over the given view span.
+    /// Creates a new linear 1D viewport over the given view span.
     ///
     /// - `view_span` is expressed in view/device units (typically pixels).
     /// - Initial zoom is `1.0`.
@@ -34,8 +41,20 @@ impl Viewport1D {
     /// - Zoom is clamped to the range `[1e-6, 1e6]` by default.
     #[must_use]
     pub fn new(view_span: Range<f64>) -> Self {
+        Self::with_transform(view_span, LinearAxis)
+    }
+}
+
+impl<T: AxisTransform> Viewport1D<T> {
+    /// Creates a new 1D viewport over the given view span with an axis transform.
+    ///
+    /// Defaults are as for [`Viewport1D::new`], with the axis origin (rather
+    /// than the world origin) mapping to `view_span.start`.
+    #[must_use]
+    pub fn with_transform(view_span: Range<f64>, transform: T) -> Self {
         Self {
             view_span,
+            transform,
             world_bounds: None,
             zoom: 1.0,
             pan: 0.0,
@@ -46,6 +65,20 @@ impl Viewport1D {
         }
     }
 
+    /// Returns the axis transform.
+    #[must_use]
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    /// Replaces the axis transform, keeping zoom and pan in axis units.
+    ///
+    /// Clamping is applied afterwards if world bounds are set.
+    pub fn set_transform(&mut self, transform: T) {
+        self.transform = transform;
+        self.clamp_to_bounds();
+    }
+
     /// Returns the current view span in device coordinates.
     #[must_use]
     pub fn view_span(&self) -> Range<f64> {
@@ -135,6 +168,48 @@ impl Viewport1D {
         self.fit_mode
     }
 
+    /// Returns the current pan offset in view/device coordinates.
+    ///
+    /// The world origin maps to `view_span.start + pan`.
+    #[must_use]
+    pub fn pan(&self) -> f64 {
+        self.pan
+    }
+
+    /// Sets the pan offset in view/device coordinates.
+    ///
+    /// Clamping relative to world bounds is applied afterwards if configured.
+    pub fn set_pan(&mut self, pan: f64) {
+        if self.pan == pan {
+            return;
+        }
+        self.pan = pan;
+        self.clamp_to_bounds();
+    }
+
+    /// Returns the range of pan offsets that keep the visible range inside the world bounds.
+    ///
+    /// When the world bounds are shorter than the view span at the current
+    /// zoom, the range collapses to the single pan offset that positions them
+    /// according to the [`FitMode`]. Returns `None` if no (non‑empty) world
+    /// bounds are set.
+    #[must_use]
+    pub fn pan_range(&self) -> Option<Range<f64>> {
+        let bounds = self.axis_bounds()?;
+        let v_len = self.view_span.end - self.view_span.start;
+        // Visible start is `-pan / zoom` and visible end is `(v_len - pan) / zoom`.
+        let max = -bounds.start * self.zoom;
+        let min = v_len - bounds.end * self.zoom;
+        if min <= max {
+            return Some(min..max);
+        }
+        let pan = match self.fit_mode {
+            FitMode::Center => (min + max) * 0.5,
+            FitMode::AlignMin => max,
+        };
+        Some(pan..pan)
+    }
+
     /// Pans the view by a delta in view/device space.
     ///
     /// This adjusts the pan offset and then applies clamping relative to world
@@ -161,9 +236,9 @@ impl Viewport1D {
             return;
         }
 
-        let old_world = self.view_to_world_x(anchor_view_x);
+        let old_axis = self.view_to_axis(anchor_view_x);
         self.zoom = new_zoom;
-        let new_anchor_view = self.world_to_view_x(old_world);
+        let new_anchor_view = self.axis_to_view(old_axis);
         let delta_view = anchor_view_x - new_anchor_view;
         self.pan_by_view(delta_view);
     }
@@ -179,6 +254,7 @@ impl Viewport1D {
 
     /// Fits the given world‑space range into the view span, preserving aspect ratio.
     pub fn fit_range(&mut self, world_range: Range<f64>) {
+        let world_range = self.world_to_axis_range(world_range);
         let w_len = world_range.end - world_range.start;
         if w_len <= 0.0 {
             return;
@@ -207,6 +283,25 @@ impl Viewport1D {
         self.clamp_to_bounds();
     }
 
+    /// Zooms and pans so that exactly `world_range` is visible, if zoom limits allow.
+    ///
+    /// Unlike [`Viewport1D::fit_range`], this ignores the [`FitMode`]: the
+    /// range is stretched across the whole view span. If the required zoom is
+    /// outside the zoom limits, the range is centered at the nearest allowed
+    /// zoom. Clamping is applied afterwards if configured.
+    pub fn set_visible_world_range(&mut self, world_range: Range<f64>) {
+        let world_range = self.world_to_axis_range(world_range);
+        let w_len = world_range.end - world_range.start;
+        let v_len = self.view_span.end - self.view_span.start;
+        if w_len <= 0.0 || v_len <= 0.0 {
+            return;
+        }
+        self.zoom = (v_len / w_len).clamp(self.min_zoom, self.max_zoom);
+        let world_center = (world_range.start + world_range.end) * 0.5;
+        self.pan = v_len * 0.5 - world_center * self.zoom;
+        self.clamp_to_bounds();
+    }
+
     /// Returns the visible world‑space range.
     #[must_use]
     pub fn visible_world_range(&self) -> Range<f64> {
@@ -218,13 +313,13 @@ impl Viewport1D {
     /// Converts a world‑space X coordinate into view/device coordinates.
     #[must_use]
     pub fn world_to_view_x(&self, x: f64) -> f64 {
-        self.view_span.start + self.pan + self.zoom * x
+        self.axis_to_view(self.transform.world_to_axis(x))
     }
 
     /// Converts a view/device‑space X coordinate into world coordinates.
     #[must_use]
     pub fn view_to_world_x(&self, x: f64) -> f64 {
-        (x - self.view_span.start - self.pan) / self.zoom
+        self.transform.axis_to_world(self.view_to_axis(x))
     }
 
     /// Convenience conversion from a `Point`, using its X coordinate.
@@ -238,9 +333,19 @@ impl Viewport1D {
     }
 
     /// Returns the current world‑units‑per‑pixel ratio along the X axis.
+    ///
+    /// This is `1.0 / zoom` for a linear axis. For non‑linear axes the ratio
+    /// varies along the view; this returns its value at the center of the
+    /// view span (see [`Viewport1D::world_units_per_pixel_at`]).
     #[must_use]
     pub fn world_units_per_pixel_x(&self) -> f64 {
-        1.0 / self.zoom
+        self.world_units_per_pixel_at((self.view_span.start + self.view_span.end) * 0.5)
+    }
+
+    /// Returns the world‑units‑per‑pixel ratio at a view/device X coordinate.
+    #[must_use]
+    pub fn world_units_per_pixel_at(&self, view_x: f64) -> f64 {
+        self.transform.world_per_axis(self.view_to_axis(view_x)) / self.zoom
     }
 
     /// Suggests a “nice” grid spacing in world units for the current zoom.
@@ -290,6 +395,24 @@ impl Viewport1D {
         }
     }
 
+    fn axis_to_view(&self, u: f64) -> f64 {
+        self.view_span.start + self.pan + self.zoom * u
+    }
+
+    fn view_to_axis(&self, x: f64) -> f64 {
+        (x - self.view_span.start - self.pan) / self.zoom
+    }
+
+    fn world_to_axis_range(&self, range: Range<f64>) -> Range<f64> {
+        self.transform.world_to_axis(range.start)..self.transform.world_to_axis(range.end)
+    }
+
+    /// World bounds in axis coordinates, if set and non‑empty.
+    fn axis_bounds(&self) -> Option<Range<f64>> {
+        let bounds = self.world_bounds.clone().filter(|b| b.end > b.start)?;
+        Some(self.world_to_axis_range(bounds))
+    }
+
     fn world_bounds_is_eq(&self, other: &Option<Range<f64>>) -> bool {
         match (&self.world_bounds, other) {
             (None, None) => true,
@@ -302,12 +425,13 @@ impl Viewport1D {
         if self.clamp_mode == ClampMode::None {
             return;
         }
-        let bounds = match &self.world_bounds {
-            Some(b) if b.end > b.start => b,
-            _ => return,
+        let Some(bounds) = self.axis_bounds() else {
+            return;
         };
 
-        let visible = self.visible_world_range();
+        let start = self.view_to_axis(self.view_span.start);
+        let end = self.view_to_axis(self.view_span.end);
+        let visible = if start <= end { start..end } else { end..start };
         if visible.end <= visible.start {
             return;
         }
@@ -357,6 +481,7 @@ mod tests {
     use kurbo::Point;
 
     use super::{ClampMode, FitMode, Viewport1D};
+    use crate::axis::{LogAxis, PiecewiseAxis, SymlogAxis};
 
     #[test]
     fn world_view_roundtrip_1d() {
@@ -440,6 +565,27 @@ mod tests {
         assert_eq!(info.fit_mode, FitMode::Center);
     }
 
+    #[test]
+    fn pan_range_keeps_visible_range_inside_bounds_1d() {
+        let mut vp = Viewport1D::new(0.0..100.0);
+        vp.set_world_bounds(Some(0.0..400.0));
+        vp.set_zoom(2.0);
+
+        // 800 px of content in a 100 px span.
+        let range = vp.pan_range().unwrap();
+        assert_eq!(range, -700.0..0.0);
+        vp.set_pan(range.start);
+        let vis = vp.visible_world_range();
+        assert!((vis.end - 400.0).abs() < 1e-9);
+        assert_eq!(vp.pan(), -700.0);
+
+        // Content shorter than the span collapses the range.
+        vp.set_zoom(0.125);
+        let range = vp.pan_range().unwrap();
+        assert_eq!(range.start, range.end);
+        assert!((range.start - 25.0).abs() < 1e-9);
+    }
+
     #[test]
     fn view_to_world_point_x_ignores_y_coordinate() {
         let mut vp = Viewport1D::new(0.0..800.0);
@@ -453,4 +599,44 @@ mod tests {
         });
         assert!((world_from_y0 - world_from_y1).abs() < 1e-9);
     }
+
+    #[test]
+    fn log_axis_zoom_fit_and_clamp_1d() {
+        let mut vp = Viewport1D::with_transform(0.0..600.0, LogAxis);
+        vp.set_world_bounds(Some(20.0..20_000.0));
+        vp.fit_world();
+        let vis = vp.visible_world_range();
+        assert!((vis.start - 20.0).abs() < 1e-9 && (vis.end - 20_000.0).abs() < 1e-6);
+        assert!((vp.world_to_view_x(200.0) - 200.0).abs() < 1e-9);
+        assert!((vp.world_to_view_x(2_000.0) - 400.0).abs() < 1e-9);
+
+        vp.zoom_about_view_point(400.0, 4.0);
+        assert!((vp.view_to_world_x(400.0) - 2_000.0).abs() < 1e-6);
+        assert!(vp.world_units_per_pixel_at(500.0) > vp.world_units_per_pixel_at(100.0));
+
+        vp.set_clamp_mode(ClampMode::KeepSomeVisible);
+        vp.pan_by_view(1e6);
+        assert!(vp.visible_world_range().end >= 20.0 - 1e-9);
+    }
+
+    #[test]
+    fn symlog_and_broken_axes_1d() {
+        let mut vp = Viewport1D::with_transform(0.0..100.0, SymlogAxis::new(1.0));
+        vp.set_visible_world_range(-1_000.0..1_000.0);
+        assert!((vp.world_to_view_x(0.0) - 50.0).abs() < 1e-9);
+        let vis = vp.visible_world_range();
+        assert!((vis.start + 1_000.0).abs() < 1e-6 && (vis.end - 1_000.0).abs() < 1e-6);
+
+        // Two 10-unit segments separated by a 5 px gap at zoom 1.
+        let mut vp = Viewport1D::with_transform(
+            0.0..25.0,
+            PiecewiseAxis::new([0.0..10.0, 90.0..100.0], 5.0),
+        );
+        vp.fit_range(0.0..100.0);
+        assert_eq!(vp.zoom(), 1.0);
+        assert_eq!(vp.world_to_view_x(10.0), 10.0);
+        assert_eq!(vp.world_to_view_x(90.0), 15.0);
+        assert_eq!(vp.view_to_world_x(20.0), 95.0);
+        assert_eq!(vp.world_units_per_pixel_x(), 16.0);
+    }
 }
@@ -1,23 +1,40 @@
 // Copyright 2025 the Understory Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use core::f64::consts::{PI, TAU};
+use core::ops::Range;
+
+#[cfg(not(feature = "std"))]
+use kurbo::common::FloatFuncs as _;
 use kurbo::{Affine, Point, Rect, Vec2};
 
-use crate::modes::{ClampMode, FitMode};
+use crate::axis::{AxisTransform, LinearAxis};
+use crate::modes::{ClampMode, FitMode, ZoomMode};
 
 /// 2D viewport over a world‑space plane.
 ///
 /// `Viewport2D` tracks a rectangular region in device/view space and a
-/// uniform pan+zoom transform mapping world coordinates into that region.
+/// pan+zoom+rotation transform mapping world coordinates into that region.
 /// It can be used to:
 /// - Convert points and rectangles between world and view coordinates.
-/// - Pan and zoom around a chosen anchor point.
+/// - Pan, zoom, and rotate around a chosen anchor point.
 /// - Fit the entire world bounds (or a sub‑rect) into the view.
+///
+/// World coordinates are first mapped through a per‑axis [`AxisTransform`]
+/// (the identity [`LinearAxis`] by default), then scaled by the zoom factors
+/// (one per axis, equal unless [`ZoomMode::Anisotropic`] is selected), then
+/// rotated, then translated by the pan offset into the view rect.
 #[derive(Clone, Debug)]
-pub struct Viewport2D {
+pub struct Viewport2D<X = LinearAxis, Y = LinearAxis> {
     view_rect: Rect,
+    x_axis: X,
+    y_axis: Y,
     world_bounds: Option<Rect>,
-    zoom: f64,
+    /// Zoom factors along the world X and Y axes.
+    zoom: Vec2,
+    zoom_mode: ZoomMode,
+    /// Rotation in radians, normalized to `(-π, π]`.
+    rotation: f64,
     pan: Vec2,
     min_zoom: f64,
     max_zoom: f64,
@@ -30,15 +47,31 @@ pub struct Viewport2D {
 impl Viewport2D {
     /// Creates a new viewport covering `view_rect` with default zoom and clamping.
     ///
-    /// - Initial zoom is `1.0`.
+    /// - Initial zoom is `1.0` and uniform.
+    /// - Initial rotation is zero.
     /// - Initial pan is zero (world origin maps to the view rect origin).
     /// - Zoom is clamped to the range `[1e-3, 1e3]` by default.
     #[must_use]
     pub fn new(view_rect: Rect) -> Self {
+        Self::with_axes(view_rect, LinearAxis, LinearAxis)
+    }
+}
+
+impl<X: AxisTransform, Y: AxisTransform> Viewport2D<X, Y> {
+    /// Creates a new viewport covering `view_rect` with per‑axis transforms.
+    ///
+    /// Defaults are as for [`Viewport2D::new`], with the axis origin (rather
+    /// than the world origin) mapping to the view rect origin.
+    #[must_use]
+    pub fn with_axes(view_rect: Rect, x_axis: X, y_axis: Y) -> Self {
         let mut vp = Self {
             view_rect,
+            x_axis,
+            y_axis,
             world_bounds: None,
-            zoom: 1.0,
+            zoom: Vec2::new(1.0, 1.0),
+            zoom_mode: ZoomMode::default(),
+            rotation: 0.0,
             pan: Vec2::ZERO,
             min_zoom: 1e-3,
             max_zoom: 1e3,
@@ -51,6 +84,27 @@ impl Viewport2D {
         vp
     }
 
+    /// Returns the transform of the world X axis.
+    #[must_use]
+    pub fn x_axis(&self) -> &X {
+        &self.x_axis
+    }
+
+    /// Returns the transform of the world Y axis.
+    #[must_use]
+    pub fn y_axis(&self) -> &Y {
+        &self.y_axis
+    }
+
+    /// Replaces both axis transforms, keeping zoom and pan in axis units.
+    ///
+    /// Clamping is applied afterwards if world bounds are set.
+    pub fn set_axes(&mut self, x_axis: X, y_axis: Y) {
+        self.x_axis = x_axis;
+        self.y_axis = y_axis;
+        self.clamp_to_bounds();
+    }
+
     /// Returns the current view rectangle in device coordinates.
     #[must_use]
     pub fn view_rect(&self) -> Rect {
@@ -85,16 +139,49 @@ impl Viewport2D {
         self.world_bounds
     }
 
-    /// Returns the current uniform zoom factor.
+    /// Returns the current zoom factor.
+    ///
+    /// With [`ZoomMode::Anisotropic`], this is the zoom along the world X axis;
+    /// see [`Viewport2D::zoom_xy`] for both axes.
     #[must_use]
     pub fn zoom(&self) -> f64 {
+        self.zoom.x
+    }
+
+    /// Returns the zoom factors along the world X and Y axes.
+    ///
+    /// Both components are equal in [`ZoomMode::Uniform`].
+    #[must_use]
+    pub fn zoom_xy(&self) -> Vec2 {
         self.zoom
     }
 
+    /// Returns the current zoom mode.
+    #[must_use]
+    pub fn zoom_mode(&self) -> ZoomMode {
+        self.zoom_mode
+    }
+
+    /// Sets the zoom mode.
+    ///
+    /// Switching to [`ZoomMode::Uniform`] resets the Y zoom to the X zoom.
+    pub fn set_zoom_mode(&mut self, mode: ZoomMode) {
+        if self.zoom_mode == mode {
+            return;
+        }
+        self.zoom_mode = mode;
+        if mode == ZoomMode::Uniform && self.zoom.y != self.zoom.x {
+            self.zoom.y = self.zoom.x;
+            self.rebuild_transforms();
+            self.clamp_to_bounds();
+        }
+    }
+
     /// Sets the minimum and maximum zoom factors.
     ///
     /// The provided range is normalized so that `min_zoom <= max_zoom`. The
-    /// current zoom is clamped into the new range.
+    /// current zoom is clamped into the new range. With
+    /// [`ZoomMode::Anisotropic`], the limits apply to each axis separately.
     pub fn set_zoom_limits(&mut self, min_zoom: f64, max_zoom: f64) {
         let (min_zoom, max_zoom) = if min_zoom <= max_zoom {
             (min_zoom, max_zoom)
@@ -103,7 +190,7 @@ impl Viewport2D {
         };
         self.min_zoom = min_zoom;
         self.max_zoom = max_zoom;
-        self.set_zoom(self.zoom);
+        self.set_zoom_xy(self.zoom);
     }
 
     /// Sets the clamp mode for panning relative to world bounds.
@@ -131,10 +218,18 @@ impl Viewport2D {
         self.fit_mode
     }
 
-    /// Sets the zoom factor, clamping it into the configured zoom range.
+    /// Sets the zoom factor for both axes, clamping it into the configured zoom range.
     pub fn set_zoom(&mut self, zoom: f64) {
-        let clamped = zoom.clamp(self.min_zoom, self.max_zoom);
-        if (self.zoom - clamped).abs() < f64::EPSILON {
+        self.set_zoom_xy(Vec2::new(zoom, zoom));
+    }
+
+    /// Sets the zoom factors along the world X and Y axes, clamping each into
+    /// the configured zoom range.
+    ///
+    /// In [`ZoomMode::Uniform`], the X component is used for both axes.
+    pub fn set_zoom_xy(&mut self, zoom: Vec2) {
+        let clamped = self.clamp_zoom(zoom);
+        if (self.zoom - clamped).hypot() < f64::EPSILON {
             return;
         }
         self.zoom = clamped;
@@ -142,6 +237,97 @@ impl Viewport2D {
         self.clamp_to_bounds();
     }
 
+    /// Returns the current rotation in radians, normalized to `(-π, π]`.
+    ///
+    /// Positive angles rotate the world clockwise on screen (for a Y‑down view
+    /// space), matching [`Affine::rotate`].
+    #[must_use]
+    pub fn rotation(&self) -> f64 {
+        self.rotation
+    }
+
+    /// Sets the rotation in radians.
+    ///
+    /// Like [`Viewport2D::set_zoom`], this keeps the pan offset, so the world
+    /// origin stays fixed in view space; use
+    /// [`Viewport2D::rotate_about_view_point`] to rotate around another point.
+    pub fn set_rotation(&mut self, radians: f64) {
+        let rotation = normalize_angle(radians);
+        if (self.rotation - rotation).abs() < f64::EPSILON {
+            return;
+        }
+        self.rotation = rotation;
+        self.rebuild_transforms();
+        self.clamp_to_bounds();
+    }
+
+    /// Rotates the view by `delta_radians` around an anchor point in view/device coordinates.
+    ///
+    /// The anchor point remains fixed in view space.
+    pub fn rotate_about_view_point(&mut self, anchor_view: Point, delta_radians: f64) {
+        if delta_radians == 0.0 {
+            return;
+        }
+        let old_axis = self.view_to_world * anchor_view;
+        self.rotation = normalize_angle(self.rotation + delta_radians);
+        self.rebuild_transforms();
+        let new_anchor_view = self.world_to_view * old_axis;
+        self.pan += anchor_view - new_anchor_view;
+        self.rebuild_transforms();
+        self.clamp_to_bounds();
+    }
+
+    /// Returns the current pan offset in view/device coordinates.
+    ///
+    /// The world origin maps to `view_rect.origin() + pan`.
+    #[must_use]
+    pub fn pan(&self) -> Vec2 {
+        self.pan
+    }
+
+    /// Sets the pan offset in view/device coordinates.
+    ///
+    /// Clamping relative to world bounds is applied afterwards if configured.
+    pub fn set_pan(&mut self, pan: Vec2) {
+        if self.pan == pan {
+            return;
+        }
+        self.pan = pan;
+        self.rebuild_transforms();
+        self.clamp_to_bounds();
+    }
+
+    /// Returns the region of pan offsets that keep the visible rect inside the world bounds.
+    ///
+    /// The result is expressed in pan space (view/device units). Along an
+    /// axis where the world bounds are smaller than the view at the current
+    /// zoom, the region collapses to the single pan offset that positions them
+    /// according to the [`FitMode`]. When the view is rotated, the bounds'
+    /// view‑space bounding box is used. Returns `None` if no (non‑empty) world
+    /// bounds are set.
+    #[must_use]
+    pub fn pan_bounds(&self) -> Option<Rect> {
+        let bounds = self.axis_bounds()?;
+        // Bounds in view space relative to `view_rect.origin() + pan`.
+        let content = transform_rect_bbox(self.linear(), bounds);
+        let axis = |view_len: f64, content_min: f64, content_max: f64| {
+            let max = -content_min;
+            let min = view_len - content_max;
+            if min <= max {
+                (min, max)
+            } else {
+                let pan = match self.fit_mode {
+                    FitMode::Center => (min + max) * 0.5,
+                    FitMode::AlignMin => max,
+                };
+                (pan, pan)
+            }
+        };
+        let (x0, x1) = axis(self.view_rect.width(), content.x0, content.x1);
+        let (y0, y1) = axis(self.view_rect.height(), content.y0, content.y1);
+        Some(Rect::new(x0, y0, x1, y1))
+    }
+
     /// Pans the view by a delta in view/device space.
     ///
     /// This adjusts the pan offset and then applies clamping relative to world
@@ -158,21 +344,28 @@ impl Viewport2D {
     /// Zooms around a given anchor point in view/device coordinates.
     ///
     /// The anchor point remains fixed in view space as much as possible under
-    /// the new zoom level.
+    /// the new zoom level. Both axes are scaled by `factor`.
     pub fn zoom_about_view_point(&mut self, anchor_view: Point, factor: f64) {
-        if factor <= 0.0 {
+        self.zoom_about_view_point_xy(anchor_view, Vec2::new(factor, factor));
+    }
+
+    /// Zooms around a given anchor point with separate factors for the world X and Y axes.
+    ///
+    /// In [`ZoomMode::Uniform`], the X factor is used for both axes.
+    pub fn zoom_about_view_point_xy(&mut self, anchor_view: Point, factor: Vec2) {
+        if factor.x <= 0.0 || factor.y <= 0.0 {
             return;
         }
         let old_zoom = self.zoom;
-        let new_zoom = (old_zoom * factor).clamp(self.min_zoom, self.max_zoom);
-        if (new_zoom - old_zoom).abs() < f64::EPSILON {
+        let new_zoom = self.clamp_zoom(Vec2::new(old_zoom.x * factor.x, old_zoom.y * factor.y));
+        if (new_zoom - old_zoom).hypot() < f64::EPSILON {
             return;
         }
 
-        let old_world = self.view_to_world_point(anchor_view);
+        let old_axis = self.view_to_world * anchor_view;
         self.zoom = new_zoom;
         self.rebuild_transforms();
-        let new_anchor_view = self.world_to_view_point(old_world);
+        let new_anchor_view = self.world_to_view * old_axis;
         let delta_view = anchor_view - new_anchor_view;
         self.pan_by_view(delta_view);
     }
@@ -186,8 +379,14 @@ impl Viewport2D {
         }
     }
 
-    /// Fits the given world‑space rectangle into the view, preserving aspect ratio.
+    /// Fits the given world‑space rectangle into the view.
+    ///
+    /// In [`ZoomMode::Uniform`] the aspect ratio is preserved. In
+    /// [`ZoomMode::Anisotropic`] each axis is zoomed independently so that the
+    /// rectangle fills the view. When the view is rotated, the rotated
+    /// rectangle's bounding box is fitted.
     pub fn fit_rect(&mut self, rect: Rect) {
+        let rect = self.world_to_axis_rect(rect);
         if rect.width() <= 0.0 || rect.height() <= 0.0 {
             return;
         }
@@ -196,25 +395,38 @@ impl Viewport2D {
             return;
         }
 
-        let sx = view_size.width / rect.width().max(f64::MIN_POSITIVE);
-        let sy = view_size.height / rect.height().max(f64::MIN_POSITIVE);
-        let target_zoom = sx.min(sy);
+        // Start from the zoom ratio we want between the axes, then scale it
+        // uniformly so that the rotated rectangle's bounding box fits.
+        let base = match self.zoom_mode {
+            ZoomMode::Uniform => Vec2::new(1.0, 1.0),
+            ZoomMode::Anisotropic => Vec2::new(
+                view_size.width / rect.width(),
+                view_size.height / rect.height(),
+            ),
+        };
+        let (sin, cos) = self.rotation.sin_cos();
+        let (sin, cos) = (sin.abs(), cos.abs());
+        let (w, h) = (rect.width() * base.x, rect.height() * base.y);
+        let extent_x = w * cos + h * sin;
+        let extent_y = w * sin + h * cos;
+        let sx = view_size.width / extent_x.max(f64::MIN_POSITIVE);
+        let sy = view_size.height / extent_y.max(f64::MIN_POSITIVE);
+        let target_zoom = base * sx.min(sy);
 
-        let zoom = target_zoom.clamp(self.min_zoom, self.max_zoom);
-        self.zoom = zoom;
+        self.zoom = self.clamp_zoom(target_zoom);
 
         // Choose pan based on fit mode so that either the content is centered
         // or its minimum corner aligns with the view origin.
-        let view_origin = self.view_rect.origin().to_vec2();
+        let linear = self.linear();
         self.pan = match self.fit_mode {
             FitMode::Center => {
-                let view_center = self.view_rect.center().to_vec2();
-                let world_center = rect.center().to_vec2();
-                view_center - view_origin - world_center * zoom
+                let view_center = self.view_rect.center() - self.view_rect.origin();
+                let world_center = linear * rect.center();
+                view_center - world_center.to_vec2()
             }
             FitMode::AlignMin => {
-                let world_min = rect.origin().to_vec2();
-                -world_min * zoom
+                let content = transform_rect_bbox(linear, rect);
+                -content.origin().to_vec2()
             }
         };
 
@@ -224,94 +436,177 @@ impl Viewport2D {
 
     /// Centers the view on the given world‑space point.
     pub fn center_on(&mut self, world_pt: Point) {
-        let view_center = self.view_rect.center();
-        let world_in_view = self.world_to_view_point(world_pt);
-        let delta = view_center - world_in_view;
-        self.pan_by_view(delta);
+        self.center_on_axis(self.world_to_axis_point(world_pt));
     }
 
     /// Returns the visible world‑space rectangle.
+    ///
+    /// When the view is rotated, this is the axis‑aligned bounding box of the
+    /// visible region; see [`Viewport2D::visible_world_quad`] for its exact shape.
     #[must_use]
     pub fn visible_world_rect(&self) -> Rect {
         self.view_to_world_rect(self.view_rect)
     }
 
+    /// Returns the visible world‑space range along the X axis.
+    ///
+    /// This is the X extent of [`Viewport2D::visible_world_rect`].
+    #[must_use]
+    pub fn visible_world_range_x(&self) -> Range<f64> {
+        let r = self.visible_world_rect();
+        r.x0..r.x1
+    }
+
+    /// Returns the visible world‑space range along the Y axis.
+    ///
+    /// This is the Y extent of [`Viewport2D::visible_world_rect`].
+    #[must_use]
+    pub fn visible_world_range_y(&self) -> Range<f64> {
+        let r = self.visible_world_rect();
+        r.y0..r.y1
+    }
+
+    /// Zooms and pans along the X axis so that exactly `world_range` is visible horizontally.
+    ///
+    /// The world coordinate at the vertical center of the view is kept. In
+    /// [`ZoomMode::Uniform`] the Y zoom changes along with the X zoom. If the
+    /// required zoom is outside the zoom limits, the range is centered at the
+    /// nearest allowed zoom. This is intended for unrotated views.
+    pub fn set_visible_world_range_x(&mut self, world_range: Range<f64>) {
+        let (start, end) = (
+            self.x_axis.world_to_axis(world_range.start),
+            self.x_axis.world_to_axis(world_range.end),
+        );
+        let len = end - start;
+        let width = self.view_rect.width();
+        if len <= 0.0 || width <= 0.0 {
+            return;
+        }
+        let center = self.view_to_world * self.view_rect.center();
+        self.zoom = self.clamp_zoom(Vec2::new(width / len, self.zoom.y));
+        self.rebuild_transforms();
+        self.center_on_axis(Point::new((start + end) * 0.5, center.y));
+    }
+
+    /// Zooms and pans along the Y axis so that exactly `world_range` is visible vertically.
+    ///
+    /// This is the Y axis counterpart of [`Viewport2D::set_visible_world_range_x`].
+    pub fn set_visible_world_range_y(&mut self, world_range: Range<f64>) {
+        let (start, end) = (
+            self.y_axis.world_to_axis(world_range.start),
+            self.y_axis.world_to_axis(world_range.end),
+        );
+        let len = end - start;
+        let height = self.view_rect.height();
+        if len <= 0.0 || height <= 0.0 {
+            return;
+        }
+        let center = self.view_to_world * self.view_rect.center();
+        let zoom_y = height / len;
+        let zoom = match self.zoom_mode {
+            ZoomMode::Uniform => Vec2::new(zoom_y, zoom_y),
+            ZoomMode::Anisotropic => Vec2::new(self.zoom.x, zoom_y),
+        };
+        self.zoom = self.clamp_zoom(zoom);
+        self.rebuild_transforms();
+        self.center_on_axis(Point::new(center.x, (start + end) * 0.5));
+    }
+
+    /// Returns the corners of the visible region in world space.
+    ///
+    /// The corners correspond to the view rect's top‑left, top‑right,
+    /// bottom‑right, and bottom‑left corners, in that order.
+    #[must_use]
+    pub fn visible_world_quad(&self) -> [Point; 4] {
+        let r = self.view_rect;
+        [
+            Point::new(r.x0, r.y0),
+            Point::new(r.x1, r.y0),
+            Point::new(r.x1, r.y1),
+            Point::new(r.x0, r.y1),
+        ]
+        .map(|p| self.axis_to_world_point(self.view_to_world * p))
+    }
+
+    /// Returns the current world → view transform.
+    ///
+    /// With non‑linear axis transforms, this maps axis coordinates (see
+    /// [`AxisTransform::world_to_axis`]) rather than world coordinates.
+    #[must_use]
+    pub fn world_to_view(&self) -> Affine {
+        self.world_to_view
+    }
+
+    /// Returns the current view → world transform.
+    ///
+    /// With non‑linear axis transforms, this maps to axis coordinates (see
+    /// [`AxisTransform::axis_to_world`]) rather than world coordinates.
+    #[must_use]
+    pub fn view_to_world(&self) -> Affine {
+        self.view_to_world
+    }
+
     /// Converts a world‑space point into view/device coordinates.
     #[must_use]
     pub fn world_to_view_point(&self, pt: Point) -> Point {
-        self.world_to_view * pt
+        self.world_to_view * self.world_to_axis_point(pt)
     }
 
     /// Converts a view/device‑space point into world coordinates.
     #[must_use]
     pub fn view_to_world_point(&self, pt: Point) -> Point {
-        self.view_to_world * pt
+        self.axis_to_world_point(self.view_to_world * pt)
     }
 
     /// Converts a world‑space rectangle into view/device coordinates.
+    ///
+    /// When the view is rotated, this is the bounding box of the transformed rectangle.
     #[must_use]
     pub fn world_to_view_rect(&self, rect: Rect) -> Rect {
-        // Transform the four corners and take their bounding box. This is
-        // sufficient for the axis‑aligned, uniform zoom transform used here.
-        let p0 = rect.origin();
-        let p1 = Point::new(rect.max_x(), rect.y0);
-        let p2 = Point::new(rect.x0, rect.max_y());
-        let p3 = Point::new(rect.max_x(), rect.max_y());
-        let q0 = self.world_to_view * p0;
-        let q1 = self.world_to_view * p1;
-        let q2 = self.world_to_view * p2;
-        let q3 = self.world_to_view * p3;
-        let min_x = q0.x.min(q1.x).min(q2.x).min(q3.x);
-        let min_y = q0.y.min(q1.y).min(q2.y).min(q3.y);
-        let max_x = q0.x.max(q1.x).max(q2.x).max(q3.x);
-        let max_y = q0.y.max(q1.y).max(q2.y).max(q3.y);
-        Rect::new(min_x, min_y, max_x, max_y)
+        transform_rect_bbox(self.world_to_view, self.world_to_axis_rect(rect))
     }
 
     /// Converts a view/device‑space rectangle into world coordinates.
+    ///
+    /// When the view is rotated, this is the bounding box of the transformed rectangle.
     #[must_use]
     pub fn view_to_world_rect(&self, rect: Rect) -> Rect {
-        let p0 = rect.origin();
-        let p1 = Point::new(rect.max_x(), rect.y0);
-        let p2 = Point::new(rect.x0, rect.max_y());
-        let p3 = Point::new(rect.max_x(), rect.max_y());
-        let q0 = self.view_to_world * p0;
-        let q1 = self.view_to_world * p1;
-        let q2 = self.view_to_world * p2;
-        let q3 = self.view_to_world * p3;
-        let min_x = q0.x.min(q1.x).min(q2.x).min(q3.x);
-        let min_y = q0.y.min(q1.y).min(q2.y).min(q3.y);
-        let max_x = q0.x.max(q1.x).max(q2.x).max(q3.x);
-        let max_y = q0.y.max(q1.y).max(q2.y).max(q3.y);
-        Rect::new(min_x, min_y, max_x, max_y)
+        self.axis_to_world_rect(transform_rect_bbox(self.view_to_world, rect))
     }
 
     /// Returns the current world‑units‑per‑pixel ratio at the view center.
     ///
-    /// This is `1.0 / zoom` for the axis‑aligned, uniform zoom model used
-    /// by this crate and can be used to choose grid spacing or stroke
-    /// thickness in world units.
+    /// This is `1.0 / zoom` for uniform zoom on linear axes (independent of
+    /// rotation) and can be used to choose grid spacing or stroke thickness
+    /// in world units. When the two axes differ, it uses the geometric mean
+    /// of both.
     #[must_use]
     pub fn world_units_per_pixel(&self) -> f64 {
-        1.0 / self.zoom
+        let x = self.world_units_per_pixel_x();
+        let y = self.world_units_per_pixel_y();
+        if x == y { x } else { (x * y).sqrt() }
     }
 
-    /// Returns the current world‑units‑per‑pixel ratio along the X axis.
+    /// Returns the current world‑units‑per‑pixel ratio along the world X axis.
     ///
-    /// For the uniform zoom model used by this crate, this is identical
-    /// to [`Viewport2D::world_units_per_pixel`].
+    /// For non‑linear axes this is the ratio at the view center. For uniform
+    /// zoom on linear axes, this is identical to
+    /// [`Viewport2D::world_units_per_pixel`].
     #[must_use]
     pub fn world_units_per_pixel_x(&self) -> f64 {
-        self.world_units_per_pixel()
+        let center = self.view_to_world * self.view_rect.center();
+        self.x_axis.world_per_axis(center.x) / self.zoom.x
     }
 
-    /// Returns the current world‑units‑per‑pixel ratio along the Y axis.
+    /// Returns the current world‑units‑per‑pixel ratio along the world Y axis.
     ///
-    /// For the uniform zoom model used by this crate, this is identical
-    /// to [`Viewport2D::world_units_per_pixel`].
+    /// For non‑linear axes this is the ratio at the view center. For uniform
+    /// zoom on linear axes, this is identical to
+    /// [`Viewport2D::world_units_per_pixel`].
     #[must_use]
     pub fn world_units_per_pixel_y(&self) -> f64 {
-        self.world_units_per_pixel()
+        let center = self.view_to_world * self.view_rect.center();
+        self.y_axis.world_per_axis(center.y) / self.zoom.y
     }
 
     /// Suggests a “nice” grid spacing in world units for the current zoom.
@@ -352,7 +647,10 @@ impl Viewport2D {
             view_rect: self.view_rect,
             world_bounds: self.world_bounds,
             visible_world_rect: self.visible_world_rect(),
-            zoom: self.zoom,
+            zoom: self.zoom.x,
+            zoom_xy: self.zoom,
+            zoom_mode: self.zoom_mode,
+            rotation: self.rotation,
             pan: self.pan,
             min_zoom: self.min_zoom,
             max_zoom: self.max_zoom,
@@ -361,11 +659,66 @@ impl Viewport2D {
         }
     }
 
+    /// Scale and rotation part of the world → view transform, without translation.
+    fn linear(&self) -> Affine {
+        Affine::rotate(self.rotation) * Affine::scale_non_uniform(self.zoom.x, self.zoom.y)
+    }
+
+    fn world_to_axis_point(&self, pt: Point) -> Point {
+        Point::new(
+            self.x_axis.world_to_axis(pt.x),
+            self.y_axis.world_to_axis(pt.y),
+        )
+    }
+
+    fn axis_to_world_point(&self, pt: Point) -> Point {
+        Point::new(
+            self.x_axis.axis_to_world(pt.x),
+            self.y_axis.axis_to_world(pt.y),
+        )
+    }
+
+    /// Maps a world rectangle to axis space; exact because transforms are monotonic.
+    fn world_to_axis_rect(&self, rect: Rect) -> Rect {
+        Rect::from_points(
+            self.world_to_axis_point(Point::new(rect.x0, rect.y0)),
+            self.world_to_axis_point(Point::new(rect.x1, rect.y1)),
+        )
+    }
+
+    fn axis_to_world_rect(&self, rect: Rect) -> Rect {
+        Rect::from_points(
+            self.axis_to_world_point(Point::new(rect.x0, rect.y0)),
+            self.axis_to_world_point(Point::new(rect.x1, rect.y1)),
+        )
+    }
+
+    /// World bounds in axis space, if set and non‑empty.
+    fn axis_bounds(&self) -> Option<Rect> {
+        let bounds = self
+            .world_bounds
+            .filter(|b| b.width() > 0.0 && b.height() > 0.0)?;
+        Some(self.world_to_axis_rect(bounds))
+    }
+
+    fn center_on_axis(&mut self, axis_pt: Point) {
+        let delta = self.view_rect.center() - self.world_to_view * axis_pt;
+        self.pan_by_view(delta);
+    }
+
+    fn clamp_zoom(&self, zoom: Vec2) -> Vec2 {
+        let x = zoom.x.clamp(self.min_zoom, self.max_zoom);
+        let y = match self.zoom_mode {
+            ZoomMode::Uniform => x,
+            ZoomMode::Anisotropic => zoom.y.clamp(self.min_zoom, self.max_zoom),
+        };
+        Vec2::new(x, y)
+    }
+
     fn rebuild_transforms(&mut self) {
         let view_origin = self.view_rect.origin().to_vec2();
-        let scale = self.zoom;
-        // World → view: translate by pan, then scale, then translate into view rect.
-        self.world_to_view = Affine::translate(view_origin + self.pan) * Affine::scale(scale);
+        // World → view: scale, rotate, then translate by pan into the view rect.
+        self.world_to_view = Affine::translate(view_origin + self.pan) * self.linear();
         self.view_to_world = self.world_to_view.inverse();
     }
 
@@ -373,14 +726,14 @@ impl Viewport2D {
         if self.clamp_mode == ClampMode::None {
             return;
         }
-        let bounds = match self.world_bounds {
-            Some(b) if b.width() > 0.0 && b.height() > 0.0 => b,
-            _ => return,
+        let Some(bounds) = self.axis_bounds() else {
+            return;
         };
 
-        // Current visible world rect; we will adjust pan to keep at least some
-        // overlap with `bounds`.
-        let visible = self.visible_world_rect();
+        // Current visible rect in axis space (the bounding box of the view
+        // when rotated); we will adjust pan to keep at least some overlap
+        // with `bounds`.
+        let visible = transform_rect_bbox(self.view_to_world, self.view_rect);
         if visible.width() <= 0.0 || visible.height() <= 0.0 {
             return;
         }
@@ -406,14 +759,32 @@ impl Viewport2D {
             // Adjust pan so that the visible world rect moves by `dx`/`dy`
             // in world space. Increasing pan moves the world in the negative
             // direction, so we need to negate.
-            let scale = self.zoom;
-            let delta_view = Vec2::new(-dx * scale, -dy * scale);
+            let delta_view = (self.linear() * Point::new(-dx, -dy)).to_vec2();
             self.pan += delta_view;
             self.rebuild_transforms();
         }
     }
 }
 
+/// Normalizes an angle in radians to `(-π, π]`.
+fn normalize_angle(radians: f64) -> f64 {
+    let wrapped = (radians + PI).rem_euclid(TAU) - PI;
+    if wrapped == -PI { PI } else { wrapped }
+}
+
+/// Returns the bounding box of `rect` transformed by `affine`.
+fn transform_rect_bbox(affine: Affine, rect: Rect) -> Rect {
+    let p0 = affine * Point::new(rect.x0, rect.y0);
+    let p1 = affine * Point::new(rect.x1, rect.y0);
+    let p2 = affine * Point::new(rect.x0, rect.y1);
+    let p3 = affine * Point::new(rect.x1, rect.y1);
+    let min_x = p0.x.min(p1.x).min(p2.x).min(p3.x);
+    let min_y = p0.y.min(p1.y).min(p2.y).min(p3.y);
+    let max_x = p0.x.max(p1.x).max(p2.x).max(p3.x);
+    let max_y = p0.y.max(p1.y).max(p2.y).max(p3.y);
+    Rect::new(min_x, min_y, max_x, max_y)
+}
+
 /// Debug snapshot of a [`Viewport2D`] state.
 #[derive(Clone, Copy, Debug)]
 pub struct Viewport2DDebugInfo {
@@ -421,10 +792,16 @@ pub struct Viewport2DDebugInfo {
     pub view_rect: Rect,
     /// Optional world bounds for clamping and fitting.
     pub world_bounds: Option<Rect>,
-    /// World‑space rectangle currently visible through the view.
+    /// Bounding box of the world‑space region currently visible through the view.
     pub visible_world_rect: Rect,
-    /// Current uniform zoom factor.
+    /// Current zoom factor (along the world X axis).
     pub zoom: f64,
+    /// Current zoom factors along the world X and Y axes.
+    pub zoom_xy: Vec2,
+    /// Current zoom mode.
+    pub zoom_mode: ZoomMode,
+    /// Current rotation in radians.
+    pub rotation: f64,
     /// Current pan offset in view coordinates.
     pub pan: Vec2,
     /// Minimum zoom factor.
@@ -439,9 +816,12 @@ pub struct Viewport2DDebugInfo {
 
 #[cfg(test)]
 mod tests {
-    use kurbo::{Point, Rect};
+    use core::f64::consts::{FRAC_PI_2, FRAC_PI_4};
 
-    use super::{ClampMode, FitMode, Viewport2D};
+    use kurbo::{Point, Rect, Vec2};
+
+    use super::{ClampMode, FitMode, Viewport2D, ZoomMode};
+    use crate::axis::{LinearAxis, LogAxis};
 
     #[test]
     fn basic_world_view_roundtrip() {
@@ -524,6 +904,22 @@ mod tests {
         assert!(visible.max_y() >= bounds.min_y() - 1e-6);
     }
 
+    #[test]
+    fn pan_bounds_keep_visible_rect_inside_world() {
+        let view_rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let mut vp = Viewport2D::new(view_rect);
+        vp.set_world_bounds(Some(Rect::new(0.0, 0.0, 400.0, 50.0)));
+
+        // Wider than the view horizontally, shorter vertically.
+        let bounds = vp.pan_bounds().unwrap();
+        assert_eq!(bounds, Rect::new(-300.0, 25.0, 0.0, 25.0));
+
+        vp.set_pan(Vec2::new(bounds.x0, bounds.y0));
+        assert_eq!(vp.pan(), Vec2::new(-300.0, 25.0));
+        let visible = vp.visible_world_rect();
+        assert!((visible.max_x() - 400.0).abs() < 1e-9);
+    }
+
     #[test]
     fn suggest_grid_spacing_and_debug_info_2d() {
         let view_rect = Rect::new(0.0, 0.0, 400.0, 300.0);
@@ -549,4 +945,141 @@ mod tests {
         assert_eq!(info.clamp_mode, ClampMode::KeepSomeVisible);
         assert!(info.min_zoom <= info.max_zoom);
     }
+
+    #[test]
+    fn rotate_about_anchor_keeps_anchor_fixed() {
+        let view_rect = Rect::new(0.0, 0.0, 200.0, 100.0);
+        let mut vp = Viewport2D::new(view_rect);
+        vp.set_zoom(2.0);
+
+        let anchor = Point::new(50.0, 30.0);
+        let world_before = vp.view_to_world_point(anchor);
+        vp.rotate_about_view_point(anchor, 0.7);
+        assert!((vp.rotation() - 0.7).abs() < 1e-12);
+        assert!((vp.view_to_world_point(anchor) - world_before).hypot() < 1e-9);
+
+        // Rotation does not change the scale of world distances.
+        let a = vp.world_to_view_point(Point::new(0.0, 0.0));
+        let b = vp.world_to_view_point(Point::new(10.0, 0.0));
+        assert!(((b - a).hypot() - 20.0).abs() < 1e-9);
+
+        // Angles are normalized.
+        vp.set_rotation(3.0 * core::f64::consts::PI);
+        assert!((vp.rotation() - core::f64::consts::PI).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rotated_view_reports_quad_and_bounding_rect() {
+        let view_rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let mut vp = Viewport2D::new(view_rect);
+        vp.set_clamp_mode(ClampMode::None);
+        vp.rotate_about_view_point(view_rect.center(), FRAC_PI_4);
+
+        let quad = vp.visible_world_quad();
+        let center = vp.view_to_world_point(view_rect.center());
+        // Each corner is half a diagonal away from the center.
+        for corner in quad {
+            assert!(((corner - center).hypot() - 50.0 * 2.0_f64.sqrt()).abs() < 1e-9);
+        }
+        // The bounding rect of a square rotated by 45° is sqrt(2) times wider.
+        let rect = vp.visible_world_rect();
+        assert!((rect.width() - 100.0 * 2.0_f64.sqrt()).abs() < 1e-9);
+        assert!((rect.center() - center).hypot() < 1e-9);
+    }
+
+    #[test]
+    fn fit_rect_accounts_for_rotation() {
+        let view_rect = Rect::new(0.0, 0.0, 200.0, 100.0);
+        let mut vp = Viewport2D::new(view_rect);
+        vp.set_rotation(FRAC_PI_2);
+
+        // A 100×200 rect rotated by 90° is 200×100 on screen and fills the view.
+        let rect = Rect::new(0.0, 0.0, 100.0, 200.0);
+        vp.fit_rect(rect);
+        assert!((vp.zoom() - 1.0).abs() < 1e-9);
+        let fitted = vp.world_to_view_rect(rect);
+        assert!((fitted.x0 - view_rect.x0).abs() < 1e-9);
+        assert!((fitted.y1 - view_rect.y1).abs() < 1e-9);
+
+        vp.set_fit_mode(FitMode::AlignMin);
+        vp.fit_rect(Rect::new(0.0, 0.0, 50.0, 50.0));
+        let fitted = vp.world_to_view_rect(Rect::new(0.0, 0.0, 50.0, 50.0));
+        assert!((fitted.origin() - view_rect.origin()).hypot() < 1e-9);
+    }
+
+    #[test]
+    fn anisotropic_zoom_scales_axes_independently() {
+        let view_rect = Rect::new(0.0, 0.0, 400.0, 100.0);
+        let mut vp = Viewport2D::new(view_rect);
+        vp.set_zoom_mode(ZoomMode::Anisotropic);
+
+        // Fit fills the view on both axes.
+        let rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+        vp.fit_rect(rect);
+        assert!((vp.zoom_xy() - Vec2::new(4.0, 1.0)).hypot() < 1e-9);
+        assert_eq!(vp.visible_world_rect(), rect);
+        assert!((vp.world_units_per_pixel_x() - 0.25).abs() < 1e-12);
+        assert!((vp.world_units_per_pixel_y() - 1.0).abs() < 1e-12);
+
+        // Zooming only X around an anchor keeps the anchor fixed.
+        let anchor = Point::new(100.0, 50.0);
+        let before = vp.view_to_world_point(anchor);
+        vp.zoom_about_view_point_xy(anchor, Vec2::new(2.0, 1.0));
+        assert!((vp.zoom_xy() - Vec2::new(8.0, 1.0)).hypot() < 1e-9);
+        assert!((vp.view_to_world_point(anchor) - before).hypot() < 1e-9);
+
+        // Limits apply per axis; uniform mode collapses to the X zoom.
+        vp.set_zoom_limits(0.5, 5.0);
+        assert!((vp.zoom_xy() - Vec2::new(5.0, 1.0)).hypot() < 1e-9);
+        vp.set_zoom_mode(ZoomMode::Uniform);
+        assert_eq!(vp.zoom_xy(), Vec2::new(5.0, 5.0));
+        vp.set_zoom_xy(Vec2::new(2.0, 3.0));
+        assert_eq!(vp.zoom_xy(), Vec2::new(2.0, 2.0));
+    }
+
+    #[test]
+    fn clamping_moves_rotated_view_back_towards_bounds() {
+        let view_rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let mut vp = Viewport2D::new(view_rect);
+        vp.set_world_bounds(Some(Rect::new(0.0, 0.0, 100.0, 100.0)));
+        vp.rotate_about_view_point(view_rect.center(), 1.0);
+
+        vp.pan_by_view(Vec2::new(5_000.0, -3_000.0));
+        let visible = vp.visible_world_rect();
+        let bounds = vp.world_bounds().unwrap();
+        assert!(
+            visible.max_x() >= bounds.min_x() - 1e-6 && visible.min_x() <= bounds.max_x() + 1e-6
+        );
+        assert!(
+            visible.max_y() >= bounds.min_y() - 1e-6 && visible.min_y() <= bounds.max_y() + 1e-6
+        );
+    }
+
+    #[test]
+    fn log_x_axis_fits_and_roundtrips() {
+        let mut vp = Viewport2D::with_axes(Rect::new(0.0, 0.0, 600.0, 400.0), LogAxis, LinearAxis);
+        vp.set_zoom_mode(ZoomMode::Anisotropic);
+        vp.set_world_bounds(Some(Rect::new(1.0, 0.0, 1000.0, 100.0)));
+        vp.fit_world();
+
+        // Three decades over 600 px: 200 px per decade.
+        for (x, view_x) in [(1.0, 0.0), (10.0, 200.0), (100.0, 400.0), (1000.0, 600.0)] {
+            let p = vp.world_to_view_point(Point::new(x, 50.0));
+            assert!((p.x - view_x).abs() < 1e-9, "{x} -> {}", p.x);
+            let back = vp.view_to_world_point(p);
+            assert!((back.x - x).abs() < 1e-9 * x);
+        }
+        let visible = vp.visible_world_rect();
+        assert!((visible.x0 - 1.0).abs() < 1e-9 && (visible.x1 - 1000.0).abs() < 1e-6);
+
+        // Zooming keeps the anchor's world value fixed.
+        let anchor = Point::new(200.0, 200.0);
+        vp.zoom_about_view_point_xy(anchor, Vec2::new(2.0, 1.0));
+        assert!((vp.view_to_world_point(anchor).x - 10.0).abs() < 1e-9);
+
+        // Pixels cover more world units further right on a log axis.
+        let wupp = vp.world_units_per_pixel_x();
+        let center_x = vp.view_to_world_point(vp.view_rect().center()).x;
+        assert!((wupp - center_x * core::f64::consts::LN_10 / 400.0).abs() < 1e-9);
+    }
 }
@@ -27,6 +27,9 @@
 //!   per-cell view for grid-like layouts (tracks × cells).
 //! - [`TailAnchoredExtentModel`]: a wrapper that adds tail-anchoring helpers
 //!   for chat/log-style lists that stick to the end of content.
+//! - [`TreeExtentModel`]: a model over a hierarchical (outline/tree-view) data
+//!   description whose rows are the visible nodes, with `O(log n)` expand and
+//!   collapse and key-based scrolling via [`VirtualList::scroll_to_key`].
 //!
 //! This crate deliberately does **not** know about widgets, display trees, or any
 //! particular UI framework. Host frameworks are responsible for:
@@ -107,6 +110,7 @@ mod model;
 mod prefix_sum;
 mod scalar;
 mod tail_anchored;
+mod tree;
 mod virtual_list;
 
 pub use fixed::FixedExtentModel;
@@ -115,4 +119,5 @@ pub use model::{ExtentModel, ResizableExtentModel, VisibleStrip, compute_visible
 pub use prefix_sum::PrefixSumExtentModel;
 pub use scalar::Scalar;
 pub use tail_anchored::TailAnchoredExtentModel;
+pub use tree::{TreeExtentModel, TreeNode, TreeRow};
 pub use virtual_list::{ScrollAlign, VirtualList};
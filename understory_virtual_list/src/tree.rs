@@ -0,0 +1,713 @@
+// Copyright 2025 the Understory Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! An [`ExtentModel`] over a hierarchical (outline/tree-view) data description.
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{ExtentModel, Scalar};
+
+/// One node in the pre-order description passed to [`TreeExtentModel::rebuild`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TreeNode<K, S: Scalar> {
+    /// Host-defined key identifying the node.
+    pub key: K,
+    /// Depth of the node; roots have depth `0`.
+    pub depth: usize,
+    /// Extent of the node's own row.
+    pub extent: S,
+    /// Whether the node's children are shown.
+    pub expanded: bool,
+}
+
+impl<K, S: Scalar> TreeNode<K, S> {
+    /// Creates a collapsed node description.
+    #[must_use]
+    pub const fn new(key: K, depth: usize, extent: S) -> Self {
+        Self {
+            key,
+            depth,
+            extent,
+            expanded: false,
+        }
+    }
+
+    /// Returns this description with the given expanded state.
+    #[must_use]
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+}
+
+/// A visible row of a [`TreeExtentModel`], as returned by [`TreeExtentModel::row`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeRow<'a, K> {
+    /// Key of the node shown in this row.
+    pub key: &'a K,
+    /// Depth of the node; roots have depth `0`.
+    pub depth: usize,
+    /// Whether the node has any children.
+    pub has_children: bool,
+    /// Whether the node is expanded.
+    pub expanded: bool,
+}
+
+/// An [`ExtentModel`] whose rows are the visible nodes of a tree.
+///
+/// The model stores every node of a tree in pre-order, together with
+/// its depth, extent, and expanded state. The strip it exposes through
+/// [`ExtentModel`] contains only the *visible rows*: nodes whose ancestors are
+/// all expanded. Row `i` of the strip maps back to a node (key, depth, expanded
+/// state) via [`TreeExtentModel::row`].
+///
+/// Because a node's descendants are contiguous in pre-order, collapsing a node
+/// hides a single range of entries. The model keeps a segment tree over the
+/// pre-order entries that counts, per entry, how many collapsed ancestors it
+/// has; an entry is visible when that count is zero. Expanding or collapsing a
+/// node is therefore a single range update in `O(log n)`, and row/offset
+/// queries descend the same tree in `O(log n)`, regardless of how large the
+/// hidden subtree is.
+///
+/// Structural edits (inserting or removing nodes) are handled by rebuilding the
+/// model from a fresh pre-order description with [`TreeExtentModel::rebuild`].
+///
+/// ## Minimal example
+///
+/// ```rust
+/// use understory_virtual_list::{ScrollAlign, TreeExtentModel, TreeNode, VirtualList};
+///
+/// // root
+/// // ├── a
+/// // │   └── a1
+/// // └── b
+/// let mut model = TreeExtentModel::<&str, f32>::new();
+/// model.rebuild([
+///     TreeNode::new("root", 0, 20.0).expanded(true),
+///     TreeNode::new("a", 1, 20.0),
+///     TreeNode::new("a1", 2, 20.0),
+///     TreeNode::new("b", 1, 20.0),
+/// ]);
+///
+/// // `a` starts collapsed, so `a1` is hidden.
+/// let mut list = VirtualList::new(model, 40.0, 0.0);
+/// assert_eq!(list.visible_strip().content_extent, 60.0);
+///
+/// // Scrolling to a hidden node expands its ancestors first.
+/// let row = list.scroll_to_key(&"a1", ScrollAlign::Start);
+/// assert_eq!(row, Some(2));
+/// assert_eq!(list.scroll_offset(), 40.0);
+/// assert_eq!(list.model().row(2).map(|r| r.depth), Some(2));
+/// ```
+///
+/// Nodes are identified by a host-defined key `K`. Row indices handed out by
+/// the [`ExtentModel`] implementation are positions among the currently
+/// visible nodes and shift when nodes above them are expanded or collapsed;
+/// keys are stable and should be used to refer to nodes across such changes.
+#[derive(Debug, Clone)]
+pub struct TreeExtentModel<K, S: Scalar> {
+    keys: Vec<K>,
+    depths: Vec<usize>,
+    parents: Vec<Option<usize>>,
+    /// Exclusive end of each node's subtree in pre-order.
+    subtree_ends: Vec<usize>,
+    expanded: Vec<bool>,
+    positions: BTreeMap<K, usize>,
+    rows: HiddenCountTree<S>,
+}
+
+impl<K: Ord + Clone, S: Scalar> Default for TreeExtentModel<K, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Clone, S: Scalar> TreeExtentModel<K, S> {
+    /// Creates an empty model.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            keys: Vec::new(),
+            depths: Vec::new(),
+            parents: Vec::new(),
+            subtree_ends: Vec::new(),
+            expanded: Vec::new(),
+            positions: BTreeMap::new(),
+            rows: HiddenCountTree::new(&[], &[]),
+        }
+    }
+
+    /// Rebuilds the model from a pre-order sequence of nodes.
+    ///
+    /// Each node's depth must be at most one greater than the depth of the
+    /// node before it (the first node must have depth `0`); deeper values are
+    /// clamped in release builds. Keys are expected to be unique; when a key
+    /// repeats, key-based lookups resolve to its last occurrence.
+    ///
+    /// Any previous nodes and expanded state are discarded.
+    pub fn rebuild<I>(&mut self, nodes: I)
+    where
+        I: IntoIterator<Item = TreeNode<K, S>>,
+    {
+        self.keys.clear();
+        self.depths.clear();
+        self.parents.clear();
+        self.subtree_ends.clear();
+        self.expanded.clear();
+        self.positions.clear();
+
+        let mut extents = Vec::new();
+        let mut hidden = Vec::new();
+        // Ancestors of the current node, innermost last.
+        let mut stack: Vec<usize> = Vec::new();
+        for node in nodes {
+            let max_depth = stack.len();
+            debug_assert!(
+                node.depth <= max_depth,
+                "TreeExtentModel nodes must be in pre-order; depth {} follows depth {}",
+                node.depth,
+                max_depth.saturating_sub(1)
+            );
+            let depth = node.depth.min(max_depth);
+            let pos = self.keys.len();
+            while stack.len() > depth {
+                let done = stack.pop().unwrap();
+                self.subtree_ends[done] = pos;
+            }
+            debug_assert!(
+                node.extent.is_finite(),
+                "TreeExtentModel extents must be finite; got {:?}",
+                node.extent
+            );
+
+            let parent = stack.last().copied();
+            let hidden_count =
+                parent.map_or(0, |p| hidden[p] + if self.expanded[p] { 0 } else { 1 });
+
+            self.positions.insert(node.key.clone(), pos);
+            self.keys.push(node.key);
+            self.depths.push(depth);
+            self.parents.push(parent);
+            self.subtree_ends.push(pos + 1);
+            self.expanded.push(node.expanded);
+            extents.push(node.extent.clamp_non_negative());
+            hidden.push(hidden_count);
+            stack.push(pos);
+        }
+        let end = self.keys.len();
+        for done in stack {
+            self.subtree_ends[done] = end;
+        }
+
+        self.rows = HiddenCountTree::new(&extents, &hidden);
+    }
+
+    /// Returns the total number of nodes, including hidden ones.
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the model contains a node with `key`.
+    #[must_use]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    /// Returns information about the node shown in visible row `row`.
+    #[must_use]
+    pub fn row(&self, row: usize) -> Option<TreeRow<'_, K>> {
+        let pos = self.rows.position_of_row(row)?;
+        Some(TreeRow {
+            key: &self.keys[pos],
+            depth: self.depths[pos],
+            has_children: self.subtree_ends[pos] > pos + 1,
+            expanded: self.expanded[pos],
+        })
+    }
+
+    /// Returns the key of the node shown in visible row `row`.
+    #[must_use]
+    pub fn key_at_row(&self, row: usize) -> Option<&K> {
+        self.row(row).map(|r| r.key)
+    }
+
+    /// Returns the visible row of `key`, or `None` if the node is unknown or
+    /// hidden under a collapsed ancestor.
+    #[must_use]
+    pub fn row_of_key(&self, key: &K) -> Option<usize> {
+        let pos = *self.positions.get(key)?;
+        self.rows
+            .is_visible(pos)
+            .then(|| self.rows.rows_before(pos))
+    }
+
+    /// Returns the depth of `key`, if present.
+    #[must_use]
+    pub fn depth_of(&self, key: &K) -> Option<usize> {
+        self.positions.get(key).map(|&pos| self.depths[pos])
+    }
+
+    /// Returns the key of the parent of `key`, if `key` is present and not a root.
+    #[must_use]
+    pub fn parent_of(&self, key: &K) -> Option<&K> {
+        let pos = *self.positions.get(key)?;
+        self.parents[pos].map(|p| &self.keys[p])
+    }
+
+    /// Returns `true` if `key` is present and expanded.
+    #[must_use]
+    pub fn is_expanded(&self, key: &K) -> bool {
+        self.positions
+            .get(key)
+            .is_some_and(|&pos| self.expanded[pos])
+    }
+
+    /// Expands or collapses `key`.
+    ///
+    /// Returns `true` if the expanded state changed. Nodes without children
+    /// record the flag but do not affect visible rows until children are added
+    /// through [`TreeExtentModel::rebuild`].
+    pub fn set_expanded(&mut self, key: &K, expanded: bool) -> bool {
+        let Some(&pos) = self.positions.get(key) else {
+            return false;
+        };
+        self.set_expanded_at(pos, expanded)
+    }
+
+    /// Toggles the expanded state of `key`, returning the new state.
+    ///
+    /// Returns `false` if `key` is unknown.
+    pub fn toggle_expanded(&mut self, key: &K) -> bool {
+        let Some(&pos) = self.positions.get(key) else {
+            return false;
+        };
+        let expanded = !self.expanded[pos];
+        self.set_expanded_at(pos, expanded);
+        expanded
+    }
+
+    /// Expands every ancestor of `key` so that it becomes visible, returning its row.
+    ///
+    /// The node itself is left as is. Returns `None` if `key` is unknown.
+    pub fn reveal(&mut self, key: &K) -> Option<usize> {
+        let pos = *self.positions.get(key)?;
+        let mut ancestor = self.parents[pos];
+        while let Some(p) = ancestor {
+            self.set_expanded_at(p, true);
+            ancestor = self.parents[p];
+        }
+        Some(self.rows.rows_before(pos))
+    }
+
+    /// Updates the extent of the row for `key`.
+    ///
+    /// Returns `false` if `key` is unknown.
+    pub fn set_extent(&mut self, key: &K, extent: S) -> bool {
+        let Some(&pos) = self.positions.get(key) else {
+            return false;
+        };
+        self.set_extent_at(pos, extent);
+        true
+    }
+
+    /// Updates the extent of visible row `row`, for example after measuring it.
+    ///
+    /// Out-of-range rows are ignored.
+    pub fn set_row_extent(&mut self, row: usize, extent: S) {
+        if let Some(pos) = self.rows.position_of_row(row) {
+            self.set_extent_at(pos, extent);
+        }
+    }
+
+    fn set_extent_at(&mut self, pos: usize, extent: S) {
+        debug_assert!(
+            extent.is_finite(),
+            "TreeExtentModel extents must be finite; got {extent:?}"
+        );
+        self.rows.set_extent(pos, extent.clamp_non_negative());
+    }
+
+    fn set_expanded_at(&mut self, pos: usize, expanded: bool) -> bool {
+        if self.expanded[pos] == expanded {
+            return false;
+        }
+        self.expanded[pos] = expanded;
+        let delta = if expanded { -1 } else { 1 };
+        self.rows.add_hidden(pos + 1, self.subtree_ends[pos], delta);
+        true
+    }
+}
+
+impl<K: Ord + Clone, S: Scalar> ExtentModel for TreeExtentModel<K, S> {
+    type Scalar = S;
+
+    fn len(&self) -> usize {
+        self.rows.visible_rows()
+    }
+
+    fn total_extent(&mut self) -> S {
+        self.rows.visible_extent()
+    }
+
+    fn extent_of(&mut self, index: usize) -> S {
+        self.rows
+            .position_of_row(index)
+            .map_or_else(S::zero, |pos| self.rows.extent(pos))
+    }
+
+    fn offset_of(&mut self, index: usize) -> S {
+        self.rows.offset_of_row(index)
+    }
+
+    fn index_at_offset(&mut self, offset: S) -> usize {
+        self.rows.row_at_offset(offset.max(S::zero()))
+    }
+}
+
+/// Segment tree over pre-order entries tracking visible row counts and extents.
+///
+/// Each leaf holds an entry's extent and its number of collapsed ancestors
+/// (the "hidden count"). Internal nodes store the minimum hidden count in their
+/// range together with the number of entries and total extent at that minimum,
+/// so the visible (hidden count zero) totals of a range are available without
+/// visiting its leaves. Range updates to hidden counts are applied lazily.
+#[derive(Debug, Clone)]
+struct HiddenCountTree<S: Scalar> {
+    /// Number of leaves in use.
+    len: usize,
+    /// Number of leaves, rounded up to a power of two.
+    width: usize,
+    min_hidden: Vec<i64>,
+    count_at_min: Vec<usize>,
+    extent_at_min: Vec<S>,
+    pending: Vec<i64>,
+}
+
+/// Hidden count assigned to padding leaves so they never appear visible.
+const PADDING_HIDDEN: i64 = i64::MAX / 4;
+
+impl<S: Scalar> HiddenCountTree<S> {
+    fn new(extents: &[S], hidden: &[usize]) -> Self {
+        let len = extents.len();
+        let width = len.next_power_of_two().max(1);
+        let mut tree = Self {
+            len,
+            width,
+            min_hidden: vec![PADDING_HIDDEN; 2 * width],
+            count_at_min: vec![0; 2 * width],
+            extent_at_min: vec![S::zero(); 2 * width],
+            pending: vec![0; 2 * width],
+        };
+        for (i, (&extent, &hidden)) in extents.iter().zip(hidden).enumerate() {
+            let leaf = width + i;
+            tree.min_hidden[leaf] = i64::try_from(hidden).unwrap_or(PADDING_HIDDEN);
+            tree.count_at_min[leaf] = 1;
+            tree.extent_at_min[leaf] = extent;
+        }
+        for node in (1..width).rev() {
+            tree.pull(node);
+        }
+        tree
+    }
+
+    fn pull(&mut self, node: usize) {
+        let (l, r) = (2 * node, 2 * node + 1);
+        let (ml, mr) = (self.min_hidden[l], self.min_hidden[r]);
+        let min = ml.min(mr);
+        let mut count = 0;
+        let mut extent = S::zero();
+        if ml == min {
+            count += self.count_at_min[l];
+            extent = extent + self.extent_at_min[l];
+        }
+        if mr == min {
+            count += self.count_at_min[r];
+            extent = extent + self.extent_at_min[r];
+        }
+        self.min_hidden[node] = min + self.pending[node];
+        self.count_at_min[node] = count;
+        self.extent_at_min[node] = extent;
+    }
+
+    /// Visible row count and extent of `node`, given the pending adjustments of
+    /// its proper ancestors.
+    fn visible(&self, node: usize, above: i64) -> (usize, S) {
+        if self.min_hidden[node] + above == 0 {
+            (self.count_at_min[node], self.extent_at_min[node])
+        } else {
+            (0, S::zero())
+        }
+    }
+
+    fn visible_rows(&self) -> usize {
+        self.visible(1, 0).0
+    }
+
+    fn visible_extent(&self) -> S {
+        self.visible(1, 0).1
+    }
+
+    /// Adds `delta` to the hidden count of entries in `start..end`.
+    fn add_hidden(&mut self, start: usize, end: usize, delta: i64) {
+        if start < end {
+            self.add_hidden_in(1, 0, self.width, start, end.min(self.len), delta);
+        }
+    }
+
+    fn add_hidden_in(
+        &mut self,
+        node: usize,
+        lo: usize,
+        hi: usize,
+        start: usize,
+        end: usize,
+        delta: i64,
+    ) {
+        if end <= lo || hi <= start {
+            return;
+        }
+        if start <= lo && hi <= end {
+            self.min_hidden[node] += delta;
+            self.pending[node] += delta;
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.add_hidden_in(2 * node, lo, mid, start, end, delta);
+        self.add_hidden_in(2 * node + 1, mid, hi, start, end, delta);
+        self.pull(node);
+    }
+
+    fn set_extent(&mut self, pos: usize, extent: S) {
+        if pos >= self.len {
+            return;
+        }
+        let mut node = self.width + pos;
+        self.extent_at_min[node] = extent;
+        while node > 1 {
+            node /= 2;
+            self.pull(node);
+        }
+    }
+
+    fn extent(&self, pos: usize) -> S {
+        self.extent_at_min[self.width + pos]
+    }
+
+    /// Sum of pending adjustments on the proper ancestors of leaf `pos`.
+    fn pending_above_leaf(&self, pos: usize) -> i64 {
+        let mut node = (self.width + pos) / 2;
+        let mut total = 0;
+        while node >= 1 {
+            total += self.pending[node];
+            node /= 2;
+        }
+        total
+    }
+
+    fn is_visible(&self, pos: usize) -> bool {
+        pos < self.len && self.min_hidden[self.width + pos] + self.pending_above_leaf(pos) == 0
+    }
+
+    /// Number of visible entries before `pos`.
+    fn rows_before(&self, pos: usize) -> usize {
+        let (mut node, mut lo, mut hi) = (1, 0, self.width);
+        let mut above = 0;
+        let mut rows = 0;
+        while node < self.width {
+            above += self.pending[node];
+            let mid = lo + (hi - lo) / 2;
+            if pos < mid {
+                node *= 2;
+                hi = mid;
+            } else {
+                rows += self.visible(2 * node, above).0;
+                node = 2 * node + 1;
+                lo = mid;
+            }
+        }
+        rows
+    }
+
+    /// Pre-order position of visible row `row`.
+    fn position_of_row(&self, row: usize) -> Option<usize> {
+        if row >= self.visible_rows() {
+            return None;
+        }
+        let mut node = 1;
+        let mut above = 0;
+        let mut remaining = row;
+        while node < self.width {
+            above += self.pending[node];
+            let (left_rows, _) = self.visible(2 * node, above);
+            if remaining < left_rows {
+                node *= 2;
+            } else {
+                remaining -= left_rows;
+                node = 2 * node + 1;
+            }
+        }
+        Some(node - self.width)
+    }
+
+    /// Total extent of the visible rows before `row`.
+    fn offset_of_row(&self, row: usize) -> S {
+        if row >= self.visible_rows() {
+            return self.visible_extent();
+        }
+        let mut node = 1;
+        let mut above = 0;
+        let mut remaining = row;
+        let mut offset = S::zero();
+        while node < self.width {
+            above += self.pending[node];
+            let (left_rows, left_extent) = self.visible(2 * node, above);
+            if remaining < left_rows {
+                node *= 2;
+            } else {
+                remaining -= left_rows;
+                offset = offset + left_extent;
+                node = 2 * node + 1;
+            }
+        }
+        offset
+    }
+
+    /// Visible row containing `offset`, clamped to the last row.
+    fn row_at_offset(&self, offset: S) -> usize {
+        let (rows, extent) = self.visible(1, 0);
+        if rows == 0 {
+            return 0;
+        }
+        if offset >= extent {
+            return rows - 1;
+        }
+        let mut node = 1;
+        let mut above = 0;
+        let mut remaining = offset;
+        let mut row = 0;
+        while node < self.width {
+            above += self.pending[node];
+            let (left_rows, left_extent) = self.visible(2 * node, above);
+            if remaining < left_extent {
+                node *= 2;
+            } else {
+                remaining = remaining - left_extent;
+                row += left_rows;
+                node = 2 * node + 1;
+            }
+        }
+        row.min(rows - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{TreeExtentModel, TreeNode};
+    use crate::ExtentModel;
+
+    /// ```text
+    /// 0 a
+    /// 1   a1
+    /// 2     a1x
+    /// 3   a2
+    /// 4 b
+    /// 5   b1
+    /// ```
+    fn sample(expanded: bool) -> TreeExtentModel<&'static str, f32> {
+        let mut model = TreeExtentModel::new();
+        model.rebuild([
+            TreeNode::new("a", 0, 10.0).expanded(expanded),
+            TreeNode::new("a1", 1, 20.0).expanded(expanded),
+            TreeNode::new("a1x", 2, 30.0),
+            TreeNode::new("a2", 1, 40.0),
+            TreeNode::new("b", 0, 50.0).expanded(expanded),
+            TreeNode::new("b1", 1, 60.0),
+        ]);
+        model
+    }
+
+    fn visible_keys(model: &TreeExtentModel<&'static str, f32>) -> Vec<&'static str> {
+        (0..model.len())
+            .map(|row| *model.key_at_row(row).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn collapsed_tree_shows_only_roots() {
+        let mut model = sample(false);
+        assert_eq!(model.node_count(), 6);
+        assert_eq!(visible_keys(&model), ["a", "b"]);
+        assert_eq!(model.total_extent(), 60.0);
+        assert_eq!(model.offset_of(1), 10.0);
+        assert_eq!(model.extent_of(1), 50.0);
+        assert_eq!(model.row_of_key(&"b"), Some(1));
+        assert_eq!(model.row_of_key(&"a1"), None);
+    }
+
+    #[test]
+    fn expanded_tree_maps_rows_to_nodes_with_depth() {
+        let mut model = sample(true);
+        assert_eq!(visible_keys(&model), ["a", "a1", "a1x", "a2", "b", "b1"]);
+        assert_eq!(model.total_extent(), 210.0);
+        assert_eq!(model.offset_of(4), 100.0);
+
+        let row = model.row(2).unwrap();
+        assert_eq!(row.depth, 2);
+        assert!(!row.has_children);
+        let row = model.row(1).unwrap();
+        assert!(row.has_children && row.expanded);
+        assert_eq!(model.parent_of(&"a1x"), Some(&"a1"));
+    }
+
+    #[test]
+    fn expand_and_collapse_update_visible_rows() {
+        let mut model = sample(true);
+
+        // Collapsing `a` hides its whole subtree, including the expanded `a1`.
+        assert!(model.set_expanded(&"a", false));
+        assert_eq!(visible_keys(&model), ["a", "b", "b1"]);
+        assert_eq!(model.total_extent(), 120.0);
+
+        // Re-expanding restores the nested expanded state of `a1`.
+        assert!(model.toggle_expanded(&"a"));
+        assert_eq!(visible_keys(&model), ["a", "a1", "a1x", "a2", "b", "b1"]);
+
+        // Collapsing an inner node only hides its own subtree.
+        model.set_expanded(&"a1", false);
+        assert_eq!(visible_keys(&model), ["a", "a1", "a2", "b", "b1"]);
+        assert!(!model.set_expanded(&"a1", false));
+        assert_eq!(model.row_of_key(&"a2"), Some(2));
+    }
+
+    #[test]
+    fn index_at_offset_skips_hidden_rows() {
+        let mut model = sample(true);
+        model.set_expanded(&"a1", false);
+        // Rows: a[0,10) a1[10,30) a2[30,70) b[70,120) b1[120,180)
+        assert_eq!(model.index_at_offset(0.0), 0);
+        assert_eq!(model.index_at_offset(29.0), 1);
+        assert_eq!(model.index_at_offset(30.0), 2);
+        assert_eq!(model.index_at_offset(125.0), 4);
+        assert_eq!(model.index_at_offset(1000.0), 4);
+    }
+
+    #[test]
+    fn reveal_expands_ancestors_and_extents_update() {
+        let mut model = sample(false);
+        assert_eq!(model.reveal(&"a1x"), Some(2));
+        assert!(model.is_expanded(&"a") && model.is_expanded(&"a1"));
+        assert!(!model.is_expanded(&"b"));
+        assert_eq!(visible_keys(&model), ["a", "a1", "a1x", "a2", "b"]);
+
+        model.set_row_extent(2, 5.0);
+        assert!(model.set_extent(&"b1", 1.0));
+        assert_eq!(model.total_extent(), 125.0);
+        assert_eq!(model.offset_of(3), 35.0);
+        assert_eq!(model.reveal(&"missing"), None);
+    }
+}
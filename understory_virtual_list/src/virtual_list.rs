@@ -3,7 +3,10 @@
 
 //! A small controller that owns an [`ExtentModel`] and scroll state.
 
-use crate::{ExtentModel, Scalar, TailAnchoredExtentModel, VisibleStrip, compute_visible_strip};
+use crate::{
+    ExtentModel, Scalar, TailAnchoredExtentModel, TreeExtentModel, VisibleStrip,
+    compute_visible_strip,
+};
 
 /// Alignment mode when scrolling a specific index into view.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -231,10 +234,21 @@ impl<M: ExtentModel> VirtualList<M> {
     /// - [`ScrollAlign::Nearest`] moves just enough to make the item fully visible, preferring
     ///   the smallest change from the current scroll offset.
     pub fn scroll_to_index(&mut self, index: usize, align: ScrollAlign) {
+        let new_offset = self.scroll_offset_for_index(index, align);
+        self.set_scroll_offset(new_offset);
+    }
+
+    /// Returns the scroll offset that [`VirtualList::scroll_to_index`] would apply,
+    /// without changing the current scroll offset.
+    ///
+    /// This is useful for animating towards an item instead of jumping to it,
+    /// for example by easing from [`VirtualList::scroll_offset`] to the returned
+    /// offset over several frames.
+    #[must_use]
+    pub fn scroll_offset_for_index(&mut self, index: usize, align: ScrollAlign) -> M::Scalar {
         let len = self.model.len();
         if len == 0 {
-            self.set_scroll_offset(M::Scalar::zero());
-            return;
+            return M::Scalar::zero();
         }
         let idx = index.min(len.saturating_sub(1));
         let offset = self.model.offset_of(idx);
@@ -243,7 +257,7 @@ impl<M: ExtentModel> VirtualList<M> {
         let item_end = item_start + extent;
         let viewport = self.viewport_extent;
 
-        let new_offset = match align {
+        match align {
             ScrollAlign::Start => item_start,
             ScrollAlign::End => (item_end - viewport).max(M::Scalar::zero()),
             ScrollAlign::Center => {
@@ -266,9 +280,7 @@ impl<M: ExtentModel> VirtualList<M> {
                     (item_end - viewport).max(M::Scalar::zero())
                 }
             }
-        };
-
-        self.set_scroll_offset(new_offset);
+        }
     }
 }
 
@@ -307,10 +319,29 @@ impl<M: ExtentModel> VirtualList<TailAnchoredExtentModel<M>> {
     }
 }
 
+impl<K: Ord + Clone, S: Scalar> VirtualList<TreeExtentModel<K, S>> {
+    /// Scrolls so that the node with `key` is brought into view using the given alignment.
+    ///
+    /// Collapsed ancestors of the node are expanded first (see
+    /// [`TreeExtentModel::reveal`]), then this behaves like
+    /// [`VirtualList::scroll_to_index`] on the node's row.
+    ///
+    /// Returns the node's visible row, or `None` (leaving the scroll offset
+    /// unchanged) if `key` is unknown.
+    pub fn scroll_to_key(&mut self, key: &K, align: ScrollAlign) -> Option<usize> {
+        let row = self.model_mut().reveal(key)?;
+        self.scroll_to_index(row, align);
+        Some(row)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::ScrollAlign;
-    use crate::{FixedExtentModel, GridTrackModel, TailAnchoredExtentModel, VirtualList};
+    use crate::{
+        FixedExtentModel, GridTrackModel, TailAnchoredExtentModel, TreeExtentModel, TreeNode,
+        VirtualList,
+    };
 
     #[test]
     fn visible_strip_tracks_scroll_and_viewport() {
@@ -352,6 +383,11 @@ mod tests {
         let before = list.scroll_offset();
         list.scroll_to_index(3, ScrollAlign::Nearest);
         assert!((list.scroll_offset() - before).abs() < f32::EPSILON);
+
+        // Querying a target offset does not scroll.
+        let target = list.scroll_offset_for_index(8, ScrollAlign::Start);
+        assert!((target - 80.0_f32).abs() < f32::EPSILON);
+        assert!((list.scroll_offset() - before).abs() < f32::EPSILON);
     }
 
     #[test]
@@ -448,4 +484,31 @@ mod tests {
         list.stick_to_tail_if_anchored();
         assert!((list.scroll_offset() - 10.0_f32).abs() < f32::EPSILON);
     }
+
+    #[test]
+    fn scroll_to_key_expands_ancestors_and_aligns_row() {
+        let mut model = TreeExtentModel::<u32, f32>::new();
+        // Ten collapsed roots, each with two children.
+        model.rebuild((0..10_u32).flat_map(|i| {
+            [
+                TreeNode::new(i * 10, 0, 10.0),
+                TreeNode::new(i * 10 + 1, 1, 10.0),
+                TreeNode::new(i * 10 + 2, 1, 10.0),
+            ]
+        }));
+        let mut list = VirtualList::new(model, 30.0_f32, 0.0);
+        assert_eq!(list.visible_strip().content_extent, 100.0);
+
+        // Root 50 is row 5, so once it is expanded its second child, node 52, is row 7.
+        assert_eq!(list.scroll_to_key(&52, ScrollAlign::Start), Some(7));
+        assert!(list.model().is_expanded(&50));
+        assert!((list.scroll_offset() - 70.0_f32).abs() < f32::EPSILON);
+        let strip = list.visible_strip();
+        assert_eq!(strip.content_extent, 120.0);
+        assert_eq!(list.model().key_at_row(strip.start), Some(&52));
+
+        // Unknown keys leave the scroll offset alone.
+        assert_eq!(list.scroll_to_key(&999, ScrollAlign::Start), None);
+        assert!((list.scroll_offset() - 70.0_f32).abs() < f32::EPSILON);
+    }
 }